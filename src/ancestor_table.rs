@@ -0,0 +1,186 @@
+use super::VecTree;
+use generational_arena::Index;
+
+use std::collections::HashMap;
+use std::ops::Range;
+
+/// A binary-lifting table built from a `VecTree`, answering k-th ancestor and lowest common
+/// ancestor queries in O(log n).
+///
+/// The table also records the position of each node in tree order, the nodes of a subtree
+/// occupying a contiguous range of positions.
+///
+/// The table is a snapshot of the tree structure at the time it was built, it has to be rebuilt
+/// after the tree is modified. Nodes inserted afterwards are unknown to the table.
+///
+/// # Examples
+///
+/// ```
+/// use vec_tree::{AncestorTable, VecTree};
+///
+/// let mut tree = VecTree::new();
+/// let root = tree.insert_root(0);
+/// let child_1 = tree.insert(1, root);
+/// let child_2 = tree.insert(2, root);
+/// let grandchild = tree.insert(3, child_1);
+///
+/// let table = AncestorTable::new(&tree);
+///
+/// assert_eq!(table.kth_ancestor(grandchild, 2), Some(root));
+/// assert_eq!(table.lca(grandchild, child_2), Some(root));
+/// ```
+#[derive(Clone, Debug)]
+pub struct AncestorTable {
+    node_ids: Vec<Index>,
+    // Dense ids are the positions of the nodes in tree order.
+    dense_ids: HashMap<Index, usize>,
+    depths: Vec<u32>,
+    // `last_descendants[id]` is the dense id of the last node in tree order of the subtree
+    // rooted at `id`.
+    last_descendants: Vec<usize>,
+    // `ancestors[level][id]` is the `2^level`-th ancestor of `id`, or the root when the node is
+    // not that deep.
+    ancestors: Vec<Vec<usize>>,
+}
+
+impl AncestorTable {
+    /// Build the table for all the nodes of `tree`.
+    pub fn new<T>(tree: &VecTree<T>) -> AncestorTable {
+        let mut node_ids = Vec::new();
+        let mut dense_ids = HashMap::new();
+        let mut depths = Vec::new();
+        let mut parents = Vec::new();
+
+        if let Some(root_index) = tree.get_root_index() {
+            for (node_id, depth) in tree.descendants_with_depth(root_index) {
+                let id = node_ids.len();
+                // Parents come before their children in tree order.
+                let parent_id = tree.parent(node_id).map_or(id, |parent| dense_ids[&parent]);
+
+                node_ids.push(node_id);
+                dense_ids.insert(node_id, id);
+                depths.push(depth);
+                parents.push(parent_id);
+            }
+        }
+
+        // Children come after their parents, so walking backwards reaches a node after all its
+        // descendants.
+        let mut last_descendants = (0..node_ids.len()).collect::<Vec<_>>();
+        for id in (1..node_ids.len()).rev() {
+            let parent_id = parents[id];
+            last_descendants[parent_id] = last_descendants[parent_id].max(last_descendants[id]);
+        }
+
+        let max_depth = depths.iter().cloned().max().unwrap_or(0);
+        let mut ancestors = vec![parents];
+        while 1u64 << ancestors.len() <= u64::from(max_depth) {
+            let previous = &ancestors[ancestors.len() - 1];
+            let level = previous.iter().map(|&id| previous[id]).collect();
+            ancestors.push(level);
+        }
+
+        AncestorTable {
+            node_ids,
+            dense_ids,
+            depths,
+            last_descendants,
+            ancestors,
+        }
+    }
+
+    /// Return the depth of the node at index `node_id`.
+    ///
+    /// If the node was not in the tree when the table was built, `None` is returned.
+    pub fn depth(&self, node_id: Index) -> Option<u32> {
+        self.dense_ids.get(&node_id).map(|&id| self.depths[id])
+    }
+
+    /// Return the position of the node at index `node_id` in tree order, the root being at
+    /// position 0.
+    ///
+    /// If the node was not in the tree when the table was built, `None` is returned.
+    pub fn position(&self, node_id: Index) -> Option<usize> {
+        self.dense_ids.get(&node_id).cloned()
+    }
+
+    /// Return the range of positions in tree order of the subtree rooted at `node_id`, the node
+    /// itself included.
+    ///
+    /// A node is in the subtree if and only if its position is in the range.
+    ///
+    /// If the node was not in the tree when the table was built, `None` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::{AncestorTable, VecTree};
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(0);
+    /// let child_1 = tree.insert(1, root);
+    /// let grandchild = tree.insert(2, child_1);
+    /// let child_2 = tree.insert(3, root);
+    ///
+    /// let table = AncestorTable::new(&tree);
+    /// let range = table.descendant_range(child_1).unwrap();
+    ///
+    /// assert_eq!(range, 1..3);
+    /// assert!(range.contains(&table.position(grandchild).unwrap()));
+    /// assert!(!range.contains(&table.position(child_2).unwrap()));
+    /// ```
+    pub fn descendant_range(&self, node_id: Index) -> Option<Range<usize>> {
+        let id = *self.dense_ids.get(&node_id)?;
+        Some(id..self.last_descendants[id] + 1)
+    }
+
+    /// Return the `k`-th ancestor of the node at index `node_id`, the node itself being its 0-th
+    /// ancestor and its parent its 1st.
+    ///
+    /// If the node is less than `k` levels deep, or was not in the tree when the table was built,
+    /// `None` is returned.
+    pub fn kth_ancestor(&self, node_id: Index, k: u32) -> Option<Index> {
+        let id = *self.dense_ids.get(&node_id)?;
+        if k > self.depths[id] {
+            return None;
+        }
+
+        Some(self.node_ids[self.lift(id, k)])
+    }
+
+    /// Return the lowest common ancestor of the nodes at index `a` and `b`.
+    ///
+    /// If one of the nodes was not in the tree when the table was built, `None` is returned.
+    pub fn lca(&self, a: Index, b: Index) -> Option<Index> {
+        let mut a = *self.dense_ids.get(&a)?;
+        let mut b = *self.dense_ids.get(&b)?;
+
+        if self.depths[a] > self.depths[b] {
+            a = self.lift(a, self.depths[a] - self.depths[b]);
+        } else {
+            b = self.lift(b, self.depths[b] - self.depths[a]);
+        }
+
+        if a == b {
+            return Some(self.node_ids[a]);
+        }
+
+        for level in self.ancestors.iter().rev() {
+            if level[a] != level[b] {
+                a = level[a];
+                b = level[b];
+            }
+        }
+
+        Some(self.node_ids[self.ancestors[0][a]])
+    }
+
+    fn lift(&self, mut id: usize, k: u32) -> usize {
+        for (level, ancestors) in self.ancestors.iter().enumerate() {
+            if k & (1 << level) != 0 {
+                id = ancestors[id];
+            }
+        }
+        id
+    }
+}
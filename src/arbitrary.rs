@@ -0,0 +1,38 @@
+use super::VecTree;
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+/// Generate a tree with an arbitrary number of nodes, each node after the root being inserted as
+/// the last child of an arbitrary node inserted before it.
+///
+/// # Examples
+///
+/// ```
+/// use arbitrary::{Arbitrary, Unstructured};
+/// use vec_tree::VecTree;
+///
+/// let bytes = [4, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15];
+/// let tree = VecTree::<u8>::arbitrary(&mut Unstructured::new(&bytes)).unwrap();
+///
+/// let root = tree.get_root_index().unwrap();
+/// assert_eq!(tree.descendants(root).count(), tree.iter().count());
+/// ```
+impl<'a, T: Arbitrary<'a>> Arbitrary<'a> for VecTree<T> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<VecTree<T>> {
+        let len = u.arbitrary_len::<T>()?;
+        let mut tree = VecTree::with_capacity(len);
+        if len == 0 {
+            return Ok(tree);
+        }
+
+        let mut node_ids = Vec::with_capacity(len);
+        node_ids.push(tree.insert_root(T::arbitrary(u)?));
+
+        for _ in 1..len {
+            let parent_id = node_ids[u.choose_index(node_ids.len())?];
+            node_ids.push(tree.insert(T::arbitrary(u)?, parent_id));
+        }
+
+        Ok(tree)
+    }
+}
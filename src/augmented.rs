@@ -0,0 +1,167 @@
+use super::VecTree;
+use generational_arena::Index;
+
+use std::collections::HashMap;
+use std::ops;
+
+/// A `VecTree` maintaining, for each node, an aggregate of its subtree computed by a user
+/// defined function, like subtree sums or bounding boxes.
+///
+/// The aggregate of a node is computed by the combine function given at construction time, from
+/// the node’s data and the aggregates of its children in order. Aggregates are updated
+/// incrementally: an edit only recomputes the aggregates of the modified nodes and of their
+/// ancestors.
+///
+/// Read-only access to the underlying tree is provided through `Deref`, mutations must go through
+/// `AugmentedVecTree` methods so the aggregates stay in sync.
+///
+/// # Examples
+///
+/// ```
+/// use vec_tree::AugmentedVecTree;
+///
+/// // Maintain the sum of each subtree.
+/// let mut tree = AugmentedVecTree::new(|&value: &u32, children: &[&u32]| {
+///     value + children.iter().cloned().sum::<u32>()
+/// });
+/// let root = tree.insert_root(1);
+/// let child = tree.insert(2, root);
+/// tree.insert(3, child);
+///
+/// assert_eq!(tree.aggregate(root), Some(&6));
+///
+/// tree.modify(child, |value| *value = 10);
+/// assert_eq!(tree.aggregate(root), Some(&14));
+/// ```
+#[derive(Clone, Debug)]
+pub struct AugmentedVecTree<T, A> {
+    tree: VecTree<T>,
+    aggregates: HashMap<Index, A>,
+    combine: fn(&T, &[&A]) -> A,
+}
+
+impl<T, A> AugmentedVecTree<T, A> {
+    /// Constructs a new, empty `AugmentedVecTree` using `combine` to compute the aggregate of a
+    /// node from its data and the aggregates of its children.
+    pub fn new(combine: fn(&T, &[&A]) -> A) -> AugmentedVecTree<T, A> {
+        AugmentedVecTree {
+            tree: VecTree::new(),
+            aggregates: HashMap::new(),
+            combine,
+        }
+    }
+
+    /// Constructs a new, empty `AugmentedVecTree` with the specified capacity, using `combine` to
+    /// compute the aggregate of a node from its data and the aggregates of its children.
+    pub fn with_capacity(n: usize, combine: fn(&T, &[&A]) -> A) -> AugmentedVecTree<T, A> {
+        AugmentedVecTree {
+            tree: VecTree::with_capacity(n),
+            aggregates: HashMap::with_capacity(n),
+            combine,
+        }
+    }
+
+    /// Insert `data` into the tree as a root node, allocating more capacity if necessary.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a root node already exists.
+    pub fn insert_root(&mut self, data: T) -> Index {
+        let node_id = self.tree.insert_root(data);
+        self.update_from(Some(node_id));
+        node_id
+    }
+
+    /// Insert `data` into the tree as the last child of `parent_id`, allocating more capacity if
+    /// necessary.
+    pub fn insert(&mut self, data: T, parent_id: Index) -> Index {
+        let node_id = self.tree.insert(data, parent_id);
+        self.update_from(Some(node_id));
+        node_id
+    }
+
+    /// Remove the element at index `node_id` and its descendants from the tree.
+    ///
+    /// If the element at index `node_id` is still in the tree, then it is returned. If it is not
+    /// in the tree, then `None` is returned.
+    pub fn remove(&mut self, node_id: Index) -> Option<T> {
+        if !self.tree.contains(node_id) {
+            return None;
+        }
+
+        for descendant_id in self.tree.descendants(node_id) {
+            self.aggregates.remove(&descendant_id);
+        }
+
+        let parent_id = self.tree.parent(node_id);
+        let data = self.tree.remove(node_id);
+        self.update_from(parent_id);
+        data
+    }
+
+    /// Move the node at index `new_child_id`, and its descendants, to the last child position of
+    /// the node at index `node_id`.
+    pub fn append_child(&mut self, node_id: Index, new_child_id: Index) {
+        let old_parent_id = self.tree.parent(new_child_id);
+        self.tree.append_child(node_id, new_child_id);
+        self.update_from(old_parent_id);
+        self.update_from(Some(node_id));
+    }
+
+    /// Modify the data of the node at index `node_id` with `f`, updating the aggregates of the
+    /// node and its ancestors.
+    ///
+    /// Returns `None` without calling `f` if the node is not in the tree.
+    pub fn modify<F, R>(&mut self, node_id: Index, f: F) -> Option<R>
+    where
+        F: FnOnce(&mut T) -> R,
+    {
+        let result = f(self.tree.get_mut(node_id)?);
+        self.update_from(Some(node_id));
+        Some(result)
+    }
+
+    /// Return the aggregate of the subtree rooted at `node_id`.
+    pub fn aggregate(&self, node_id: Index) -> Option<&A> {
+        self.aggregates.get(&node_id)
+    }
+
+    /// Clear all the items inside the tree and their aggregates, but keep the tree allocation.
+    pub fn clear(&mut self) {
+        self.tree.clear();
+        self.aggregates.clear();
+    }
+
+    /// Return the underlying tree, dropping the aggregates.
+    pub fn into_inner(self) -> VecTree<T> {
+        self.tree
+    }
+
+    /// Recompute the aggregates of the node at index `node_id` and of its ancestors, the
+    /// aggregates of its children being up to date.
+    fn update_from(&mut self, node_id: Option<Index>) {
+        let mut node_id_opt = node_id;
+
+        while let Some(node_id) = node_id_opt {
+            let aggregate = {
+                let children = self
+                    .tree
+                    .children(node_id)
+                    .map(|child_id| &self.aggregates[&child_id])
+                    .collect::<Vec<_>>();
+                (self.combine)(&self.tree[node_id], &children)
+            };
+            self.aggregates.insert(node_id, aggregate);
+
+            node_id_opt = self.tree.parent(node_id);
+        }
+    }
+}
+
+impl<T, A> ops::Deref for AugmentedVecTree<T, A> {
+    type Target = VecTree<T>;
+
+    fn deref(&self) -> &VecTree<T> {
+        &self.tree
+    }
+}
@@ -0,0 +1,170 @@
+use super::VecTree;
+use generational_arena::Index;
+
+use std::io::{self, Read, Write};
+
+// The bytes every encoded tree starts with, followed by the format version.
+const MAGIC: &[u8; 4] = b"VTRE";
+const FORMAT_VERSION: u8 = 1;
+
+// The number of nodes reserved up front when reading a tree, so a corrupted node count does not
+// make us allocate an absurd amount of memory before failing.
+const MAX_RESERVED_NODES: usize = 1 << 16;
+
+impl<T> VecTree<T> {
+    /// Write the tree to `writer` in a compact binary format, using `write_data` to write the
+    /// data of each node.
+    ///
+    /// The tree is written as a header with a magic number and the format version, the number of
+    /// nodes, and then each node in tree order as its number of children followed by its data.
+    /// Numbers are written as LEB128 variable-length integers. Only the structure and the data
+    /// are written, the indices of the nodes are not preserved.
+    ///
+    /// The writer is used for many small writes, so it should be buffered.
+    ///
+    /// # Errors
+    ///
+    /// Fails if `writer` or `write_data` fail.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    /// use std::io::{Read, Write};
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(1u16);
+    /// tree.insert(2, root);
+    ///
+    /// let mut bytes = Vec::new();
+    /// tree.write_to(&mut bytes, |writer, data| writer.write_all(&data.to_le_bytes()))
+    ///     .unwrap();
+    ///
+    /// let copy = VecTree::read_from(&bytes[..], |reader| {
+    ///     let mut data = [0; 2];
+    ///     reader.read_exact(&mut data)?;
+    ///     Ok(u16::from_le_bytes(data))
+    /// })
+    /// .unwrap();
+    ///
+    /// assert_eq!(copy, tree);
+    /// ```
+    pub fn write_to<W, F>(&self, mut writer: W, mut write_data: F) -> io::Result<()>
+    where
+        W: Write,
+        F: FnMut(&mut W, &T) -> io::Result<()>,
+    {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&[FORMAT_VERSION])?;
+
+        let root_id = match self.root_index {
+            Some(root_id) => root_id,
+            None => return write_varint(&mut writer, 0),
+        };
+
+        write_varint(&mut writer, self.subtree_size(root_id) as u64)?;
+        for node_id in self.descendants(root_id) {
+            write_varint(&mut writer, self.child_count(node_id) as u64)?;
+            write_data(&mut writer, &self[node_id])?;
+        }
+
+        Ok(())
+    }
+
+    /// Read a tree written by `write_to` from `reader`, using `read_data` to read the data of
+    /// each node.
+    ///
+    /// The reader is used for many small reads, so it should be buffered.
+    ///
+    /// # Errors
+    ///
+    /// Fails with `io::ErrorKind::InvalidData` if the bytes are not a tree written by `write_to`,
+    /// or were written in another version of the format, and fails if `reader` or `read_data`
+    /// fail.
+    pub fn read_from<R, F>(mut reader: R, mut read_data: F) -> io::Result<VecTree<T>>
+    where
+        R: Read,
+        F: FnMut(&mut R) -> io::Result<T>,
+    {
+        let mut header = [0; 5];
+        reader.read_exact(&mut header)?;
+        if &header[..4] != MAGIC {
+            return Err(invalid_data("not a binary tree"));
+        }
+        if header[4] != FORMAT_VERSION {
+            return Err(invalid_data("unsupported binary tree format version"));
+        }
+
+        let node_count = read_varint(&mut reader)?;
+        let mut tree = VecTree::with_capacity(node_count.min(MAX_RESERVED_NODES as u64) as usize);
+        if node_count == 0 {
+            return Ok(tree);
+        }
+
+        let child_count = read_varint(&mut reader)?;
+        let root_id = tree.insert_root(read_data(&mut reader)?);
+
+        // The nodes still expecting children, with the number of children left to read.
+        let mut open_nodes: Vec<(Index, u64)> = vec![(root_id, child_count)];
+
+        for _ in 1..node_count {
+            while let Some(&(_, 0)) = open_nodes.last() {
+                open_nodes.pop();
+            }
+            let (parent_id, remaining) = match open_nodes.last_mut() {
+                Some(open_node) => open_node,
+                None => return Err(invalid_data("more nodes than children in binary tree")),
+            };
+            *remaining -= 1;
+            let parent_id = *parent_id;
+
+            let child_count = read_varint(&mut reader)?;
+            let node_id = tree.insert(read_data(&mut reader)?, parent_id);
+            open_nodes.push((node_id, child_count));
+        }
+
+        if open_nodes.iter().any(|&(_, remaining)| remaining > 0) {
+            return Err(invalid_data("fewer nodes than children in binary tree"));
+        }
+
+        Ok(tree)
+    }
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn write_varint<W: Write>(writer: &mut W, mut value: u64) -> io::Result<()> {
+    let mut bytes = [0; 10];
+    let mut len = 0;
+
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            bytes[len] = byte;
+            len += 1;
+            break;
+        }
+        bytes[len] = byte | 0x80;
+        len += 1;
+    }
+
+    writer.write_all(&bytes[..len])
+}
+
+fn read_varint<R: Read>(reader: &mut R) -> io::Result<u64> {
+    let mut value = 0u64;
+
+    for shift in (0..64).step_by(7) {
+        let mut byte = [0];
+        reader.read_exact(&mut byte)?;
+        value |= u64::from(byte[0] & 0x7f) << shift;
+        if byte[0] & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+
+    Err(invalid_data("variable-length integer too long"))
+}
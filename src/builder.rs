@@ -0,0 +1,125 @@
+use super::VecTree;
+use generational_arena::Index;
+
+use std::iter::FromIterator;
+
+/// An event consumed by a `TreeBuilder`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TreeEvent<T> {
+    /// Starts a new node holding the given data, as the last child of the currently open node.
+    Start(T),
+
+    /// Ends the currently open node.
+    End,
+}
+
+/// A builder creating a `VecTree` from a stream of start and end events, the inverse of
+/// `VecTree::traverse`.
+///
+/// This makes it possible to build a tree from a pull parser without recursion.
+///
+/// # Examples
+///
+/// ```
+/// use vec_tree::TreeBuilder;
+///
+/// let mut builder = TreeBuilder::new();
+/// builder.start("html");
+/// builder.start("head");
+/// builder.end();
+/// builder.start("body");
+/// builder.end();
+/// builder.end();
+/// let tree = builder.finish();
+///
+/// let root = tree.get_root_index().unwrap();
+/// let names = tree
+///     .descendants(root)
+///     .map(|node_id| tree[node_id])
+///     .collect::<Vec<_>>();
+///
+/// assert_eq!(names, ["html", "head", "body"]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct TreeBuilder<T> {
+    tree: VecTree<T>,
+    open_nodes: Vec<Index>,
+}
+
+impl<T> TreeBuilder<T> {
+    /// Constructs a new builder for an empty tree.
+    pub fn new() -> TreeBuilder<T> {
+        TreeBuilder {
+            tree: VecTree::new(),
+            open_nodes: Vec::new(),
+        }
+    }
+
+    /// Start a new node holding `data`, as the last child of the currently open node or as the
+    /// root if no node is open. The new node becomes the currently open node.
+    ///
+    /// # Panics
+    ///
+    /// Panics if no node is open and the root has already been created.
+    pub fn start(&mut self, data: T) -> Index {
+        let node_id = match self.open_nodes.last() {
+            Some(&parent_id) => self.tree.insert(data, parent_id),
+            None => self.tree.insert_root(data),
+        };
+        self.open_nodes.push(node_id);
+        node_id
+    }
+
+    /// End the currently open node, its parent becomes the currently open node.
+    ///
+    /// The index of the ended node is returned, or `None` if no node was open.
+    pub fn end(&mut self) -> Option<Index> {
+        self.open_nodes.pop()
+    }
+
+    /// Process an event, calling `start` or `end` accordingly.
+    pub fn event(&mut self, event: TreeEvent<T>) {
+        match event {
+            TreeEvent::Start(data) => {
+                self.start(data);
+            }
+            TreeEvent::End => {
+                self.end();
+            }
+        }
+    }
+
+    /// Return the number of nodes started but not ended yet.
+    pub fn open_count(&self) -> usize {
+        self.open_nodes.len()
+    }
+
+    /// Return the built tree.
+    ///
+    /// Nodes that are still open are kept in the tree, as if they had been ended.
+    pub fn finish(self) -> VecTree<T> {
+        self.tree
+    }
+}
+
+impl<T> Default for TreeBuilder<T> {
+    fn default() -> Self {
+        TreeBuilder::new()
+    }
+}
+
+impl<T> Extend<TreeEvent<T>> for TreeBuilder<T> {
+    fn extend<I: IntoIterator<Item = TreeEvent<T>>>(&mut self, iter: I) {
+        for event in iter {
+            self.event(event);
+        }
+    }
+}
+
+impl<T> FromIterator<TreeEvent<T>> for VecTree<T> {
+    fn from_iter<I: IntoIterator<Item = TreeEvent<T>>>(iter: I) -> Self {
+        let mut builder = TreeBuilder::new();
+        builder.extend(iter);
+        builder.finish()
+    }
+}
@@ -0,0 +1,126 @@
+use super::VecTree;
+use generational_arena::Index;
+
+use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+/// A `VecTree` behind a read-write lock, so it can be shared between threads (usually in an
+/// `Arc`), read by many of them at once and edited by one at a time.
+///
+/// Traversals go through the guard returned by `read`, which keeps the tree from changing while
+/// it is alive. Single edits can use the methods of `SyncVecTree` directly, and batches of edits
+/// the guard returned by `write`.
+///
+/// # Panics
+///
+/// All the methods panic if a thread panicked while editing the tree, as the tree may then be
+/// left in an inconsistent state.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Arc;
+/// use std::thread;
+/// use vec_tree::SyncVecTree;
+///
+/// let tree = Arc::new(SyncVecTree::new());
+/// let root = tree.insert_root(0);
+///
+/// let writers = (1..=4)
+///     .map(|i| {
+///         let tree = Arc::clone(&tree);
+///         thread::spawn(move || tree.insert(i, root))
+///     })
+///     .collect::<Vec<_>>();
+/// for writer in writers {
+///     writer.join().unwrap();
+/// }
+///
+/// let tree = tree.read();
+/// assert_eq!(tree.children_values(root).map(|(_, v)| v).sum::<i32>(), 10);
+/// ```
+#[derive(Debug)]
+pub struct SyncVecTree<T> {
+    tree: RwLock<VecTree<T>>,
+}
+
+impl<T> SyncVecTree<T> {
+    /// Constructs a new, empty `SyncVecTree`.
+    pub fn new() -> SyncVecTree<T> {
+        SyncVecTree::from(VecTree::new())
+    }
+
+    /// Lock the tree for reading, waiting for the current writer to finish if any.
+    pub fn read(&self) -> RwLockReadGuard<'_, VecTree<T>> {
+        self.tree.read().expect("The tree lock is poisoned")
+    }
+
+    /// Lock the tree for writing, waiting for the current readers and writer to finish if any.
+    pub fn write(&self) -> RwLockWriteGuard<'_, VecTree<T>> {
+        self.tree.write().expect("The tree lock is poisoned")
+    }
+
+    /// Insert `data` into the tree as a root node.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a root node already exists.
+    pub fn insert_root(&self, data: T) -> Index {
+        self.write().insert_root(data)
+    }
+
+    /// Insert `data` into the tree as the last child of `parent_id`.
+    pub fn insert(&self, data: T, parent_id: Index) -> Index {
+        self.write().insert(data, parent_id)
+    }
+
+    /// Remove the element at index `node_id` and its descendants from the tree.
+    ///
+    /// If the element at index `node_id` is still in the tree, then it is returned. If it is not
+    /// in the tree, then `None` is returned.
+    pub fn remove(&self, node_id: Index) -> Option<T> {
+        self.write().remove(node_id)
+    }
+
+    /// Move the node at index `new_child_id`, and its descendants, to the last child position of
+    /// the node at index `node_id`.
+    pub fn append_child(&self, node_id: Index, new_child_id: Index) {
+        self.write().append_child(node_id, new_child_id);
+    }
+
+    /// Modify the data of the node at index `node_id` with `f`.
+    ///
+    /// Returns `None` without calling `f` if the node is not in the tree.
+    pub fn modify<F, R>(&self, node_id: Index, f: F) -> Option<R>
+    where
+        F: FnOnce(&mut T) -> R,
+    {
+        self.write().get_mut(node_id).map(f)
+    }
+
+    /// Return a clone of the data of the node at index `node_id`, if it is in the tree.
+    pub fn get_cloned(&self, node_id: Index) -> Option<T>
+    where
+        T: Clone,
+    {
+        self.read().get(node_id).cloned()
+    }
+
+    /// Return the underlying tree.
+    pub fn into_inner(self) -> VecTree<T> {
+        self.tree.into_inner().expect("The tree lock is poisoned")
+    }
+}
+
+impl<T> Default for SyncVecTree<T> {
+    fn default() -> Self {
+        SyncVecTree::new()
+    }
+}
+
+impl<T> From<VecTree<T>> for SyncVecTree<T> {
+    fn from(tree: VecTree<T>) -> SyncVecTree<T> {
+        SyncVecTree {
+            tree: RwLock::new(tree),
+        }
+    }
+}
@@ -0,0 +1,180 @@
+use super::VecTree;
+use generational_arena::Index;
+
+impl<T> VecTree<T> {
+    /// Return a cursor pointing at the node at index `node_id`, allowing to navigate and edit the
+    /// tree from there.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node is not in the tree.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(0);
+    /// tree.insert(2, root);
+    ///
+    /// let mut cursor = tree.cursor_mut(root);
+    /// assert!(cursor.move_first_child());
+    /// cursor.insert_before(1);
+    /// cursor.insert_after(3);
+    /// *cursor.value_mut() *= 10;
+    ///
+    /// let values = tree
+    ///     .children(root)
+    ///     .map(|node_id| tree[node_id])
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(values, [1, 20, 3]);
+    /// ```
+    pub fn cursor_mut(&mut self, node_id: Index) -> CursorMut<'_, T> {
+        if !self.contains(node_id) {
+            panic!("The node you are trying to point to is invalid");
+        }
+
+        CursorMut {
+            tree: self,
+            current: node_id,
+        }
+    }
+}
+
+/// A cursor over a tree, pointing at a node, which can be moved around and edit the tree.
+#[derive(Debug)]
+pub struct CursorMut<'a, T: 'a> {
+    tree: &'a mut VecTree<T>,
+    current: Index,
+}
+
+impl<'a, T> CursorMut<'a, T> {
+    /// Return the index of the node the cursor points at.
+    pub fn current(&self) -> Index {
+        self.current
+    }
+
+    /// Return a shared reference to the data of the node the cursor points at.
+    pub fn value(&self) -> &T {
+        &self.tree[self.current]
+    }
+
+    /// Return an exclusive reference to the data of the node the cursor points at.
+    pub fn value_mut(&mut self) -> &mut T {
+        &mut self.tree[self.current]
+    }
+
+    /// Return a shared reference to the whole tree.
+    pub fn tree(&self) -> &VecTree<T> {
+        self.tree
+    }
+
+    /// Move the cursor to the parent of the current node.
+    ///
+    /// Returns `false`, without moving the cursor, if the current node is the root.
+    pub fn move_parent(&mut self) -> bool {
+        self.move_to(self.tree.parent(self.current))
+    }
+
+    /// Move the cursor to the first child of the current node.
+    ///
+    /// Returns `false`, without moving the cursor, if the current node has no children.
+    pub fn move_first_child(&mut self) -> bool {
+        self.move_to(self.tree.first_child(self.current))
+    }
+
+    /// Move the cursor to the last child of the current node.
+    ///
+    /// Returns `false`, without moving the cursor, if the current node has no children.
+    pub fn move_last_child(&mut self) -> bool {
+        self.move_to(self.tree.last_child(self.current))
+    }
+
+    /// Move the cursor to the next sibling of the current node.
+    ///
+    /// Returns `false`, without moving the cursor, if the current node is the last child of its
+    /// parent.
+    pub fn move_next_sibling(&mut self) -> bool {
+        self.move_to(self.tree.next_sibling(self.current))
+    }
+
+    /// Move the cursor to the previous sibling of the current node.
+    ///
+    /// Returns `false`, without moving the cursor, if the current node is the first child of its
+    /// parent.
+    pub fn move_previous_sibling(&mut self) -> bool {
+        self.move_to(self.tree.previous_sibling(self.current))
+    }
+
+    /// Insert `data` as the last child of the current node. The cursor does not move.
+    pub fn append_child(&mut self, data: T) -> Index {
+        self.tree.insert(data, self.current)
+    }
+
+    /// Insert `data` as the sibling right before the current node. The cursor does not move.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the current node is the root.
+    pub fn insert_before(&mut self, data: T) -> Index {
+        if self.tree.is_root(self.current) {
+            panic!("The root node cannot have siblings");
+        }
+
+        let node_id = self.tree.create_node(data);
+        self.tree.insert_before(self.current, node_id);
+        node_id
+    }
+
+    /// Insert `data` as the sibling right after the current node. The cursor does not move.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the current node is the root.
+    pub fn insert_after(&mut self, data: T) -> Index {
+        let parent_id = match self.tree.parent(self.current) {
+            Some(parent_id) => parent_id,
+            None => panic!("The root node cannot have siblings"),
+        };
+
+        match self.tree.next_sibling(self.current) {
+            Some(next_sibling) => {
+                let node_id = self.tree.create_node(data);
+                self.tree.insert_before(next_sibling, node_id);
+                node_id
+            }
+            None => self.tree.insert(data, parent_id),
+        }
+    }
+
+    /// Remove the current node and its descendants from the tree, and move the cursor to its
+    /// parent.
+    ///
+    /// The data of the removed node is returned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the current node is the root.
+    pub fn remove_current(&mut self) -> T {
+        let parent_id = match self.tree.parent(self.current) {
+            Some(parent_id) => parent_id,
+            None => panic!("The cursor cannot remove the root node"),
+        };
+
+        let data = self.tree.remove(self.current).unwrap();
+        self.current = parent_id;
+        data
+    }
+
+    fn move_to(&mut self, node_id: Option<Index>) -> bool {
+        match node_id {
+            Some(node_id) => {
+                self.current = node_id;
+                true
+            }
+            None => false,
+        }
+    }
+}
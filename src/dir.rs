@@ -0,0 +1,273 @@
+use super::VecTree;
+
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::fs::{self, Metadata};
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// What to do with the symbolic links found while loading a directory with `VecTree::from_dir`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SymlinkPolicy {
+    /// Leave the links out of the tree.
+    Skip,
+    /// Add the links to the tree as leaves, without following them.
+    Include,
+    /// Add the entries the links point to, loading the directories they point to unless it
+    /// would loop.
+    Follow,
+}
+
+/// The options of `VecTree::from_dir`.
+#[derive(Clone, Debug)]
+pub struct DirOptions {
+    max_depth: Option<u32>,
+    symlinks: SymlinkPolicy,
+}
+
+impl Default for DirOptions {
+    fn default() -> Self {
+        DirOptions::new()
+    }
+}
+
+impl DirOptions {
+    /// Return the default options: load the whole directory, including the symbolic links as
+    /// leaves.
+    pub fn new() -> DirOptions {
+        DirOptions {
+            max_depth: None,
+            symlinks: SymlinkPolicy::Include,
+        }
+    }
+
+    /// Only load the entries down to `max_depth`, the directory itself being at depth 0.
+    pub fn max_depth(mut self, max_depth: u32) -> DirOptions {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Set what to do with symbolic links.
+    pub fn symlinks(mut self, symlinks: SymlinkPolicy) -> DirOptions {
+        self.symlinks = symlinks;
+        self
+    }
+}
+
+/// The type of an entry loaded by `VecTree::from_dir`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DirEntryKind {
+    File,
+    Dir,
+    /// A symbolic link, which was not followed.
+    Symlink,
+    /// Anything else, such as a socket or a device.
+    Other,
+}
+
+/// An entry of a directory loaded by `VecTree::from_dir`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DirEntryData {
+    /// The name of the entry, or the path given to `VecTree::from_dir` for the root.
+    pub name: OsString,
+
+    /// The path of the entry, starting with the path given to `VecTree::from_dir`.
+    pub path: PathBuf,
+
+    /// The type of the entry.
+    pub kind: DirEntryKind,
+
+    /// The size of the entry in bytes, as given by its metadata.
+    pub size: u64,
+}
+
+/// An error met on an entry while loading a directory with `VecTree::from_dir`.
+#[derive(Debug)]
+pub struct DirError {
+    /// The path of the entry.
+    pub path: PathBuf,
+
+    /// The error.
+    pub error: io::Error,
+}
+
+impl VecTree<DirEntryData> {
+    /// Load the directory at `path` and its content into a tree, each directory having its
+    /// entries as children, sorted by name.
+    ///
+    /// The path itself is always followed if it is a symbolic link. Errors met on the entries
+    /// below it do not stop the loading: the entry is left out and the error is returned along
+    /// with the tree.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the metadata of `path` cannot be read.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use vec_tree::{DirEntryKind, DirOptions, VecTree};
+    ///
+    /// let (tree, errors) = VecTree::from_dir("src", &DirOptions::new().max_depth(2)).unwrap();
+    ///
+    /// // The size of the directory, computed from the sizes of the files.
+    /// let root = tree.get_root_index().unwrap();
+    /// let size = tree.fold_subtree(root, |entry, sizes: &[u64]| match entry.kind {
+    ///     DirEntryKind::File => entry.size,
+    ///     _ => sizes.iter().sum(),
+    /// });
+    /// println!("{} bytes, {} errors", size, errors.len());
+    /// ```
+    pub fn from_dir<P: AsRef<Path>>(
+        path: P,
+        options: &DirOptions,
+    ) -> io::Result<(VecTree<DirEntryData>, Vec<DirError>)> {
+        let path = path.as_ref();
+        let metadata = fs::metadata(path)?;
+
+        let mut tree = VecTree::new();
+        let mut errors = Vec::new();
+        let root_id = tree.insert_root(DirEntryData::new(
+            path.as_os_str().to_owned(),
+            path.to_path_buf(),
+            &metadata,
+        ));
+
+        // The canonical path of the directories being loaded, to detect symbolic link loops.
+        let mut canonical_paths = HashMap::new();
+        if options.symlinks == SymlinkPolicy::Follow && metadata.is_dir() {
+            match fs::canonicalize(path) {
+                Ok(canonical_path) => {
+                    canonical_paths.insert(root_id, canonical_path);
+                }
+                Err(error) => errors.push(DirError::new(path, error)),
+            }
+        }
+
+        // The directories left to load, with their depth.
+        let mut pending = Vec::new();
+        if metadata.is_dir() {
+            pending.push((root_id, 0));
+        }
+
+        while let Some((dir_id, depth)) = pending.pop() {
+            if options
+                .max_depth
+                .is_some_and(|max_depth| depth >= max_depth)
+            {
+                continue;
+            }
+
+            let dir_path = tree[dir_id].path.clone();
+            let entries = match read_dir_sorted(&dir_path) {
+                Ok(entries) => entries,
+                Err(error) => {
+                    errors.push(DirError::new(&dir_path, error));
+                    continue;
+                }
+            };
+
+            // Children are pushed in reverse, so the directories are loaded in tree order.
+            let mut subdirs = Vec::new();
+            for (name, entry_path) in entries {
+                let metadata = match entry_metadata(&entry_path, options.symlinks) {
+                    Ok(Some(metadata)) => metadata,
+                    Ok(None) => continue,
+                    Err(error) => {
+                        errors.push(DirError::new(&entry_path, error));
+                        continue;
+                    }
+                };
+
+                let is_dir = metadata.is_dir();
+                let canonical_path = if is_dir && options.symlinks == SymlinkPolicy::Follow {
+                    let canonical_path = match fs::canonicalize(&entry_path) {
+                        Ok(canonical_path) => canonical_path,
+                        Err(error) => {
+                            errors.push(DirError::new(&entry_path, error));
+                            continue;
+                        }
+                    };
+
+                    let is_loop = tree.ancestors(dir_id).any(|ancestor_id| {
+                        canonical_paths.get(&ancestor_id) == Some(&canonical_path)
+                    });
+                    if is_loop {
+                        let error = io::Error::other("symbolic link loop");
+                        errors.push(DirError::new(&entry_path, error));
+                        continue;
+                    }
+                    Some(canonical_path)
+                } else {
+                    None
+                };
+
+                let node_id = tree.insert(DirEntryData::new(name, entry_path, &metadata), dir_id);
+                if let Some(canonical_path) = canonical_path {
+                    canonical_paths.insert(node_id, canonical_path);
+                }
+                if is_dir {
+                    subdirs.push((node_id, depth + 1));
+                }
+            }
+            pending.extend(subdirs.into_iter().rev());
+        }
+
+        Ok((tree, errors))
+    }
+}
+
+impl DirEntryData {
+    fn new(name: OsString, path: PathBuf, metadata: &Metadata) -> DirEntryData {
+        let file_type = metadata.file_type();
+        let kind = if file_type.is_file() {
+            DirEntryKind::File
+        } else if file_type.is_dir() {
+            DirEntryKind::Dir
+        } else if file_type.is_symlink() {
+            DirEntryKind::Symlink
+        } else {
+            DirEntryKind::Other
+        };
+
+        DirEntryData {
+            name,
+            path,
+            kind,
+            size: metadata.len(),
+        }
+    }
+}
+
+impl DirError {
+    fn new(path: &Path, error: io::Error) -> DirError {
+        DirError {
+            path: path.to_path_buf(),
+            error,
+        }
+    }
+}
+
+// Return the name and path of the entries of the directory at `path`, sorted by name.
+fn read_dir_sorted(path: &Path) -> io::Result<Vec<(OsString, PathBuf)>> {
+    let mut entries = fs::read_dir(path)?
+        .map(|entry| entry.map(|entry| (entry.file_name(), entry.path())))
+        .collect::<io::Result<Vec<_>>>()?;
+    entries.sort();
+    Ok(entries)
+}
+
+// Return the metadata of the entry at `path` according to the symbolic link policy, or `None` if
+// the entry is to be skipped.
+fn entry_metadata(path: &Path, symlinks: SymlinkPolicy) -> io::Result<Option<Metadata>> {
+    let metadata = fs::symlink_metadata(path)?;
+    if !metadata.file_type().is_symlink() {
+        return Ok(Some(metadata));
+    }
+
+    match symlinks {
+        SymlinkPolicy::Skip => Ok(None),
+        SymlinkPolicy::Include => Ok(Some(metadata)),
+        SymlinkPolicy::Follow => fs::metadata(path).map(Some),
+    }
+}
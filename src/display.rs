@@ -0,0 +1,175 @@
+use super::{NodeEdge, VecTree};
+use generational_arena::Index;
+
+use std::fmt;
+
+/// Render the tree with one node per line, drawing the branches with box-drawing characters.
+///
+/// # Examples
+///
+/// ```
+/// use vec_tree::VecTree;
+///
+/// let mut tree = VecTree::new();
+/// let root = tree.insert_root("root");
+/// let child = tree.insert("child", root);
+/// tree.insert("grandchild", child);
+/// tree.insert("other child", root);
+///
+/// assert_eq!(
+///     tree.to_string(),
+///     "root\n\
+///      ├── child\n\
+///      │   └── grandchild\n\
+///      └── other child"
+/// );
+/// ```
+impl<T: fmt::Display> fmt::Display for VecTree<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let root_id = match self.root_index {
+            Some(root_id) => root_id,
+            None => return Ok(()),
+        };
+
+        // Whether each ancestor of the current node, below the root, is the last of its siblings.
+        let mut last_siblings = Vec::new();
+
+        for edge in self.traverse(root_id) {
+            match edge {
+                NodeEdge::Start(node_id) if node_id == root_id => write!(f, "{}", self[node_id])?,
+                NodeEdge::Start(node_id) => {
+                    f.write_str("\n")?;
+                    for &is_last in &last_siblings {
+                        f.write_str(if is_last { "    " } else { "│   " })?;
+                    }
+
+                    let is_last = self.next_sibling(node_id).is_none();
+                    f.write_str(if is_last { "└── " } else { "├── " })?;
+                    write!(f, "{}", self[node_id])?;
+                    last_siblings.push(is_last);
+                }
+                NodeEdge::End(node_id) if node_id == root_id => {}
+                NodeEdge::End(_) => {
+                    last_siblings.pop();
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Show the tree as nested nodes, a node with children being shown as a map from its data to the
+/// list of its children.
+///
+/// Use `VecTree::debug_flat` to show the underlying storage instead. Trees deeper than 64 levels
+/// are always shown that way, since nesting them would overflow the stack.
+///
+/// # Examples
+///
+/// ```
+/// use vec_tree::VecTree;
+///
+/// let mut tree = VecTree::new();
+/// let root = tree.insert_root(0);
+/// let child = tree.insert(1, root);
+/// tree.insert(2, child);
+/// tree.insert(3, root);
+///
+/// assert_eq!(format!("{:?}", tree), "VecTree({0: [{1: [2]}, 3]})");
+/// ```
+impl<T: fmt::Debug> fmt::Debug for VecTree<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Each level of nesting takes a few stack frames of the formatting machinery.
+        const MAX_NESTED_HEIGHT: u32 = 64;
+
+        let too_deep = self
+            .root_index
+            .and_then(|root_id| self.height(root_id))
+            .is_some_and(|height| height > MAX_NESTED_HEIGHT);
+        if too_deep {
+            return self.debug_flat().fmt(f);
+        }
+
+        let mut debug_tuple = f.debug_tuple("VecTree");
+        if let Some(root_id) = self.root_index {
+            debug_tuple.field(&DebugNode {
+                tree: self,
+                node_id: root_id,
+            });
+        }
+        debug_tuple.finish()
+    }
+}
+
+struct DebugNode<'a, T: 'a> {
+    tree: &'a VecTree<T>,
+    node_id: Index,
+}
+
+impl<'a, T: fmt::Debug> fmt::Debug for DebugNode<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let data = &self.tree[self.node_id];
+        if !self.tree.has_children(self.node_id) {
+            return data.fmt(f);
+        }
+
+        f.debug_map().entry(data, &DebugChildren(*self)).finish()
+    }
+}
+
+impl<'a, T> Clone for DebugNode<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T> Copy for DebugNode<'a, T> {}
+
+struct DebugChildren<'a, T: 'a>(DebugNode<'a, T>);
+
+impl<'a, T: fmt::Debug> fmt::Debug for DebugChildren<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let tree = self.0.tree;
+        f.debug_list()
+            .entries(
+                tree.children(self.0.node_id)
+                    .map(|node_id| DebugNode { tree, node_id }),
+            )
+            .finish()
+    }
+}
+
+impl<T> VecTree<T> {
+    /// Return a value showing the underlying storage of the tree when formatted with `Debug`,
+    /// with the parent, sibling and child links of each node.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// tree.insert_root(0);
+    ///
+    /// assert!(format!("{:?}", tree.debug_flat()).contains("first_child: None"));
+    /// ```
+    pub fn debug_flat(&self) -> DebugFlat<'_, T> {
+        DebugFlat(self)
+    }
+}
+
+/// A value showing the underlying storage of a tree when formatted with `Debug`, created by
+/// `VecTree::debug_flat`.
+pub struct DebugFlat<'a, T: 'a>(&'a VecTree<T>);
+
+impl<'a, T: fmt::Debug> fmt::Debug for DebugFlat<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("VecTree")
+            .field("nodes", &self.0.nodes)
+            .field("root_index", &self.0.root_index)
+            .field("version", &self.0.version)
+            .field("id", &self.0.id)
+            .finish()
+    }
+}
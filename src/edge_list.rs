@@ -0,0 +1,299 @@
+use super::{index_slot, VecTree};
+use generational_arena::Index;
+
+use std::collections::hash_map::{Entry, HashMap};
+use std::error::Error;
+use std::fmt;
+use std::io::{Read, Write};
+use std::str::FromStr;
+
+// A row of an edge list, once read.
+struct Row<T> {
+    line: u64,
+    id: String,
+    parent_id: String,
+    data: T,
+}
+
+impl<T> VecTree<T>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    /// Read a tree from a CSV edge list, made of a header and then one `id,parent_id,value`
+    /// row per node, the `parent_id` of the root being empty.
+    ///
+    /// The rows may come in any order, the children of a node being inserted in the order of
+    /// their rows. The tree is returned along with the index of the node of each id.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the CSV is malformed, if a value cannot be parsed, if an id is duplicated or
+    /// refers to a missing parent, or if there is not exactly one root with every node
+    /// descending from it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let csv = "\
+    /// id,parent_id,value
+    /// 2,1,20
+    /// 1,,10
+    /// 3,1,30
+    /// ";
+    ///
+    /// let (tree, ids) = VecTree::<i32>::read_csv_edges(csv.as_bytes()).unwrap();
+    ///
+    /// assert_eq!(tree[ids["1"]], 10);
+    /// assert_eq!(tree.get_root_index(), Some(ids["1"]));
+    /// assert_eq!(tree.children(ids["1"]).collect::<Vec<_>>(), [ids["2"], ids["3"]]);
+    /// ```
+    pub fn read_csv_edges<R: Read>(
+        reader: R,
+    ) -> Result<(VecTree<T>, HashMap<String, Index>), CsvEdgesError> {
+        let mut rows: Vec<Row<T>> = Vec::new();
+        let mut reader = csv::Reader::from_reader(reader);
+
+        for record in reader.records() {
+            let record = record?;
+            let line = record.position().map_or(0, |position| position.line());
+
+            if record.len() != 3 {
+                return Err(CsvEdgesError::InvalidRow { line });
+            }
+            let data = match record[2].parse::<T>() {
+                Ok(data) => data,
+                Err(error) => {
+                    return Err(CsvEdgesError::InvalidValue {
+                        line,
+                        message: error.to_string(),
+                    })
+                }
+            };
+
+            rows.push(Row {
+                line,
+                id: record[0].to_string(),
+                parent_id: record[1].to_string(),
+                data,
+            });
+        }
+
+        // The position of the row of each id, and the positions of the children of each row.
+        let mut positions = HashMap::with_capacity(rows.len());
+        for (position, row) in rows.iter().enumerate() {
+            match positions.entry(row.id.as_str()) {
+                Entry::Occupied(_) => {
+                    return Err(CsvEdgesError::DuplicateId {
+                        line: row.line,
+                        id: row.id.clone(),
+                    })
+                }
+                Entry::Vacant(entry) => entry.insert(position),
+            };
+        }
+
+        let mut root_position: Option<usize> = None;
+        let mut children = vec![Vec::new(); rows.len()];
+        for (position, row) in rows.iter().enumerate() {
+            if row.parent_id.is_empty() {
+                if let Some(root_position) = root_position {
+                    return Err(CsvEdgesError::MultipleRoots {
+                        first: rows[root_position].id.clone(),
+                        second: row.id.clone(),
+                    });
+                }
+                root_position = Some(position);
+                continue;
+            }
+
+            match positions.get(row.parent_id.as_str()) {
+                Some(&parent_position) => children[parent_position].push(position),
+                None => {
+                    return Err(CsvEdgesError::MissingParent {
+                        line: row.line,
+                        id: row.id.clone(),
+                        parent_id: row.parent_id.clone(),
+                    })
+                }
+            }
+        }
+
+        let mut tree = VecTree::with_capacity(rows.len());
+        let mut ids = HashMap::with_capacity(rows.len());
+        let root_position = match root_position {
+            Some(root_position) => root_position,
+            None if rows.is_empty() => return Ok((tree, ids)),
+            None => return Err(CsvEdgesError::NoRoot),
+        };
+
+        let mut rows = rows.into_iter().map(Some).collect::<Vec<_>>();
+        let root = rows[root_position].take().unwrap();
+        let root_id = tree.insert_root(root.data);
+        ids.insert(root.id, root_id);
+
+        // The rows left to insert, with the index of their parent, children being reversed so
+        // they are popped in order.
+        let mut pending = children[root_position]
+            .iter()
+            .rev()
+            .map(|&position| (position, root_id))
+            .collect::<Vec<_>>();
+
+        while let Some((position, parent_id)) = pending.pop() {
+            let row = rows[position].take().unwrap();
+            let node_id = tree.insert(row.data, parent_id);
+            ids.insert(row.id, node_id);
+            pending.extend(
+                children[position]
+                    .iter()
+                    .rev()
+                    .map(|&position| (position, node_id)),
+            );
+        }
+
+        // The rows left are in cycles, not descending from the root.
+        if let Some(row) = rows.into_iter().flatten().next() {
+            return Err(CsvEdgesError::Cycle {
+                line: row.line,
+                id: row.id,
+            });
+        }
+
+        Ok((tree, ids))
+    }
+}
+
+impl<T: fmt::Display> VecTree<T> {
+    /// Write the tree as a CSV edge list, with a header and then one `id,parent_id,value` row
+    /// per node in tree order, the `parent_id` of the root being empty.
+    ///
+    /// The id of a node is its slot, as returned by `index_slot`.
+    ///
+    /// # Errors
+    ///
+    /// Fails if `writer` fails.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root("root");
+    /// tree.insert("child, with a comma", root);
+    ///
+    /// let mut csv = Vec::new();
+    /// tree.write_csv_edges(&mut csv).unwrap();
+    ///
+    /// assert_eq!(
+    ///     String::from_utf8(csv).unwrap(),
+    ///     "id,parent_id,value\n0,,root\n1,0,\"child, with a comma\"\n"
+    /// );
+    /// ```
+    pub fn write_csv_edges<W: Write>(&self, writer: W) -> Result<(), CsvEdgesError> {
+        let mut writer = csv::Writer::from_writer(writer);
+        writer.write_record(["id", "parent_id", "value"])?;
+
+        if let Some(root_id) = self.root_index {
+            for node_id in self.descendants(root_id) {
+                let parent_id = self
+                    .parent(node_id)
+                    .map_or(String::new(), |parent_id| index_slot(parent_id).to_string());
+                writer.write_record([
+                    index_slot(node_id).to_string(),
+                    parent_id,
+                    self[node_id].to_string(),
+                ])?;
+            }
+        }
+
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+/// An error returned when reading or writing a CSV edge list.
+#[derive(Debug)]
+pub enum CsvEdgesError {
+    /// The CSV is malformed, or could not be read or written.
+    Csv(csv::Error),
+    /// The row does not have exactly three fields.
+    InvalidRow { line: u64 },
+    /// The value of the row could not be parsed.
+    InvalidValue { line: u64, message: String },
+    /// The id of the row was already used by a previous row.
+    DuplicateId { line: u64, id: String },
+    /// The parent of the row is not in the edge list.
+    MissingParent {
+        line: u64,
+        id: String,
+        parent_id: String,
+    },
+    /// No row has an empty parent id.
+    NoRoot,
+    /// Several rows have an empty parent id.
+    MultipleRoots { first: String, second: String },
+    /// The row does not descend from the root, its ancestors forming a cycle.
+    Cycle { line: u64, id: String },
+}
+
+impl From<csv::Error> for CsvEdgesError {
+    fn from(error: csv::Error) -> CsvEdgesError {
+        CsvEdgesError::Csv(error)
+    }
+}
+
+impl From<std::io::Error> for CsvEdgesError {
+    fn from(error: std::io::Error) -> CsvEdgesError {
+        CsvEdgesError::Csv(error.into())
+    }
+}
+
+impl fmt::Display for CsvEdgesError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CsvEdgesError::Csv(error) => error.fmt(f),
+            CsvEdgesError::InvalidRow { line } => {
+                write!(f, "line {}: expected id, parent id and value", line)
+            }
+            CsvEdgesError::InvalidValue { line, message } => {
+                write!(f, "line {}: invalid value: {}", line, message)
+            }
+            CsvEdgesError::DuplicateId { line, id } => {
+                write!(f, "line {}: duplicate id {:?}", line, id)
+            }
+            CsvEdgesError::MissingParent {
+                line,
+                id,
+                parent_id,
+            } => write!(
+                f,
+                "line {}: the parent {:?} of {:?} is missing",
+                line, parent_id, id
+            ),
+            CsvEdgesError::NoRoot => f.write_str("no row without parent id, the tree has no root"),
+            CsvEdgesError::MultipleRoots { first, second } => write!(
+                f,
+                "{:?} and {:?} have no parent id, the tree would have several roots",
+                first, second
+            ),
+            CsvEdgesError::Cycle { line, id } => write!(
+                f,
+                "line {}: {:?} does not descend from the root, its ancestors form a cycle",
+                line, id
+            ),
+        }
+    }
+}
+
+impl Error for CsvEdgesError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            CsvEdgesError::Csv(error) => Some(error),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use super::VecTree;
+use generational_arena::Index;
+
+use ego_tree::{NodeId, NodeRef, Tree};
+use std::collections::HashMap;
+
+impl<T: Clone> VecTree<T> {
+    /// Copy the subtree of an `ego_tree::Tree` rooted at `root` into a new tree, and return it
+    /// along with the index of the copy of each node.
+    ///
+    /// Pass `tree.root()` to copy the whole tree.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut ego = ego_tree::Tree::new("root");
+    /// let child = ego.root_mut().append("child").id();
+    ///
+    /// let (tree, indices) = VecTree::from_ego_tree(ego.root());
+    ///
+    /// assert_eq!(tree[indices[&child]], "child");
+    /// assert_eq!(tree.parent(indices[&child]), tree.get_root_index());
+    /// ```
+    pub fn from_ego_tree(root: NodeRef<'_, T>) -> (VecTree<T>, HashMap<NodeId, Index>) {
+        let mut tree = VecTree::new();
+        let mut indices = HashMap::new();
+
+        // Parents come before their children in tree order, so they are always inserted first.
+        for node in root.descendants() {
+            let data = node.value().clone();
+            let index = match node.parent() {
+                Some(parent) if node.id() != root.id() => tree.insert(data, indices[&parent.id()]),
+                _ => tree.insert_root(data),
+            };
+            indices.insert(node.id(), index);
+        }
+
+        (tree, indices)
+    }
+}
+
+impl<T> VecTree<T> {
+    /// Move the nodes of the tree into a new `ego_tree::Tree`, and return it along with the id
+    /// of each node in the new tree.
+    ///
+    /// Returns `None` if the tree has no root, since an `ego_tree::Tree` always has one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root("root");
+    /// let child = tree.insert("child", root);
+    ///
+    /// let (ego, node_ids) = tree.into_ego_tree().unwrap();
+    ///
+    /// assert_eq!(ego.root().id(), node_ids[&root]);
+    /// assert_eq!(ego.get(node_ids[&child]).unwrap().value(), &"child");
+    /// ```
+    pub fn into_ego_tree(mut self) -> Option<(Tree<T>, HashMap<Index, NodeId>)> {
+        let root_id = self.root_index?;
+        let order = self.descendants(root_id).collect::<Vec<_>>();
+
+        let mut node_ids = HashMap::with_capacity(order.len());
+        let root = self.nodes.remove(root_id).unwrap();
+        let mut ego = Tree::with_capacity(root.data, order.len());
+        node_ids.insert(root_id, ego.root().id());
+
+        for &index in &order[1..] {
+            let node = self.nodes.remove(index).unwrap();
+            // Every node but the root has a parent, inserted before it.
+            let parent_id = node_ids[&node.parent.unwrap()];
+            let node_id = ego.get_mut(parent_id).unwrap().append(node.data).id();
+            node_ids.insert(index, node_id);
+        }
+
+        Some((ego, node_ids))
+    }
+}
+
+/// Copy a whole `ego_tree::Tree`, use `VecTree::from_ego_tree` to get the index of each node too.
+impl<'a, T: Clone> From<&'a Tree<T>> for VecTree<T> {
+    fn from(tree: &'a Tree<T>) -> VecTree<T> {
+        VecTree::from_ego_tree(tree.root()).0
+    }
+}
@@ -0,0 +1,338 @@
+use super::{NodeEdge, VecTree};
+use generational_arena::Index;
+
+use std::collections::HashMap;
+use std::ops;
+
+// The largest distance between the numbers given to the nodes of an attached subtree, so it only
+// takes the slice of the gap it needs and leaves the rest free for the next insertions.
+const SPACING: u64 = 1 << 32;
+
+// A range of `2^i` numbers is sparse enough to be renumbered if it holds at most
+// `(2 / OVERFLOW_BASE)^i` edges. The closer to 1, the more edges a range may hold.
+const OVERFLOW_BASE: f64 = 1.25;
+
+/// A `VecTree` maintaining Euler-tour intervals for its nodes, allowing to check whether a node
+/// is an ancestor of another one in O(1).
+///
+/// Each node is given an entry and an exit number such that the interval of a node contains the
+/// intervals of all its descendants. Numbers are spread with gaps, so inserting or moving a node
+/// usually only numbers the new or moved nodes. When a gap is exhausted, only the smallest range
+/// of numbers around it that is sparse enough is renumbered, keeping insertions amortized
+/// O(log n) in the size of the tree.
+///
+/// Read-only access to the underlying tree is provided through `Deref`, structural mutations must
+/// go through `EulerTourVecTree` methods so the intervals stay in sync.
+///
+/// # Examples
+///
+/// ```
+/// use vec_tree::EulerTourVecTree;
+///
+/// let mut tree = EulerTourVecTree::new();
+/// let root = tree.insert_root(0);
+/// let child = tree.insert(1, root);
+/// let grandchild = tree.insert(2, child);
+///
+/// assert!(tree.is_ancestor_of(root, grandchild));
+/// assert!(!tree.is_ancestor_of(grandchild, child));
+/// ```
+#[derive(Clone, Debug)]
+pub struct EulerTourVecTree<T> {
+    tree: VecTree<T>,
+    intervals: HashMap<Index, (u64, u64)>,
+}
+
+impl<T> EulerTourVecTree<T> {
+    /// Constructs a new, empty `EulerTourVecTree`.
+    pub fn new() -> EulerTourVecTree<T> {
+        EulerTourVecTree {
+            tree: VecTree::new(),
+            intervals: HashMap::new(),
+        }
+    }
+
+    /// Constructs a new, empty `EulerTourVecTree` with the specified capacity.
+    pub fn with_capacity(n: usize) -> EulerTourVecTree<T> {
+        EulerTourVecTree {
+            tree: VecTree::with_capacity(n),
+            intervals: HashMap::with_capacity(n),
+        }
+    }
+
+    /// Insert `data` into the tree as a root node, allocating more capacity if necessary.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a root node already exists.
+    pub fn insert_root(&mut self, data: T) -> Index {
+        let node_id = self.tree.insert_root(data);
+        self.renumber();
+        node_id
+    }
+
+    /// Insert `data` into the tree as the last child of `parent_id`, allocating more capacity if
+    /// necessary.
+    pub fn insert(&mut self, data: T, parent_id: Index) -> Index {
+        let node_id = self.tree.insert(data, parent_id);
+        self.number_attached(node_id);
+        node_id
+    }
+
+    /// Remove the element at index `node_id` and its descendants from the tree.
+    ///
+    /// If the element at index `node_id` is still in the tree, then it is returned. If it is not
+    /// in the tree, then `None` is returned.
+    pub fn remove(&mut self, node_id: Index) -> Option<T> {
+        if !self.tree.contains(node_id) {
+            return None;
+        }
+
+        for descendant_id in self.tree.descendants(node_id) {
+            self.intervals.remove(&descendant_id);
+        }
+
+        self.tree.remove(node_id)
+    }
+
+    /// Move the node at index `new_child_id`, and its descendants, to the last child position of
+    /// the node at index `node_id`.
+    pub fn append_child(&mut self, node_id: Index, new_child_id: Index) {
+        self.tree.append_child(node_id, new_child_id);
+        self.number_attached(new_child_id);
+    }
+
+    /// Get an exclusive reference to the element at index `node_id` if it is in the tree.
+    pub fn get_mut(&mut self, node_id: Index) -> Option<&mut T> {
+        self.tree.get_mut(node_id)
+    }
+
+    /// Return the entry and exit numbers of the node at index `node_id`.
+    ///
+    /// The interval of a node strictly contains the intervals of its descendants, and is disjoint
+    /// from the intervals of the other nodes. Numbers change when the tree is modified.
+    pub fn interval(&self, node_id: Index) -> Option<(u64, u64)> {
+        self.intervals.get(&node_id).cloned()
+    }
+
+    /// Is the node at index `ancestor_id` a strict ancestor of the node at index `node_id`?
+    ///
+    /// Returns `false` if one of the nodes is not in the tree.
+    pub fn is_ancestor_of(&self, ancestor_id: Index, node_id: Index) -> bool {
+        match (self.interval(ancestor_id), self.interval(node_id)) {
+            (Some((ancestor_entry, ancestor_exit)), Some((entry, exit))) => {
+                ancestor_entry < entry && exit < ancestor_exit
+            }
+            _ => false,
+        }
+    }
+
+    /// Is the node at index `descendant_id` a strict descendant of the node at index `node_id`?
+    ///
+    /// Returns `false` if one of the nodes is not in the tree.
+    pub fn is_descendant_of(&self, descendant_id: Index, node_id: Index) -> bool {
+        self.is_ancestor_of(node_id, descendant_id)
+    }
+
+    /// Is the node at index `node_id` in the subtree rooted at `subtree_id`, the subtree root
+    /// included?
+    pub fn is_in_subtree(&self, node_id: Index, subtree_id: Index) -> bool {
+        match (self.interval(subtree_id), self.interval(node_id)) {
+            (Some((subtree_entry, subtree_exit)), Some((entry, exit))) => {
+                subtree_entry <= entry && exit <= subtree_exit
+            }
+            _ => false,
+        }
+    }
+
+    /// Clear all the items inside the tree, but keep its allocation.
+    pub fn clear(&mut self) {
+        self.tree.clear();
+        self.intervals.clear();
+    }
+
+    /// Return the underlying tree, dropping the intervals.
+    pub fn into_inner(self) -> VecTree<T> {
+        self.tree
+    }
+
+    /// Number the subtree rooted at `node_id`, which has just been attached to the tree, in the
+    /// gap left between the edges surrounding it in the Euler tour.
+    fn number_attached(&mut self, node_id: Index) {
+        let parent_id = match self.tree.parent(node_id) {
+            Some(parent_id) => parent_id,
+            None => return self.renumber(),
+        };
+
+        let low_edge = match self.tree.previous_sibling(node_id) {
+            Some(previous_sibling) => NodeEdge::End(previous_sibling),
+            None => NodeEdge::Start(parent_id),
+        };
+        let high_edge = match self.tree.next_sibling(node_id) {
+            Some(next_sibling) => NodeEdge::Start(next_sibling),
+            None => NodeEdge::End(parent_id),
+        };
+
+        let (low, high) = (self.number(low_edge), self.number(high_edge));
+        if !self.number_subtree(node_id, low, high) {
+            self.renumber_around(node_id, low_edge, high_edge);
+        }
+    }
+
+    /// Renumber the whole tree, spreading the numbers as much as possible.
+    fn renumber(&mut self) {
+        self.intervals.clear();
+        if let Some(root_index) = self.tree.get_root_index() {
+            let numbered = self.number_subtree(root_index, 0, u64::MAX);
+            debug_assert!(numbered);
+        }
+    }
+
+    /// Number the subtree rooted at `node_id`, placed between `low_edge` and `high_edge`, by
+    /// evenly renumbering the smallest aligned range of numbers around `low_edge` that is sparse
+    /// enough. Falls back to renumbering the whole tree if there is no such range.
+    fn renumber_around(
+        &mut self,
+        node_id: Index,
+        low_edge: NodeEdge<Index>,
+        high_edge: NodeEdge<Index>,
+    ) {
+        let subtree_edge_count = 2 * self.tree.subtree_size(node_id) as u128;
+        let low = u128::from(self.number(low_edge));
+
+        // The numbered edges of the range, walking away from the subtree on both sides.
+        let mut before = vec![low_edge];
+        let mut after = Vec::new();
+        let mut next_after = Some(high_edge);
+
+        for bits in 1..=64 {
+            let start = low >> bits << bits;
+            let end = start + (1 << bits);
+
+            while let Some(edge) = self.previous_edge(before[before.len() - 1]) {
+                if u128::from(self.number(edge)) < start {
+                    break;
+                }
+                before.push(edge);
+            }
+            while let Some(edge) = next_after {
+                if u128::from(self.number(edge)) >= end {
+                    break;
+                }
+                after.push(edge);
+                next_after = self.next_edge(edge);
+            }
+
+            let edge_count = before.len() as u128 + subtree_edge_count + after.len() as u128;
+            if edge_count as f64 > (2.0 / OVERFLOW_BASE).powi(bits) {
+                continue;
+            }
+
+            let edges: Vec<_> = before
+                .iter()
+                .rev()
+                .cloned()
+                .chain(self.tree.traverse(node_id))
+                .chain(after)
+                .collect();
+            let step = (end - start) / (edge_count + 1);
+            for (position, edge) in edges.into_iter().enumerate() {
+                let number = start + step * (position as u128 + 1);
+                set_number(&mut self.intervals, edge, number as u64);
+            }
+            return;
+        }
+
+        self.renumber();
+    }
+
+    /// Number the subtree rooted at `node_id` using numbers strictly between `low` and `high`,
+    /// starting right after `low`.
+    ///
+    /// Returns `false`, without numbering anything, if there is not enough room.
+    fn number_subtree(&mut self, node_id: Index, low: u64, high: u64) -> bool {
+        let edge_count = 2 * self.tree.subtree_size(node_id) as u64;
+        let step = ((high - low) / (edge_count + 1)).min(SPACING);
+        if step == 0 {
+            return false;
+        }
+
+        let mut number = low;
+        for edge in self.tree.traverse(node_id) {
+            number += step;
+            set_number(&mut self.intervals, edge, number);
+        }
+
+        true
+    }
+
+    fn number(&self, edge: NodeEdge<Index>) -> u64 {
+        match edge {
+            NodeEdge::Start(node_id) => self.intervals[&node_id].0,
+            NodeEdge::End(node_id) => self.intervals[&node_id].1,
+        }
+    }
+
+    // The edge before `edge` in the Euler tour of the whole tree.
+    fn previous_edge(&self, edge: NodeEdge<Index>) -> Option<NodeEdge<Index>> {
+        match edge {
+            NodeEdge::Start(node_id) => match self.tree.previous_sibling(node_id) {
+                Some(previous_sibling) => Some(NodeEdge::End(previous_sibling)),
+                None => self.tree.parent(node_id).map(NodeEdge::Start),
+            },
+            NodeEdge::End(node_id) => match self.tree.last_child(node_id) {
+                Some(last_child) => Some(NodeEdge::End(last_child)),
+                None => Some(NodeEdge::Start(node_id)),
+            },
+        }
+    }
+
+    // The edge after `edge` in the Euler tour of the whole tree.
+    fn next_edge(&self, edge: NodeEdge<Index>) -> Option<NodeEdge<Index>> {
+        match edge {
+            NodeEdge::Start(node_id) => match self.tree.first_child(node_id) {
+                Some(first_child) => Some(NodeEdge::Start(first_child)),
+                None => Some(NodeEdge::End(node_id)),
+            },
+            NodeEdge::End(node_id) => match self.tree.next_sibling(node_id) {
+                Some(next_sibling) => Some(NodeEdge::Start(next_sibling)),
+                None => self.tree.parent(node_id).map(NodeEdge::End),
+            },
+        }
+    }
+}
+
+fn set_number(intervals: &mut HashMap<Index, (u64, u64)>, edge: NodeEdge<Index>, number: u64) {
+    match edge {
+        NodeEdge::Start(node_id) => intervals.entry(node_id).or_insert((number, number)).0 = number,
+        NodeEdge::End(node_id) => intervals.entry(node_id).or_insert((number, number)).1 = number,
+    }
+}
+
+impl<T> Default for EulerTourVecTree<T> {
+    fn default() -> Self {
+        EulerTourVecTree::new()
+    }
+}
+
+impl<T> ops::Deref for EulerTourVecTree<T> {
+    type Target = VecTree<T>;
+
+    fn deref(&self) -> &VecTree<T> {
+        &self.tree
+    }
+}
+
+impl<T> ops::Index<Index> for EulerTourVecTree<T> {
+    type Output = T;
+
+    fn index(&self, index: Index) -> &T {
+        &self.tree[index]
+    }
+}
+
+impl<T> ops::IndexMut<Index> for EulerTourVecTree<T> {
+    fn index_mut(&mut self, index: Index) -> &mut T {
+        &mut self.tree[index]
+    }
+}
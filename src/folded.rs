@@ -0,0 +1,81 @@
+use super::{NodeEdge, VecTree};
+
+use std::fmt::{self, Write};
+
+impl<T> VecTree<T> {
+    /// Return the tree in the folded stacks format read by flame graph tools such as `inferno`
+    /// or `flamegraph.pl`.
+    ///
+    /// Each node whose `weight` is not zero gives a line with the `label` of its ancestors and
+    /// itself separated by `;`, followed by a space and its weight. The weight is the node's
+    /// own, the tools adding up the weights of the children to compute the width of their
+    /// parent. Semicolons and line breaks in the labels are replaced by `_`, since they would
+    /// break the format.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// // The time spent in each function, excluding the functions it called.
+    /// let mut tree = VecTree::new();
+    /// let main = tree.insert_root(("main", 1));
+    /// let parse = tree.insert(("parse", 5), main);
+    /// tree.insert(("alloc", 2), parse);
+    /// tree.insert(("render", 3), main);
+    ///
+    /// let folded = tree.to_folded_stacks(|(_, time)| *time, |(name, _)| *name);
+    ///
+    /// assert_eq!(
+    ///     folded,
+    ///     "main 1\n\
+    ///      main;parse 5\n\
+    ///      main;parse;alloc 2\n\
+    ///      main;render 3\n"
+    /// );
+    /// ```
+    pub fn to_folded_stacks<W, L, D>(&self, weight: W, label: L) -> String
+    where
+        W: Fn(&T) -> u64,
+        L: Fn(&T) -> D,
+        D: fmt::Display,
+    {
+        let mut folded = String::new();
+        let root_id = match self.root_index {
+            Some(root_id) => root_id,
+            None => return folded,
+        };
+
+        // The stack of the current node, and the length of the stack of each of its ancestors.
+        let mut stack = String::new();
+        let mut stack_lens = Vec::new();
+        let mut label_buf = String::new();
+
+        for edge in self.traverse(root_id) {
+            match edge {
+                NodeEdge::Start(node_id) => {
+                    stack_lens.push(stack.len());
+                    if node_id != root_id {
+                        stack.push(';');
+                    }
+
+                    label_buf.clear();
+                    // Writing to a `String` cannot fail.
+                    write!(label_buf, "{}", label(&self[node_id])).unwrap();
+                    stack.extend(label_buf.chars().map(|c| match c {
+                        ';' | '\n' | '\r' => '_',
+                        c => c,
+                    }));
+
+                    let weight = weight(&self[node_id]);
+                    if weight != 0 {
+                        writeln!(folded, "{} {}", stack, weight).unwrap();
+                    }
+                }
+                NodeEdge::End(_) => stack.truncate(stack_lens.pop().unwrap()),
+            }
+        }
+
+        folded
+    }
+}
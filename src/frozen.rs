@@ -0,0 +1,76 @@
+use super::VecTree;
+
+use std::ops;
+use std::sync::Arc;
+
+impl<T> VecTree<T> {
+    /// Consume the tree and return an immutable snapshot of it, which can be cloned cheaply and
+    /// shared between threads without a lock.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::thread;
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(1);
+    /// tree.insert(2, root);
+    /// tree.insert(3, root);
+    ///
+    /// let frozen = tree.freeze();
+    /// let workers = (0..2)
+    ///     .map(|_| {
+    ///         let frozen = frozen.clone();
+    ///         thread::spawn(move || frozen.descendants_values(root).map(|(_, v)| v).sum::<i32>())
+    ///     })
+    ///     .collect::<Vec<_>>();
+    ///
+    /// for worker in workers {
+    ///     assert_eq!(worker.join().unwrap(), 6);
+    /// }
+    /// ```
+    pub fn freeze(self) -> FrozenVecTree<T> {
+        FrozenVecTree(Arc::new(self))
+    }
+}
+
+/// An immutable snapshot of a tree, created by `VecTree::freeze`.
+///
+/// Only the read API of the tree is available, through `Deref`. Cloning the snapshot only clones
+/// a reference to it, and the snapshot is `Send` and `Sync` whenever the data is.
+#[derive(Debug)]
+pub struct FrozenVecTree<T>(Arc<VecTree<T>>);
+
+impl<T> FrozenVecTree<T> {
+    /// Return a mutable tree with the content of the snapshot, the indices of the snapshot
+    /// staying valid in it.
+    ///
+    /// The tree is only copied if other clones of the snapshot are still alive.
+    pub fn thaw(self) -> VecTree<T>
+    where
+        T: Clone,
+    {
+        Arc::try_unwrap(self.0).unwrap_or_else(|tree| (*tree).clone())
+    }
+}
+
+impl<T> Clone for FrozenVecTree<T> {
+    fn clone(&self) -> Self {
+        FrozenVecTree(Arc::clone(&self.0))
+    }
+}
+
+impl<T> ops::Deref for FrozenVecTree<T> {
+    type Target = VecTree<T>;
+
+    fn deref(&self) -> &VecTree<T> {
+        &self.0
+    }
+}
+
+impl<T> From<VecTree<T>> for FrozenVecTree<T> {
+    fn from(tree: VecTree<T>) -> FrozenVecTree<T> {
+        tree.freeze()
+    }
+}
@@ -0,0 +1,106 @@
+use super::VecTree;
+use generational_arena::Index;
+
+/// Return the slot of `index`, that is its position in the tree storage.
+///
+/// The slot of a removed node is reused by later insertions, with a greater generation.
+///
+/// # Examples
+///
+/// ```
+/// use vec_tree::VecTree;
+///
+/// let mut tree = VecTree::new();
+/// let root = tree.insert_root(0);
+/// let child = tree.insert(1, root);
+/// tree.remove(child);
+/// let new_child = tree.insert(2, root);
+///
+/// assert_eq!(vec_tree::index_slot(new_child), vec_tree::index_slot(child));
+/// assert!(vec_tree::index_generation(new_child) > vec_tree::index_generation(child));
+/// ```
+pub fn index_slot(index: Index) -> usize {
+    index.into_raw_parts().0
+}
+
+/// Return the generation of `index`, that is the number of removals the tree had seen when the
+/// node was inserted.
+pub fn index_generation(index: Index) -> u64 {
+    index.into_raw_parts().1
+}
+
+/// Split `index` into its slot and generation, to store it as plain integers.
+///
+/// # Examples
+///
+/// ```
+/// use vec_tree::VecTree;
+///
+/// let mut tree = VecTree::new();
+/// let root = tree.insert_root(0);
+///
+/// // Pack the index into a single integer, for instance to send it over FFI.
+/// let (slot, generation) = vec_tree::index_to_raw_parts(root);
+/// let packed = (generation << 32) | slot as u64;
+///
+/// let unpacked = vec_tree::index_from_raw_parts((packed & 0xffff_ffff) as usize, packed >> 32);
+/// assert_eq!(unpacked, root);
+/// assert_eq!(tree[unpacked], 0);
+/// ```
+pub fn index_to_raw_parts(index: Index) -> (usize, u64) {
+    index.into_raw_parts()
+}
+
+/// Rebuild an index from the slot and generation returned by `index_to_raw_parts`.
+///
+/// Nothing checks that the parts come from a real index: a made-up index is not in the tree, or
+/// designates whichever node currently has this slot and generation.
+pub fn index_from_raw_parts(slot: usize, generation: u64) -> Index {
+    Index::from_raw_parts(slot, generation)
+}
+
+impl<T> VecTree<T> {
+    /// Is `index` a dangling reference to a node that was removed from the tree?
+    ///
+    /// Unlike `!contains(index)`, this returns `false` for indices whose slot the tree never
+    /// allocated, which most likely come from another tree.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(0);
+    /// let child = tree.insert(1, root);
+    /// assert!(!tree.is_stale(child));
+    ///
+    /// tree.remove(child);
+    /// assert!(tree.is_stale(child));
+    /// ```
+    pub fn is_stale(&self, index: Index) -> bool {
+        index_slot(index) < self.nodes.capacity() && !self.contains(index)
+    }
+
+    /// Return the index of the node currently stored in `slot`, if any.
+    ///
+    /// This allows to find which node replaced a stale index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(0);
+    /// let child = tree.insert(1, root);
+    /// tree.remove(child);
+    /// assert_eq!(tree.current_index(vec_tree::index_slot(child)), None);
+    ///
+    /// let new_child = tree.insert(2, root);
+    /// assert_eq!(tree.current_index(vec_tree::index_slot(child)), Some(new_child));
+    /// ```
+    pub fn current_index(&self, slot: usize) -> Option<Index> {
+        self.nodes.get_unknown_gen(slot).map(|(_, index)| index)
+    }
+}
@@ -0,0 +1,156 @@
+use super::VecTree;
+use generational_arena::Index;
+
+use std::error::Error;
+use std::fmt::{self, Write};
+
+impl VecTree<String> {
+    /// Parse an outline, where each line is a node and the children of a node are the following
+    /// lines indented by one more level, each level being `indent_width` spaces.
+    ///
+    /// Blank lines are skipped, and an empty outline gives a tree with no root. Since the tree has
+    /// a single root, only the first line may be unindented.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the indentation of a line is not a multiple of `indent_width` spaces, if a line is
+    /// indented by more than one level below the previous one, or if several lines are
+    /// unindented.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `indent_width` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let outline = "\
+    /// todo
+    ///   groceries
+    ///     milk
+    ///   laundry
+    /// ";
+    ///
+    /// let tree = VecTree::from_indented_str(outline, 2).unwrap();
+    /// let root = tree.get_root_index().unwrap();
+    ///
+    /// assert_eq!(tree[root], "todo");
+    /// assert_eq!(tree.subtree_size(root), 4);
+    /// assert_eq!(tree.to_indented_string(2), outline);
+    /// ```
+    pub fn from_indented_str(
+        s: &str,
+        indent_width: usize,
+    ) -> Result<VecTree<String>, ParseIndentError> {
+        if indent_width == 0 {
+            panic!("The indent width must be positive");
+        }
+
+        let mut tree = VecTree::new();
+
+        // The index of the last node seen at each depth, from the root to the previous line.
+        let mut open_nodes: Vec<Index> = Vec::new();
+
+        for (line_index, line) in s.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let error = |kind| ParseIndentError {
+                line: line_index + 1,
+                kind,
+            };
+
+            let content = line.trim_start_matches(' ');
+            let indent = line.len() - content.len();
+            if indent % indent_width != 0 || content.starts_with(char::is_whitespace) {
+                return Err(error(ParseIndentErrorKind::InvalidIndent));
+            }
+
+            let depth = indent / indent_width;
+            if depth > open_nodes.len() {
+                return Err(error(ParseIndentErrorKind::TooDeep));
+            }
+
+            let node_id = if depth == 0 {
+                if tree.get_root_index().is_some() {
+                    return Err(error(ParseIndentErrorKind::SeveralRoots));
+                }
+                tree.insert_root(content.to_string())
+            } else {
+                tree.insert(content.to_string(), open_nodes[depth - 1])
+            };
+
+            open_nodes.truncate(depth);
+            open_nodes.push(node_id);
+        }
+
+        Ok(tree)
+    }
+}
+
+impl<T: fmt::Display> VecTree<T> {
+    /// Write the tree as an outline, in tree order, with one node per line indented by
+    /// `indent_width` spaces per level of depth.
+    ///
+    /// The outline can be parsed back with `from_indented_str` as long as the data of the nodes
+    /// do not span several lines or start with whitespace.
+    pub fn to_indented_string(&self, indent_width: usize) -> String {
+        let mut outline = String::new();
+
+        if let Some(root_id) = self.root_index {
+            for (node_id, depth) in self.descendants_with_depth(root_id) {
+                let indent = depth as usize * indent_width;
+                // Writing to a `String` cannot fail.
+                writeln!(outline, "{:indent$}{}", "", self[node_id], indent = indent).unwrap();
+            }
+        }
+
+        outline
+    }
+}
+
+/// An error returned by `VecTree::from_indented_str` when a line is not properly indented.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseIndentError {
+    line: usize,
+    kind: ParseIndentErrorKind,
+}
+
+/// The reason why `VecTree::from_indented_str` failed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParseIndentErrorKind {
+    /// The indentation of the line is not a multiple of the indent width in spaces.
+    InvalidIndent,
+    /// The line is indented by more than one level below the previous one.
+    TooDeep,
+    /// The line is unindented while the tree already has a root.
+    SeveralRoots,
+}
+
+impl ParseIndentError {
+    /// Return the number of the faulty line, starting from 1.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Return the reason of the error.
+    pub fn kind(&self) -> ParseIndentErrorKind {
+        self.kind
+    }
+}
+
+impl fmt::Display for ParseIndentError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let reason = match self.kind {
+            ParseIndentErrorKind::InvalidIndent => "invalid indentation",
+            ParseIndentErrorKind::TooDeep => "line indented too deep",
+            ParseIndentErrorKind::SeveralRoots => "several unindented lines",
+        };
+        write!(f, "{} at line {}", reason, self.line)
+    }
+}
+
+impl Error for ParseIndentError {}
@@ -0,0 +1,157 @@
+use super::VecTree;
+use generational_arena::Index;
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops;
+
+/// A `VecTree` maintaining a map from a key extracted from each node’s data to the node’s
+/// index, allowing to find a node by key in O(1).
+///
+/// The key of a node is computed by the key function given at construction time when the node is
+/// inserted or modified, and forgotten when the node (or one of its ancestors) is removed. Keys are
+/// expected to be unique, if two nodes share the same key the map points to the most recently
+/// inserted or modified one.
+///
+/// Read-only access to the underlying tree is provided through `Deref`, mutations must go through
+/// `IndexedVecTree` methods so the map stays in sync.
+///
+/// # Examples
+///
+/// ```
+/// use vec_tree::IndexedVecTree;
+///
+/// let mut tree = IndexedVecTree::new(|&(id, _): &(u32, &str)| id);
+/// let root = tree.insert_root((1, "root"));
+/// let child = tree.insert((2, "child"), root);
+///
+/// assert_eq!(tree.find_by_key(&2), Some(child));
+///
+/// tree.remove(root);
+/// assert_eq!(tree.find_by_key(&2), None);
+/// ```
+#[derive(Clone, Debug)]
+pub struct IndexedVecTree<T, K> {
+    tree: VecTree<T>,
+    keys: HashMap<K, Index>,
+    key_fn: fn(&T) -> K,
+}
+
+impl<T, K> IndexedVecTree<T, K>
+where
+    K: Hash + Eq,
+{
+    /// Constructs a new, empty `IndexedVecTree` using `key_fn` to extract the key of a node
+    /// from its data.
+    pub fn new(key_fn: fn(&T) -> K) -> IndexedVecTree<T, K> {
+        IndexedVecTree {
+            tree: VecTree::new(),
+            keys: HashMap::new(),
+            key_fn,
+        }
+    }
+
+    /// Constructs a new, empty `IndexedVecTree` with the specified capacity, using `key_fn` to
+    /// extract the key of a node from its data.
+    pub fn with_capacity(n: usize, key_fn: fn(&T) -> K) -> IndexedVecTree<T, K> {
+        IndexedVecTree {
+            tree: VecTree::with_capacity(n),
+            keys: HashMap::with_capacity(n),
+            key_fn,
+        }
+    }
+
+    /// Insert `data` into the tree as a root node, allocating more capacity if necessary.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a root node already exists.
+    pub fn insert_root(&mut self, data: T) -> Index {
+        let key = (self.key_fn)(&data);
+        let node_id = self.tree.insert_root(data);
+        self.keys.insert(key, node_id);
+        node_id
+    }
+
+    /// Insert `data` into the tree as the last child of `parent_id`, allocating more capacity if
+    /// necessary.
+    pub fn insert(&mut self, data: T, parent_id: Index) -> Index {
+        let key = (self.key_fn)(&data);
+        let node_id = self.tree.insert(data, parent_id);
+        self.keys.insert(key, node_id);
+        node_id
+    }
+
+    /// Remove the element at index `node_id` and its descendants from the tree, as well as
+    /// their keys.
+    ///
+    /// If the element at index `node_id` is still in the tree, then it is returned. If it is not
+    /// in the tree, then `None` is returned.
+    pub fn remove(&mut self, node_id: Index) -> Option<T> {
+        if !self.tree.contains(node_id) {
+            return None;
+        }
+
+        for descendant_id in self.tree.descendants(node_id) {
+            let key = (self.key_fn)(&self.tree[descendant_id]);
+            if self.keys.get(&key) == Some(&descendant_id) {
+                self.keys.remove(&key);
+            }
+        }
+
+        self.tree.remove(node_id)
+    }
+
+    /// Move the node at index `new_child_id`, and its descendants, to the last child position of
+    /// the node at index `node_id`.
+    pub fn append_child(&mut self, node_id: Index, new_child_id: Index) {
+        self.tree.append_child(node_id, new_child_id);
+    }
+
+    /// Modify the data of the node at index `node_id` with `f`, updating its key.
+    ///
+    /// Returns `None` without calling `f` if the node is not in the tree.
+    pub fn modify<F, R>(&mut self, node_id: Index, f: F) -> Option<R>
+    where
+        F: FnOnce(&mut T) -> R,
+    {
+        let data = self.tree.get_mut(node_id)?;
+
+        let old_key = (self.key_fn)(data);
+        let result = f(data);
+        let new_key = (self.key_fn)(data);
+
+        if old_key != new_key {
+            if self.keys.get(&old_key) == Some(&node_id) {
+                self.keys.remove(&old_key);
+            }
+            self.keys.insert(new_key, node_id);
+        }
+
+        Some(result)
+    }
+
+    /// Return the index of the node whose key is `key`.
+    pub fn find_by_key(&self, key: &K) -> Option<Index> {
+        self.keys.get(key).cloned()
+    }
+
+    /// Clear all the items inside the tree and their keys, but keep the tree allocation.
+    pub fn clear(&mut self) {
+        self.tree.clear();
+        self.keys.clear();
+    }
+
+    /// Return the underlying tree, dropping the key map.
+    pub fn into_inner(self) -> VecTree<T> {
+        self.tree
+    }
+}
+
+impl<T, K> ops::Deref for IndexedVecTree<T, K> {
+    type Target = VecTree<T>;
+
+    fn deref(&self) -> &VecTree<T> {
+        &self.tree
+    }
+}
@@ -0,0 +1,99 @@
+use super::VecTree;
+use generational_arena::Index;
+
+use indextree::{Arena, NodeId};
+use std::collections::HashMap;
+
+impl<T: Clone> VecTree<T> {
+    /// Copy the subtree of an `indextree` arena rooted at `root` into a new tree, and return it
+    /// along with the index of the copy of each node.
+    ///
+    /// The other trees of the arena are left out, since a `VecTree` has a single root.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `root` was removed from the arena.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut arena = indextree::Arena::new();
+    /// let root = arena.new_node("root");
+    /// let child = root.append_value("child", &mut arena);
+    ///
+    /// let (tree, indices) = VecTree::from_indextree(&arena, root);
+    ///
+    /// assert_eq!(tree[indices[&child]], "child");
+    /// assert_eq!(tree.parent(indices[&child]), Some(indices[&root]));
+    /// ```
+    pub fn from_indextree(arena: &Arena<T>, root: NodeId) -> (VecTree<T>, HashMap<NodeId, Index>) {
+        if root.is_removed(arena) {
+            panic!("The root node you are trying to convert is removed");
+        }
+
+        let mut tree = VecTree::new();
+        let mut indices = HashMap::new();
+
+        // Parents come before their children in tree order, so they are always inserted first.
+        for node_id in root.descendants(arena) {
+            let data = arena[node_id].get().clone();
+            let index = match node_id.parent(arena) {
+                Some(parent_id) if node_id != root => tree.insert(data, indices[&parent_id]),
+                _ => tree.insert_root(data),
+            };
+            indices.insert(node_id, index);
+        }
+
+        (tree, indices)
+    }
+}
+
+impl<T> VecTree<T> {
+    /// Move the nodes of the tree into a new `indextree` arena, and return it along with the id
+    /// of each node in the arena.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root("root");
+    /// let child = tree.insert("child", root);
+    ///
+    /// let (arena, node_ids) = tree.into_indextree();
+    ///
+    /// assert_eq!(*arena[node_ids[&child]].get(), "child");
+    /// assert_eq!(node_ids[&child].parent(&arena), Some(node_ids[&root]));
+    /// ```
+    pub fn into_indextree(mut self) -> (Arena<T>, HashMap<Index, NodeId>) {
+        let root_id = match self.root_index {
+            Some(root_id) => root_id,
+            None => return (Arena::new(), HashMap::new()),
+        };
+
+        let order = self.descendants(root_id).collect::<Vec<_>>();
+        let mut arena = Arena::with_capacity(order.len());
+        let mut node_ids: HashMap<Index, NodeId> = HashMap::with_capacity(order.len());
+        for index in order {
+            let node = self.nodes.remove(index).unwrap();
+            let node_id = arena.new_node(node.data);
+            if let Some(parent) = node.parent {
+                node_ids[&parent].append(node_id, &mut arena);
+            }
+            node_ids.insert(index, node_id);
+        }
+
+        (arena, node_ids)
+    }
+}
+
+/// Move the nodes of a tree into a new `indextree` arena, use `VecTree::into_indextree` to get
+/// the id of each node too.
+impl<T> From<VecTree<T>> for Arena<T> {
+    fn from(tree: VecTree<T>) -> Arena<T> {
+        tree.into_indextree().0
+    }
+}
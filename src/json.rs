@@ -0,0 +1,259 @@
+use super::{NodeEdge, VecTree};
+use generational_arena::Index;
+
+use serde::de::{DeserializeOwned, Error};
+use serde::Serialize;
+use serde_json::{Map, Number, Value};
+
+impl<T: Serialize> VecTree<T> {
+    /// Return the tree as nested JSON objects, each node being an object with its `value` and the
+    /// array of its `children`. A tree with no root gives `null`.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the data of a node cannot be represented as JSON.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    /// use serde_json::json;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root("root");
+    /// tree.insert("child", root);
+    ///
+    /// assert_eq!(
+    ///     tree.to_json_value().unwrap(),
+    ///     json!({
+    ///         "value": "root",
+    ///         "children": [{ "value": "child", "children": [] }]
+    ///     })
+    /// );
+    /// ```
+    pub fn to_json_value(&self) -> serde_json::Result<Value> {
+        let root_id = match self.root_index {
+            Some(root_id) => root_id,
+            None => return Ok(Value::Null),
+        };
+
+        // The children converted so far of each node being converted, from the root to the
+        // deepest.
+        let mut stack: Vec<Vec<Value>> = Vec::new();
+
+        for edge in self.traverse(root_id) {
+            match edge {
+                NodeEdge::Start(_) => stack.push(Vec::new()),
+                NodeEdge::End(node_id) => {
+                    let mut object = Map::new();
+                    object.insert("value".to_string(), serde_json::to_value(&self[node_id])?);
+                    object.insert("children".to_string(), Value::Array(stack.pop().unwrap()));
+
+                    match stack.last_mut() {
+                        Some(parent_children) => parent_children.push(Value::Object(object)),
+                        None => return Ok(Value::Object(object)),
+                    }
+                }
+            }
+        }
+
+        unreachable!("The traversal always ends with the root")
+    }
+}
+
+impl<T: DeserializeOwned> VecTree<T> {
+    /// Build a tree from nested JSON objects, as returned by `to_json_value`. The `children` of a
+    /// node may be left out when it has none, and `null` gives a tree with no root.
+    ///
+    /// # Errors
+    ///
+    /// Fails if a node is not an object with a `value`, if its `children` are not an array, or if
+    /// a value cannot be deserialized as `T`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    /// use serde_json::json;
+    ///
+    /// let tree = VecTree::<i32>::from_json_value(json!({
+    ///     "value": 0,
+    ///     "children": [{ "value": 1 }, { "value": 2 }]
+    /// }))
+    /// .unwrap();
+    ///
+    /// let root = tree.get_root_index().unwrap();
+    /// let values = tree
+    ///     .children(root)
+    ///     .map(|node_id| tree[node_id])
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(values, [1, 2]);
+    /// ```
+    pub fn from_json_value(value: Value) -> serde_json::Result<VecTree<T>> {
+        let mut tree = VecTree::new();
+        if value.is_null() {
+            return Ok(tree);
+        }
+
+        let (data, children) = split_json_node(value)?;
+        let root_id = tree.insert_root(data);
+
+        // The nodes left to insert, with the index of their parent, children being reversed so
+        // they are popped in order.
+        let mut pending = children
+            .into_iter()
+            .rev()
+            .map(|child| (child, root_id))
+            .collect::<Vec<_>>();
+
+        while let Some((node, parent_id)) = pending.pop() {
+            let (data, children) = split_json_node(node)?;
+            let node_id = tree.insert(data, parent_id);
+            pending.extend(children.into_iter().rev().map(|child| (child, node_id)));
+        }
+
+        Ok(tree)
+    }
+}
+
+// Return the data and the children of a node from its JSON object.
+fn split_json_node<T: DeserializeOwned>(node: Value) -> serde_json::Result<(T, Vec<Value>)> {
+    let mut object = match node {
+        Value::Object(object) => object,
+        _ => return Err(serde_json::Error::custom("a node must be an object")),
+    };
+
+    let data = match object.remove("value") {
+        Some(value) => serde_json::from_value(value)?,
+        None => return Err(serde_json::Error::missing_field("value")),
+    };
+
+    let children = match object.remove("children") {
+        Some(Value::Array(children)) => children,
+        Some(_) => return Err(serde_json::Error::custom("the children must be an array")),
+        None => Vec::new(),
+    };
+
+    Ok((data, children))
+}
+
+impl VecTree<JsonNode> {
+    /// Build a tree with a node for every value of a JSON document, the elements of arrays and the
+    /// fields of objects being the children of their container, in document order.
+    ///
+    /// Unlike `from_json_value`, which reads a tree serialized by `to_json_value`, this accepts
+    /// any document, keeping how each value is reached from its parent in its `key`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::{JsonKey, JsonKind, JsonNode, VecTree};
+    /// use serde_json::json;
+    ///
+    /// let tree = VecTree::from_json_document(&json!({ "tags": ["a", "b"] }));
+    ///
+    /// let root = tree.get_root_index().unwrap();
+    /// assert_eq!(tree[root].kind, JsonKind::Object);
+    ///
+    /// let tags = tree.first_child(root).unwrap();
+    /// assert_eq!(tree[tags].key, JsonKey::Field("tags".to_string()));
+    ///
+    /// let b = tree.last_child(tags).unwrap();
+    /// assert_eq!(
+    ///     tree[b],
+    ///     JsonNode {
+    ///         key: JsonKey::Item(1),
+    ///         kind: JsonKind::String("b".to_string()),
+    ///     }
+    /// );
+    /// ```
+    pub fn from_json_document(value: &Value) -> VecTree<JsonNode> {
+        let mut tree = VecTree::new();
+        let root_id = tree.insert_root(JsonNode::new(JsonKey::Root, value));
+
+        // The values left to insert, with their key and the index of their parent, children
+        // being reversed so they are popped in order.
+        let mut pending = Vec::new();
+        push_json_children(&mut pending, value, root_id);
+
+        while let Some((key, value, parent_id)) = pending.pop() {
+            let node_id = tree.insert(JsonNode::new(key, value), parent_id);
+            push_json_children(&mut pending, value, node_id);
+        }
+
+        tree
+    }
+}
+
+fn push_json_children<'a>(
+    pending: &mut Vec<(JsonKey, &'a Value, Index)>,
+    value: &'a Value,
+    node_id: Index,
+) {
+    match value {
+        Value::Array(items) => pending.extend(
+            items
+                .iter()
+                .enumerate()
+                .rev()
+                .map(|(position, item)| (JsonKey::Item(position), item, node_id)),
+        ),
+        Value::Object(fields) => pending.extend(
+            fields
+                .iter()
+                .rev()
+                .map(|(name, field)| (JsonKey::Field(name.clone()), field, node_id)),
+        ),
+        _ => {}
+    }
+}
+
+/// A value of a JSON document, as stored in a tree built by `VecTree::from_json_document`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct JsonNode {
+    /// How the value is reached from its parent.
+    pub key: JsonKey,
+
+    /// The type of the value, along with its content if it is not a container.
+    pub kind: JsonKind,
+}
+
+impl JsonNode {
+    fn new(key: JsonKey, value: &Value) -> JsonNode {
+        let kind = match value {
+            Value::Null => JsonKind::Null,
+            Value::Bool(value) => JsonKind::Bool(*value),
+            Value::Number(value) => JsonKind::Number(value.clone()),
+            Value::String(value) => JsonKind::String(value.clone()),
+            Value::Array(_) => JsonKind::Array,
+            Value::Object(_) => JsonKind::Object,
+        };
+
+        JsonNode { key, kind }
+    }
+}
+
+/// How a value of a JSON document is reached from its parent.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum JsonKey {
+    /// The value is the whole document.
+    Root,
+    /// The value is the field with this name of an object.
+    Field(String),
+    /// The value is the element at this position of an array.
+    Item(usize),
+}
+
+/// The type of a value of a JSON document, along with its content if it is not a container.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum JsonKind {
+    Null,
+    Bool(bool),
+    Number(Number),
+    String(String),
+    /// An array, its elements being the children of the node.
+    Array,
+    /// An object, its fields being the children of the node.
+    Object,
+}
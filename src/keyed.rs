@@ -0,0 +1,173 @@
+use super::VecTree;
+use generational_arena::Index;
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops;
+
+/// A `VecTree` where each node keeps a map from the keys of its children to their indices,
+/// allowing to find a child by key without scanning its siblings, like a file in a directory.
+///
+/// The key of a node is computed by the key function given at construction time when the node is
+/// inserted or modified. Keys are expected to be unique among siblings, if two siblings share the
+/// same key the map points to the most recently inserted, moved or modified one.
+///
+/// Read-only access to the underlying tree is provided through `Deref`, mutations must go through
+/// `KeyedVecTree` methods so the maps stay in sync.
+///
+/// # Examples
+///
+/// ```
+/// use vec_tree::KeyedVecTree;
+///
+/// let mut tree = KeyedVecTree::new(|name: &String| name.clone());
+/// let root = tree.insert_root("/".to_string());
+/// let usr = tree.insert("usr".to_string(), root);
+/// let bin = tree.insert("bin".to_string(), usr);
+///
+/// assert_eq!(tree.child_by_key(root, &"usr".to_string()), Some(usr));
+/// assert_eq!(tree.child_by_key(usr, &"bin".to_string()), Some(bin));
+/// assert_eq!(tree.child_by_key(root, &"bin".to_string()), None);
+/// ```
+#[derive(Clone, Debug)]
+pub struct KeyedVecTree<T, K> {
+    tree: VecTree<T>,
+    children_keys: HashMap<Index, HashMap<K, Index>>,
+    key_fn: fn(&T) -> K,
+}
+
+impl<T, K> KeyedVecTree<T, K>
+where
+    K: Hash + Eq,
+{
+    /// Constructs a new, empty `KeyedVecTree` using `key_fn` to extract the key of a node from
+    /// its data.
+    pub fn new(key_fn: fn(&T) -> K) -> KeyedVecTree<T, K> {
+        KeyedVecTree {
+            tree: VecTree::new(),
+            children_keys: HashMap::new(),
+            key_fn,
+        }
+    }
+
+    /// Constructs a new, empty `KeyedVecTree` with the specified capacity, using `key_fn` to
+    /// extract the key of a node from its data.
+    pub fn with_capacity(n: usize, key_fn: fn(&T) -> K) -> KeyedVecTree<T, K> {
+        KeyedVecTree {
+            tree: VecTree::with_capacity(n),
+            children_keys: HashMap::new(),
+            key_fn,
+        }
+    }
+
+    /// Insert `data` into the tree as a root node, allocating more capacity if necessary.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a root node already exists.
+    pub fn insert_root(&mut self, data: T) -> Index {
+        self.tree.insert_root(data)
+    }
+
+    /// Insert `data` into the tree as the last child of `parent_id`, allocating more capacity if
+    /// necessary.
+    pub fn insert(&mut self, data: T, parent_id: Index) -> Index {
+        let key = (self.key_fn)(&data);
+        let node_id = self.tree.insert(data, parent_id);
+        self.children_keys
+            .entry(parent_id)
+            .or_default()
+            .insert(key, node_id);
+        node_id
+    }
+
+    /// Remove the element at index `node_id` and its descendants from the tree.
+    ///
+    /// If the element at index `node_id` is still in the tree, then it is returned. If it is not
+    /// in the tree, then `None` is returned.
+    pub fn remove(&mut self, node_id: Index) -> Option<T> {
+        if !self.tree.contains(node_id) {
+            return None;
+        }
+
+        self.unlink_key(node_id);
+        for descendant_id in self.tree.descendants(node_id) {
+            self.children_keys.remove(&descendant_id);
+        }
+
+        self.tree.remove(node_id)
+    }
+
+    /// Move the node at index `new_child_id`, and its descendants, to the last child position of
+    /// the node at index `node_id`.
+    pub fn append_child(&mut self, node_id: Index, new_child_id: Index) {
+        self.unlink_key(new_child_id);
+        self.tree.append_child(node_id, new_child_id);
+
+        let key = (self.key_fn)(&self.tree[new_child_id]);
+        self.children_keys
+            .entry(node_id)
+            .or_default()
+            .insert(key, new_child_id);
+    }
+
+    /// Modify the data of the node at index `node_id` with `f`, updating its key in its parent.
+    ///
+    /// Returns `None` without calling `f` if the node is not in the tree.
+    pub fn modify<F, R>(&mut self, node_id: Index, f: F) -> Option<R>
+    where
+        F: FnOnce(&mut T) -> R,
+    {
+        if !self.tree.contains(node_id) {
+            return None;
+        }
+
+        self.unlink_key(node_id);
+        let result = f(&mut self.tree[node_id]);
+
+        if let Some(parent_id) = self.tree.parent(node_id) {
+            let key = (self.key_fn)(&self.tree[node_id]);
+            self.children_keys
+                .entry(parent_id)
+                .or_default()
+                .insert(key, node_id);
+        }
+
+        Some(result)
+    }
+
+    /// Return the index of the child of the node at index `parent_id` whose key is `key`.
+    pub fn child_by_key(&self, parent_id: Index, key: &K) -> Option<Index> {
+        self.children_keys.get(&parent_id)?.get(key).cloned()
+    }
+
+    /// Clear all the items inside the tree and their keys, but keep the tree allocation.
+    pub fn clear(&mut self) {
+        self.tree.clear();
+        self.children_keys.clear();
+    }
+
+    /// Return the underlying tree, dropping the key maps.
+    pub fn into_inner(self) -> VecTree<T> {
+        self.tree
+    }
+
+    fn unlink_key(&mut self, node_id: Index) {
+        if let Some(parent_id) = self.tree.parent(node_id) {
+            let key = (self.key_fn)(&self.tree[node_id]);
+            if let Some(keys) = self.children_keys.get_mut(&parent_id) {
+                if keys.get(&key) == Some(&node_id) {
+                    keys.remove(&key);
+                }
+            }
+        }
+    }
+}
+
+impl<T, K> ops::Deref for KeyedVecTree<T, K> {
+    type Target = VecTree<T>;
+
+    fn deref(&self) -> &VecTree<T> {
+        &self.tree
+    }
+}
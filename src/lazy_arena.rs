@@ -0,0 +1,154 @@
+use generational_arena::{self, Arena, Index};
+
+use std::iter::FusedIterator;
+use std::ops;
+
+// The capacity allocated by the first insertion into an arena created without capacity.
+const DEFAULT_CAPACITY: usize = 4;
+
+// An arena which is only allocated once it needs room for an element, like a `Vec`, since
+// `Arena` always allocates when created.
+#[derive(Clone, Debug)]
+pub(crate) struct LazyArena<T>(Option<Arena<T>>);
+
+impl<T> LazyArena<T> {
+    pub(crate) const fn new() -> LazyArena<T> {
+        LazyArena(None)
+    }
+
+    pub(crate) fn with_capacity(n: usize) -> LazyArena<T> {
+        let mut arena = LazyArena::new();
+        arena.reserve(n);
+        arena
+    }
+
+    pub(crate) fn reserve(&mut self, additional_capacity: usize) {
+        match self.0 {
+            Some(ref mut arena) => arena.reserve(additional_capacity),
+            None if additional_capacity > 0 => {
+                self.0 = Some(Arena::with_capacity(additional_capacity))
+            }
+            None => {}
+        }
+    }
+
+    pub(crate) fn try_insert(&mut self, value: T) -> Result<Index, T> {
+        match self.0 {
+            Some(ref mut arena) => arena.try_insert(value),
+            None => Err(value),
+        }
+    }
+
+    pub(crate) fn insert(&mut self, value: T) -> Index {
+        self.0
+            .get_or_insert_with(|| Arena::with_capacity(DEFAULT_CAPACITY))
+            .insert(value)
+    }
+
+    pub(crate) fn remove(&mut self, i: Index) -> Option<T> {
+        self.0.as_mut()?.remove(i)
+    }
+
+    pub(crate) fn clear(&mut self) {
+        if let Some(ref mut arena) = self.0 {
+            arena.clear();
+        }
+    }
+
+    pub(crate) fn get(&self, i: Index) -> Option<&T> {
+        self.0.as_ref()?.get(i)
+    }
+
+    pub(crate) fn get_mut(&mut self, i: Index) -> Option<&mut T> {
+        self.0.as_mut()?.get_mut(i)
+    }
+
+    pub(crate) fn get2_mut(&mut self, i1: Index, i2: Index) -> (Option<&mut T>, Option<&mut T>) {
+        match self.0 {
+            Some(ref mut arena) => arena.get2_mut(i1, i2),
+            None => (None, None),
+        }
+    }
+
+    pub(crate) fn get_unknown_gen(&self, i: usize) -> Option<(&T, Index)> {
+        self.0.as_ref()?.get_unknown_gen(i)
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.0.as_ref().map_or(0, Arena::len)
+    }
+
+    pub(crate) fn capacity(&self) -> usize {
+        self.0.as_ref().map_or(0, Arena::capacity)
+    }
+
+    pub(crate) fn iter(&self) -> Iter<'_, T> {
+        Iter(self.0.as_ref().map(Arena::iter))
+    }
+
+    pub(crate) fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut(self.0.as_mut().map(Arena::iter_mut))
+    }
+}
+
+impl<T> ops::Index<Index> for LazyArena<T> {
+    type Output = T;
+
+    fn index(&self, index: Index) -> &T {
+        self.get(index).expect("No element at index")
+    }
+}
+
+impl<T> ops::IndexMut<Index> for LazyArena<T> {
+    fn index_mut(&mut self, index: Index) -> &mut T {
+        self.get_mut(index).expect("No element at index")
+    }
+}
+
+pub(crate) struct Iter<'a, T: 'a>(Option<generational_arena::Iter<'a, T>>);
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = (Index, &'a T);
+
+    fn next(&mut self) -> Option<(Index, &'a T)> {
+        self.0.as_mut()?.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.as_ref().map_or((0, Some(0)), Iterator::size_hint)
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<(Index, &'a T)> {
+        self.0.as_mut()?.next_back()
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+
+impl<'a, T> FusedIterator for Iter<'a, T> {}
+
+pub(crate) struct IterMut<'a, T: 'a>(Option<generational_arena::IterMut<'a, T>>);
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = (Index, &'a mut T);
+
+    fn next(&mut self) -> Option<(Index, &'a mut T)> {
+        self.0.as_mut()?.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.as_ref().map_or((0, Some(0)), Iterator::size_hint)
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
+    fn next_back(&mut self) -> Option<(Index, &'a mut T)> {
+        self.0.as_mut()?.next_back()
+    }
+}
+
+impl<'a, T> ExactSizeIterator for IterMut<'a, T> {}
+
+impl<'a, T> FusedIterator for IterMut<'a, T> {}
@@ -135,20 +135,171 @@ assert_eq!(descendants, [1, 10, 13, 11]);
 #![forbid(unsafe_code)]
 
 extern crate generational_arena;
-use generational_arena::Arena;
 pub use generational_arena::Index;
+use lazy_arena::LazyArena;
 
+use core::cmp::Ordering;
 use core::ops;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, VecDeque};
+use std::hash::{Hash, Hasher};
+use std::iter::FusedIterator;
+use std::marker::PhantomData;
+use std::sync::atomic::{self, AtomicU64};
 use std::{fmt, mem};
 
+mod ancestor_table;
+pub use ancestor_table::AncestorTable;
+
+mod augmented;
+pub use augmented::AugmentedVecTree;
+
+mod binary;
+
+mod builder;
+pub use builder::{TreeBuilder, TreeEvent};
+
+mod concurrent;
+pub use concurrent::SyncVecTree;
+
+mod cursor;
+pub use cursor::CursorMut;
+
+mod display;
+pub use display::DebugFlat;
+
+mod euler;
+pub use euler::EulerTourVecTree;
+
+mod folded;
+
+mod frozen;
+pub use frozen::FrozenVecTree;
+
+mod generation;
+pub use generation::{index_from_raw_parts, index_generation, index_slot, index_to_raw_parts};
+
+mod indented;
+pub use indented::{ParseIndentError, ParseIndentErrorKind};
+
+mod indexed;
+pub use indexed::IndexedVecTree;
+
+mod lazy_arena;
+
+mod mermaid;
+pub use mermaid::Mermaid;
+
+mod node_id;
+pub use node_id::NodeId;
+
+mod node_ref;
+pub use node_ref::{NodeMut, NodeRef, NodeRefsIter};
+
+mod persistent;
+pub use persistent::{PersistentValuesIter, PersistentVecTree};
+
+mod query;
+pub use query::{Query, QueryIter};
+
+mod subtree;
+pub use subtree::{SubtreeIter, SubtreeIterMut, SubtreeMut};
+
+mod tagged;
+pub use tagged::{TaggedVecTree, TypedIndex, TypedIter};
+
+mod visitor;
+pub use visitor::{VisitAction, Visitor};
+
+mod zipper;
+pub use zipper::Zipper;
+
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+
+#[cfg(feature = "fs")]
+mod dir;
+#[cfg(feature = "fs")]
+pub use dir::{DirEntryData, DirEntryKind, DirError, DirOptions, SymlinkPolicy};
+
+#[cfg(feature = "csv")]
+mod edge_list;
+#[cfg(feature = "csv")]
+pub use edge_list::CsvEdgesError;
+
+#[cfg(feature = "ego-tree")]
+mod ego_tree_compat;
+
+#[cfg(feature = "indextree")]
+mod indextree_compat;
+
+#[cfg(feature = "serde_json")]
+mod json;
+#[cfg(feature = "serde_json")]
+pub use json::{JsonKey, JsonKind, JsonNode};
+
+#[cfg(feature = "keyed-children")]
+mod keyed;
+#[cfg(feature = "keyed-children")]
+pub use keyed::KeyedVecTree;
+
+#[cfg(feature = "rayon")]
+mod parallel;
+#[cfg(feature = "rayon")]
+pub use parallel::{ParDescendantsIter, ParIter, ParValuesIter};
+
+#[cfg(feature = "proptest")]
+pub mod proptest;
+
+#[cfg(feature = "quickcheck")]
+mod quickcheck;
+
+#[cfg(feature = "serde")]
+mod serialize;
+#[cfg(feature = "serde")]
+pub use serialize::{RemappedVecTree, WithIndices};
+
+#[cfg(feature = "futures")]
+mod stream;
+#[cfg(feature = "futures")]
+pub use stream::TreeStream;
+
+#[cfg(feature = "xml")]
+mod xml;
+#[cfg(feature = "xml")]
+pub use xml::{XmlError, XmlNode};
+
 /// The `VecTree` allows inserting and removing elements that are referred to by
 /// `Index`.
 ///
 /// [See the module-level documentation for example usage and motivation.](./index.html)
-#[derive(Clone, Debug)]
+///
+/// Cloning a tree copies its storage as is, so every index of the original tree refers to the
+/// same node in the clone.
+///
+/// ```
+/// use vec_tree::VecTree;
+///
+/// let mut tree = VecTree::new();
+/// let root = tree.insert_root(0);
+/// let child = tree.insert(1, root);
+///
+/// let mut copy = tree.clone();
+/// copy[child] = 10;
+///
+/// assert_eq!(copy.parent(child), Some(root));
+/// assert_eq!(tree[child], 1);
+/// ```
+#[derive(Clone)]
 pub struct VecTree<T> {
-    nodes: Arena<Node<T>>,
+    nodes: LazyArena<Node<T>>,
     root_index: Option<Index>,
+    // The children of the nodes whose children were inserted with `insert_sorted`, in order, so
+    // they can be binary searched. An entry is dropped when the children of its node change by
+    // other means.
+    sorted_children: HashMap<Index, Vec<Index>>,
+    version: u64,
+    id: u64,
 }
 
 #[derive(Clone, Debug)]
@@ -161,32 +312,98 @@ struct Node<T> {
     data: T,
 }
 
-const DEFAULT_CAPACITY: usize = 4;
+// The id given to the next tree created, so indices can be checked against their tree.
+static NEXT_TREE_ID: AtomicU64 = AtomicU64::new(0);
 
 impl<T> Default for VecTree<T> {
     fn default() -> Self {
-        VecTree::with_capacity(DEFAULT_CAPACITY)
+        VecTree::new()
+    }
+}
+
+/// Two trees are equal if they have the same shape and their nodes hold equal data, in tree
+/// order. The indices of the nodes are not compared.
+///
+/// # Examples
+///
+/// ```
+/// use vec_tree::VecTree;
+///
+/// let mut expected = VecTree::new();
+/// let root = expected.insert_root(0);
+/// expected.insert(1, root);
+///
+/// let mut actual = VecTree::new();
+/// let root = actual.insert_root(0);
+/// let removed = actual.insert(2, root);
+/// actual.remove(removed);
+/// actual.insert(1, root);
+///
+/// assert_eq!(expected, actual);
+/// ```
+impl<T: PartialEq> PartialEq for VecTree<T> {
+    fn eq(&self, other: &VecTree<T>) -> bool {
+        let (root_id, other_root_id) = match (self.root_index, other.root_index) {
+            (Some(root_id), Some(other_root_id)) => (root_id, other_root_id),
+            (None, None) => return true,
+            _ => return false,
+        };
+
+        let mut traverse = self.traverse(root_id);
+        let mut other_traverse = other.traverse(other_root_id);
+
+        loop {
+            match (traverse.next(), other_traverse.next()) {
+                (Some(NodeEdge::Start(node_id)), Some(NodeEdge::Start(other_node_id))) => {
+                    if self[node_id] != other[other_node_id] {
+                        return false;
+                    }
+                }
+                (Some(NodeEdge::End(_)), Some(NodeEdge::End(_))) => {}
+                (None, None) => return true,
+                _ => return false,
+            }
+        }
+    }
+}
+
+impl<T: Eq> Eq for VecTree<T> {}
+
+/// The hash of a tree is computed from the data and child count of its nodes in tree order, so
+/// equal trees have equal hashes whatever their indices.
+impl<T: Hash> Hash for VecTree<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.root_index.is_some().hash(state);
+        if let Some(root_id) = self.root_index {
+            self.hash_subtree(root_id, state);
+        }
     }
 }
 
 impl<T> VecTree<T> {
     /// Constructs a new, empty `VecTree`.
     ///
+    /// The tree does not allocate until a node is inserted.
+    ///
     /// # Examples
     ///
     /// ```
     /// use vec_tree::VecTree;
     ///
     /// let mut tree = VecTree::<usize>::new();
-    /// # let _ = tree;
+    /// assert_eq!(tree.capacity(), 0);
+    ///
+    /// tree.insert_root(0);
+    /// assert!(tree.capacity() > 0);
     /// ```
     pub fn new() -> VecTree<T> {
-        VecTree::with_capacity(DEFAULT_CAPACITY)
+        VecTree::with_capacity(0)
     }
 
     /// Constructs a new, empty `VecTree<T>` with the specified capacity.
     ///
-    /// The `VecTree<T>` will be able to hold `n` elements without further allocation.
+    /// The `VecTree<T>` will be able to hold `n` elements without further allocation. If `n` is zero,
+    /// the tree does not allocate until a node is inserted.
     ///
     /// # Examples
     ///
@@ -206,8 +423,11 @@ impl<T> VecTree<T> {
     /// ```
     pub fn with_capacity(n: usize) -> VecTree<T> {
         VecTree {
-            nodes: Arena::with_capacity(n),
+            nodes: LazyArena::with_capacity(n),
             root_index: None,
+            sorted_children: HashMap::new(),
+            version: 0,
+            id: NEXT_TREE_ID.fetch_add(1, atomic::Ordering::Relaxed),
         }
     }
 
@@ -300,6 +520,63 @@ impl<T> VecTree<T> {
         node
     }
 
+    /// Insert `data` into the tree as a child of `parent_id`, keeping the children sorted
+    /// according to `compare`, and allocating more capacity if necessary.
+    ///
+    /// The children of `parent_id` are expected to be already sorted. The new node is inserted
+    /// after the children comparing less than or equal to it, so insertion is stable.
+    ///
+    /// The tree keeps the children of `parent_id` in a vector from then on, so the position of
+    /// the new node is found with a binary search. The vector is built on the first call for a
+    /// given parent, and again after its children were changed by any other method.
+    ///
+    /// The `data`'s associated index in the tree is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root("/");
+    /// tree.insert_sorted("usr", root, |a, b| a.cmp(b));
+    /// tree.insert_sorted("bin", root, |a, b| a.cmp(b));
+    /// tree.insert_sorted("etc", root, |a, b| a.cmp(b));
+    ///
+    /// let children = tree
+    ///     .children(root)
+    ///     .map(|node_id| tree[node_id])
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(children, ["bin", "etc", "usr"]);
+    /// ```
+    pub fn insert_sorted<F>(&mut self, data: T, parent_id: Index, mut compare: F) -> Index
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        // Take the children out so linking the new node does not drop them.
+        let mut children = match self.sorted_children.remove(&parent_id) {
+            Some(children) => children,
+            None => self.children(parent_id).collect(),
+        };
+
+        let position = children.partition_point(|&child_id| {
+            compare(&self.nodes[child_id].data, &data) != Ordering::Greater
+        });
+
+        let node = self.create_node(data);
+
+        match children.get(position) {
+            Some(&next_sibling) => self.insert_before(next_sibling, node),
+            None => self.append_child(parent_id, node),
+        }
+
+        children.insert(position, node);
+        self.sorted_children.insert(parent_id, children);
+
+        node
+    }
+
     /// Attempts to insert `data` into the tree as root node using existing
     /// capacity.
     ///
@@ -336,6 +613,7 @@ impl<T> VecTree<T> {
         match self.try_create_node(data) {
             Ok(node_id) => {
                 self.root_index = Some(node_id);
+                self.bump_version();
                 Ok(node_id)
             }
             Err(error) => Err(error),
@@ -367,6 +645,7 @@ impl<T> VecTree<T> {
 
         let node_id = self.create_node(data);
         self.root_index = Some(node_id);
+        self.bump_version();
         node_id
     }
 
@@ -425,6 +704,11 @@ impl<T> VecTree<T> {
         let descendants = self.descendants(node_id).skip(1).collect::<Vec<Index>>();
         let node = self.nodes.remove(node_id).unwrap();
 
+        if let Some(parent_idx) = node.parent {
+            self.forget_sorted_children(parent_idx);
+        }
+        self.forget_sorted_children(node_id);
+
         let previous_sibling_opt = node.previous_sibling;
         let next_sibling_opt = node.next_sibling;
 
@@ -463,6 +747,7 @@ impl<T> VecTree<T> {
         // Remove descendants from arena.
         for node_id in descendants {
             self.nodes.remove(node_id);
+            self.forget_sorted_children(node_id);
         }
 
         // Set root_index to None if needed
@@ -472,6 +757,8 @@ impl<T> VecTree<T> {
             }
         }
 
+        self.bump_version();
+
         Some(node.data)
     }
 
@@ -498,6 +785,7 @@ impl<T> VecTree<T> {
     #[inline]
     pub fn append_child(&mut self, node_id: Index, new_child_id: Index) {
         self.detach(new_child_id);
+        self.forget_sorted_children(node_id);
 
         let last_child_opt;
         {
@@ -531,8 +819,39 @@ impl<T> VecTree<T> {
         }
     }
 
+    /// Insert the node at index `new_sibling_id` right before the node at index `node_id`,
+    /// detaching it first.
+    fn insert_before(&mut self, node_id: Index, new_sibling_id: Index) {
+        self.detach(new_sibling_id);
+
+        let (parent, previous_sibling) = {
+            let node = &mut self.nodes[node_id];
+            let previous_sibling = node.previous_sibling.replace(new_sibling_id);
+            (node.parent, previous_sibling)
+        };
+
+        if let Some(parent) = parent {
+            self.forget_sorted_children(parent);
+        }
+
+        {
+            let new_sibling = &mut self.nodes[new_sibling_id];
+            new_sibling.parent = parent;
+            new_sibling.previous_sibling = previous_sibling;
+            new_sibling.next_sibling = Some(node_id);
+        }
+
+        if let Some(previous_sibling) = previous_sibling {
+            self.nodes[previous_sibling].next_sibling = Some(new_sibling_id);
+        } else if let Some(parent) = parent {
+            self.nodes[parent].first_child = Some(new_sibling_id);
+        }
+    }
+
     #[inline]
     fn detach(&mut self, node_id: Index) {
+        self.bump_version();
+
         let (parent, previous_sibling, next_sibling) = {
             let node = &mut self.nodes[node_id];
             (
@@ -553,6 +872,18 @@ impl<T> VecTree<T> {
         } else if let Some(parent) = parent {
             self.nodes[parent].first_child = next_sibling;
         }
+
+        if let Some(parent) = parent {
+            self.forget_sorted_children(parent);
+        }
+    }
+
+    /// Drop the sorted children of the node at index `node_id`, whose children changed.
+    #[inline]
+    fn forget_sorted_children(&mut self, node_id: Index) {
+        if !self.sorted_children.is_empty() {
+            self.sorted_children.remove(&node_id);
+        }
     }
 
     /// Get a shared reference to the element at index `node_id` if it is in the
@@ -602,6 +933,151 @@ impl<T> VecTree<T> {
             _ => None,
         }
     }
+
+    /// Get shared references to the elements at `indices`, in the same order.
+    ///
+    /// Each reference is `None` if the corresponding element is not in the tree.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(0);
+    /// let child = tree.insert(1, root);
+    /// let removed = tree.insert(2, root);
+    /// tree.remove(removed);
+    ///
+    /// assert_eq!(tree.get_many(&[child, removed, root]), [Some(&1), None, Some(&0)]);
+    /// ```
+    pub fn get_many(&self, indices: &[Index]) -> Vec<Option<&T>> {
+        indices.iter().map(|&node_id| self.get(node_id)).collect()
+    }
+
+    /// Get a pair of exclusive references to the elements at indices `a` and `b` if they are in
+    /// the tree.
+    ///
+    /// If the element at index `a` or `b` is not in the tree, then `None` is returned in its
+    /// place.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a` and `b` are the same index.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(String::from("root"));
+    /// let child = tree.insert(String::new(), root);
+    ///
+    /// if let (Some(from), Some(to)) = tree.get_pair_mut(root, child) {
+    ///     std::mem::swap(from, to);
+    /// }
+    ///
+    /// assert_eq!(tree[root], "");
+    /// assert_eq!(tree[child], "root");
+    /// ```
+    pub fn get_pair_mut(&mut self, a: Index, b: Index) -> (Option<&mut T>, Option<&mut T>) {
+        let (node_a, node_b) = self.nodes.get2_mut(a, b);
+        (
+            node_a.map(|node| &mut node.data),
+            node_b.map(|node| &mut node.data),
+        )
+    }
+
+    /// Get exclusive references to the elements at `indices`, in the same order, if they are
+    /// all in the tree.
+    ///
+    /// If any of the elements is not in the tree, then `None` is returned. This walks the whole
+    /// tree to hand out the references, prefer `get_pair_mut` when only two nodes are needed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the same index is given more than once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(0);
+    /// let child_1 = tree.insert(1, root);
+    /// let child_2 = tree.insert(2, root);
+    ///
+    /// let [sum, a, b] = tree.get_disjoint_mut([root, child_1, child_2]).unwrap();
+    /// *sum = *a + *b;
+    /// *a = 0;
+    /// *b = 0;
+    ///
+    /// assert_eq!(tree[root], 3);
+    /// ```
+    pub fn get_disjoint_mut<const N: usize>(&mut self, indices: [Index; N]) -> Option<[&mut T; N]> {
+        for (i, node_id) in indices.iter().enumerate() {
+            if indices[..i].contains(node_id) {
+                panic!("The indices must be distinct");
+            }
+        }
+
+        if !indices.iter().all(|&node_id| self.contains(node_id)) {
+            return None;
+        }
+
+        let mut references = [(); N].map(|_| None);
+        let mut remaining = N;
+
+        for (node_id, data) in self.iter_mut() {
+            if remaining == 0 {
+                break;
+            }
+
+            if let Some(position) = indices.iter().position(|&index| index == node_id) {
+                references[position] = Some(data);
+                remaining -= 1;
+            }
+        }
+
+        Some(references.map(Option::unwrap))
+    }
+
+    /// Get an exclusive reference to the element at index `node_id`, along with an exclusive
+    /// reference to the element of its parent, if it is in the tree.
+    ///
+    /// If the element at index `node_id` is not in the tree, then `None` is returned. The parent
+    /// reference is `None` for the root.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(10);
+    /// let child = tree.insert(1, root);
+    ///
+    /// let (data, parent) = tree.get_with_parent_mut(child).unwrap();
+    /// let parent = parent.unwrap();
+    /// *data += *parent;
+    /// *parent = 0;
+    ///
+    /// assert_eq!(tree[child], 11);
+    /// assert_eq!(tree[root], 0);
+    /// assert_eq!(tree.get_with_parent_mut(root), Some((&mut 0, None)));
+    /// ```
+    pub fn get_with_parent_mut(&mut self, node_id: Index) -> Option<(&mut T, Option<&mut T>)> {
+        match self.parent(node_id) {
+            Some(parent_id) => match self.get_pair_mut(node_id, parent_id) {
+                (Some(data), parent_data) => Some((data, parent_data)),
+                (None, _) => None,
+            },
+            None => self.get_mut(node_id).map(|data| (data, None)),
+        }
+    }
+
     /// Get the root node index from the tree.
     ///
     /// If no root node is created in the tree, None is returned.
@@ -667,88 +1143,2120 @@ impl<T> VecTree<T> {
     pub fn clear(&mut self) {
         self.nodes.clear();
         self.root_index = None;
+        self.sorted_children.clear();
+        self.bump_version();
     }
 
-    /// Return an iterator of references to this node’s parent.
-    pub fn parent(&self, node_id: Index) -> Option<Index> {
-        match self.nodes.get(node_id) {
-            Some(node) => node.parent,
-            _ => None,
-        }
+    /// Return the structure version of the tree, which changes every time a node is inserted,
+    /// moved or removed.
+    ///
+    /// Code keeping indices or derived data across modifications of the tree, like a cached
+    /// traversal order, can compare versions to detect that they are stale. Modifying the data of
+    /// a node does not change the version.
+    ///
+    /// The iterators of the tree borrow it, so the tree cannot be modified during an iteration:
+    ///
+    /// ```compile_fail
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(0);
+    ///
+    /// for node_id in tree.children(root) {
+    ///     tree.insert(1, node_id);
+    /// }
+    /// ```
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(0);
+    ///
+    /// let order = tree.descendants(root).collect::<Vec<_>>();
+    /// let version = tree.version();
+    ///
+    /// tree[root] = 1;
+    /// assert_eq!(tree.version(), version);
+    ///
+    /// tree.insert(2, root);
+    /// assert_ne!(tree.version(), version); // `order` is stale.
+    /// # let _ = order;
+    /// ```
+    pub fn version(&self) -> u64 {
+        self.version
     }
 
-    /// Return an iterator of references to this node’s children.
-    pub fn children(&self, node_id: Index) -> ChildrenIter<T> {
-        ChildrenIter {
-            tree: self,
-            node_id: self.nodes[node_id].first_child,
-        }
+    #[inline]
+    fn bump_version(&mut self) {
+        self.version = self.version.wrapping_add(1);
     }
 
-    /// Return an iterator of references to this node and the siblings before it.
+    /// Return an iterator of shared references to the data of all the nodes of the tree, in no
+    /// particular order.
     ///
-    /// Call `.next().unwrap()` once on the iterator to skip the node itself.
-    pub fn preceding_siblings(&self, node_id: Index) -> PrecedingSiblingsIter<T> {
-        PrecedingSiblingsIter {
-            tree: self,
-            node_id: Some(node_id),
-        }
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(1);
+    /// tree.insert(2, root);
+    /// tree.insert(3, root);
+    ///
+    /// assert_eq!(tree.values().sum::<i32>(), 6);
+    /// ```
+    pub fn values(&self) -> TreeValuesIter<'_, T> {
+        TreeValuesIter(self.nodes.iter())
     }
 
-    /// Return an iterator of references to this node and the siblings after it.
+    /// Return an iterator of exclusive references to the data of all the nodes of the tree, in
+    /// no particular order.
     ///
-    /// Call `.next().unwrap()` once on the iterator to skip the node itself.
-    pub fn following_siblings(&self, node_id: Index) -> FollowingSiblingsIter<T> {
-        FollowingSiblingsIter {
-            tree: self,
-            node_id: Some(node_id),
-        }
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(1);
+    /// tree.insert(2, root);
+    ///
+    /// for value in tree.values_mut() {
+    ///     *value *= 10;
+    /// }
+    ///
+    /// assert_eq!(tree[root], 10);
+    /// ```
+    pub fn values_mut(&mut self) -> TreeValuesMutIter<'_, T> {
+        TreeValuesMutIter(self.nodes.iter_mut())
     }
 
-    /// Return an iterator of references to this node and its ancestors.
+    /// Return an iterator of shared references to all the nodes of the tree, along with their
+    /// data, in no particular order.
     ///
-    /// Call `.next().unwrap()` once on the iterator to skip the node itself.
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(1);
+    /// let child = tree.insert(2, root);
+    ///
+    /// let mut nodes = tree.iter().collect::<Vec<_>>();
+    /// nodes.sort_by_key(|&(_, value)| *value);
+    ///
+    /// assert_eq!(nodes, [(root, &1), (child, &2)]);
+    /// ```
+    pub fn iter(&self) -> TreeIter<'_, T> {
+        TreeIter(self.nodes.iter())
+    }
+
+    /// Return an iterator of exclusive references to all the nodes of the tree, along with their
+    /// data, in no particular order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(1);
+    /// let child = tree.insert(2, root);
+    ///
+    /// for (node_id, value) in tree.iter_mut() {
+    ///     if node_id == child {
+    ///         *value = 20;
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(tree[child], 20);
+    /// ```
+    pub fn iter_mut(&mut self) -> TreeIterMut<'_, T> {
+        TreeIterMut(self.nodes.iter_mut())
+    }
+
+    /// Call `f` with the index of each node of the tree and an exclusive reference to its data,
+    /// in tree order, transforming the data in place.
+    ///
+    /// This is `for_each_descendant_mut` from the root, no index is collected beforehand.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(String::from("root"));
+    /// let child = tree.insert(String::from("child"), root);
+    ///
+    /// tree.map_in_place(|_, name| name.make_ascii_uppercase());
+    ///
+    /// assert_eq!(tree[root], "ROOT");
+    /// assert_eq!(tree[child], "CHILD");
+    /// ```
+    pub fn map_in_place<F>(&mut self, f: F)
+    where
+        F: FnMut(Index, &mut T),
+    {
+        if let Some(root_index) = self.root_index {
+            self.for_each_descendant_mut(root_index, f);
+        }
+    }
+
+    /// Consume the tree, returning an iterator of its data along with the depth of each node, in
+    /// tree order (depth-first pre-order). The root is at depth 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root("a");
+    /// let child = tree.insert("b", root);
+    /// tree.insert("c", child);
+    /// tree.insert("d", root);
+    ///
+    /// let nodes = tree.into_iter_with_depth().collect::<Vec<_>>();
+    ///
+    /// assert_eq!(nodes, [("a", 0), ("b", 1), ("c", 2), ("d", 1)]);
+    /// ```
+    pub fn into_iter_with_depth(self) -> IntoIterWithDepth<T> {
+        IntoIterWithDepth {
+            next: self.root_index.map(|root_index| (root_index, 0)),
+            nodes: self.nodes,
+            pending_siblings: Vec::new(),
+        }
+    }
+
+    /// Return an iterator of references to this node’s parent.
+    pub fn parent(&self, node_id: Index) -> Option<Index> {
+        match self.nodes.get(node_id) {
+            Some(node) => node.parent,
+            _ => None,
+        }
+    }
+
+    /// Return this node’s first child.
+    ///
+    /// If the node has no children or is not in the tree, `None` is returned.
+    pub fn first_child(&self, node_id: Index) -> Option<Index> {
+        match self.nodes.get(node_id) {
+            Some(node) => node.first_child,
+            _ => None,
+        }
+    }
+
+    /// Return this node’s last child.
+    ///
+    /// If the node has no children or is not in the tree, `None` is returned.
+    pub fn last_child(&self, node_id: Index) -> Option<Index> {
+        match self.nodes.get(node_id) {
+            Some(node) => node.last_child,
+            _ => None,
+        }
+    }
+
+    /// Return the sibling right after this node.
+    ///
+    /// If the node is the last child of its parent or is not in the tree, `None` is returned.
+    pub fn next_sibling(&self, node_id: Index) -> Option<Index> {
+        match self.nodes.get(node_id) {
+            Some(node) => node.next_sibling,
+            _ => None,
+        }
+    }
+
+    /// Return the sibling right before this node.
+    ///
+    /// If the node is the first child of its parent or is not in the tree, `None` is returned.
+    pub fn previous_sibling(&self, node_id: Index) -> Option<Index> {
+        match self.nodes.get(node_id) {
+            Some(node) => node.previous_sibling,
+            _ => None,
+        }
+    }
+
+    /// Is the node at index `node_id` the root of the tree?
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(0);
+    /// let child = tree.insert(1, root);
+    ///
+    /// assert!(tree.is_root(root));
+    /// assert!(!tree.is_root(child));
+    /// ```
+    pub fn is_root(&self, node_id: Index) -> bool {
+        self.root_index == Some(node_id)
+    }
+
+    /// Is the node at index `node_id` a leaf, i.e. a node without children?
+    ///
+    /// Returns `false` if the node is not in the tree.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(0);
+    /// let child = tree.insert(1, root);
+    ///
+    /// assert!(!tree.is_leaf(root));
+    /// assert!(tree.is_leaf(child));
+    /// ```
+    pub fn is_leaf(&self, node_id: Index) -> bool {
+        match self.nodes.get(node_id) {
+            Some(node) => node.first_child.is_none(),
+            _ => false,
+        }
+    }
+
+    /// Does the node at index `node_id` have at least one child?
+    ///
+    /// Returns `false` if the node is not in the tree.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(0);
+    /// let child = tree.insert(1, root);
+    ///
+    /// assert!(tree.has_children(root));
+    /// assert!(!tree.has_children(child));
+    /// ```
+    pub fn has_children(&self, node_id: Index) -> bool {
+        match self.nodes.get(node_id) {
+            Some(node) => node.first_child.is_some(),
+            _ => false,
+        }
+    }
+
+    /// Return the number of direct children of the node at index `node_id`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node is not in the tree.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(0);
+    /// let child = tree.insert(1, root);
+    /// tree.insert(2, root);
+    ///
+    /// assert_eq!(tree.child_count(root), 2);
+    /// assert_eq!(tree.child_count(child), 0);
+    /// ```
+    pub fn child_count(&self, node_id: Index) -> usize {
+        self.children(node_id).count()
+    }
+
+    /// Return the `n`-th child (zero-based) of the node at index `node_id`.
+    ///
+    /// If the node has `n` children or less, or is not in the tree, `None` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(0);
+    /// tree.insert(1, root);
+    /// let child = tree.insert(2, root);
+    ///
+    /// assert_eq!(tree.nth_child(root, 1), Some(child));
+    /// assert_eq!(tree.nth_child(root, 2), None);
+    /// ```
+    pub fn nth_child(&self, node_id: Index, n: usize) -> Option<Index> {
+        let mut child_id = self.first_child(node_id);
+        for _ in 0..n {
+            child_id = self.nodes[child_id?].next_sibling;
+        }
+        child_id
+    }
+
+    /// Binary search the children of the node at index `node_id`, which are expected to be
+    /// sorted, with a comparator function.
+    ///
+    /// The comparator function should return an order code that indicates whether its argument
+    /// is `Less`, `Equal` or `Greater` the desired target, like `slice::binary_search_by`.
+    ///
+    /// If a matching child is found, `Ok` is returned with its index in the tree. Otherwise
+    /// `Err` is returned with the position where a matching child could be inserted while
+    /// maintaining sorted order.
+    ///
+    /// The search takes logarithmic time when the children were inserted with `insert_sorted`.
+    /// Otherwise they are collected into a vector first, which takes linear time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(0);
+    /// tree.insert_sorted(30, root, |a, b| a.cmp(b));
+    /// let child = tree.insert_sorted(20, root, |a, b| a.cmp(b));
+    /// tree.insert_sorted(10, root, |a, b| a.cmp(b));
+    ///
+    /// assert_eq!(tree.binary_search_child(root, |value| value.cmp(&20)), Ok(child));
+    /// assert_eq!(tree.binary_search_child(root, |value| value.cmp(&25)), Err(2));
+    /// ```
+    pub fn binary_search_child<F>(&self, node_id: Index, mut f: F) -> Result<Index, usize>
+    where
+        F: FnMut(&T) -> Ordering,
+    {
+        let mut search = |children: &[Index]| {
+            children
+                .binary_search_by(|&child_id| f(&self.nodes[child_id].data))
+                .map(|position| children[position])
+        };
+
+        match self.sorted_children.get(&node_id) {
+            Some(children) => search(children),
+            None => search(&self.children(node_id).collect::<Vec<_>>()),
+        }
+    }
+
+    /// Return the position (zero-based) of the node at index `node_id` among its parent’s
+    /// children.
+    ///
+    /// If the node has no parent, or is not in the tree, `None` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(0);
+    /// tree.insert(1, root);
+    /// let child = tree.insert(2, root);
+    ///
+    /// assert_eq!(tree.sibling_position(child), Some(1));
+    /// assert_eq!(tree.sibling_position(root), None);
+    /// ```
+    pub fn sibling_position(&self, node_id: Index) -> Option<usize> {
+        self.parent(node_id)?;
+        Some(self.preceding_siblings(node_id).count() - 1)
+    }
+
+    /// Return the depth of the node at index `node_id`, that is the number of edges between the
+    /// node and the root of its tree.
+    ///
+    /// If the node is not in the tree, `None` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(0);
+    /// let child = tree.insert(1, root);
+    /// let grandchild = tree.insert(2, child);
+    ///
+    /// assert_eq!(tree.depth(root), Some(0));
+    /// assert_eq!(tree.depth(grandchild), Some(2));
+    /// ```
+    pub fn depth(&self, node_id: Index) -> Option<u32> {
+        if !self.contains(node_id) {
+            return None;
+        }
+
+        Some(self.ancestors(node_id).skip(1).count() as u32)
+    }
+
+    /// Return the height of the subtree rooted at `node_id`, that is the number of edges on the
+    /// longest downward path between the node and a leaf.
+    ///
+    /// If the node is not in the tree, `None` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(0);
+    /// let child = tree.insert(1, root);
+    /// let grandchild = tree.insert(2, child);
+    ///
+    /// assert_eq!(tree.height(root), Some(2));
+    /// assert_eq!(tree.height(grandchild), Some(0));
+    /// ```
+    pub fn height(&self, node_id: Index) -> Option<u32> {
+        if !self.contains(node_id) {
+            return None;
+        }
+
+        self.descendants_with_depth(node_id)
+            .map(|(_, depth)| depth)
+            .max()
+    }
+
+    /// Return the number of nodes in the subtree rooted at `node_id`, the node itself included.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node is not in the tree.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(0);
+    /// let child = tree.insert(1, root);
+    /// tree.insert(2, child);
+    ///
+    /// assert_eq!(tree.subtree_size(root), 3);
+    /// assert_eq!(tree.subtree_size(child), 2);
+    /// ```
+    pub fn subtree_size(&self, node_id: Index) -> usize {
+        self.descendants(node_id).count()
+    }
+
+    /// Return a hash of the subtree rooted at the node at index `node_id`, computed from the data
+    /// and child count of its nodes in tree order.
+    ///
+    /// Subtrees with the same shape and equal data have the same hash, which allows to detect
+    /// duplicated subtrees.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node is not in the tree.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(0);
+    /// let child_1 = tree.insert(1, root);
+    /// tree.insert(2, child_1);
+    /// let child_2 = tree.insert(1, root);
+    /// tree.insert(2, child_2);
+    ///
+    /// assert_eq!(tree.subtree_hash(child_1), tree.subtree_hash(child_2));
+    /// assert_ne!(tree.subtree_hash(root), tree.subtree_hash(child_1));
+    /// ```
+    pub fn subtree_hash(&self, node_id: Index) -> u64
+    where
+        T: Hash,
+    {
+        let mut hasher = DefaultHasher::new();
+        self.hash_subtree(node_id, &mut hasher);
+        hasher.finish()
+    }
+
+    fn hash_subtree<H: Hasher>(&self, node_id: Index, state: &mut H)
+    where
+        T: Hash,
+    {
+        for node_id in self.descendants(node_id) {
+            self[node_id].hash(state);
+            self.child_count(node_id).hash(state);
+        }
+    }
+
+    /// Return the depth of the deepest node of the tree.
+    ///
+    /// If the tree has no root node, `None` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// assert_eq!(tree.max_depth(), None);
+    ///
+    /// let root = tree.insert_root(0);
+    /// let child = tree.insert(1, root);
+    /// tree.insert(2, child);
+    ///
+    /// assert_eq!(tree.max_depth(), Some(2));
+    /// ```
+    pub fn max_depth(&self) -> Option<u32> {
+        self.height(self.root_index?)
+    }
+
+    /// Return the number of leaves, i.e. nodes without children, in the tree.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// assert_eq!(tree.leaf_count(), 0);
+    ///
+    /// let root = tree.insert_root(0);
+    /// let child = tree.insert(1, root);
+    /// tree.insert(2, root);
+    /// tree.insert(3, child);
+    ///
+    /// assert_eq!(tree.leaf_count(), 2);
+    /// ```
+    pub fn leaf_count(&self) -> usize {
+        match self.root_index {
+            Some(root_index) => self
+                .descendants(root_index)
+                .filter(|&node_id| self.nodes[node_id].first_child.is_none())
+                .count(),
+            None => 0,
+        }
+    }
+
+    /// Compute statistics about the shape of the tree in a single traversal.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(0);
+    /// let child = tree.insert(1, root);
+    /// tree.insert(2, root);
+    /// tree.insert(3, child);
+    ///
+    /// let stats = tree.stats();
+    /// assert_eq!(stats.node_count, 4);
+    /// assert_eq!(stats.leaf_count, 2);
+    /// assert_eq!(stats.max_depth, 2);
+    /// assert_eq!(stats.depth_histogram, [1, 2, 1]);
+    /// assert_eq!(stats.arity_histogram, [2, 1, 1]);
+    /// ```
+    pub fn stats(&self) -> TreeStats {
+        let mut stats = TreeStats::default();
+
+        let root_index = match self.root_index {
+            Some(root_index) => root_index,
+            None => return stats,
+        };
+
+        let mut depth_sum = 0u64;
+
+        for (node_id, depth) in self.descendants_with_depth(root_index) {
+            let arity = self.child_count(node_id);
+
+            stats.node_count += 1;
+            if arity == 0 {
+                stats.leaf_count += 1;
+            }
+            stats.max_depth = stats.max_depth.max(depth);
+            depth_sum += u64::from(depth);
+
+            let depth = depth as usize;
+            if stats.depth_histogram.len() <= depth {
+                stats.depth_histogram.resize(depth + 1, 0);
+            }
+            stats.depth_histogram[depth] += 1;
+
+            if stats.arity_histogram.len() <= arity {
+                stats.arity_histogram.resize(arity + 1, 0);
+            }
+            stats.arity_histogram[arity] += 1;
+        }
+
+        stats.average_depth = depth_sum as f64 / stats.node_count as f64;
+
+        stats
+    }
+
+    /// Return the lowest common ancestor of the nodes at index `a` and `b`, that is the deepest
+    /// node having both of them as descendants. A node is considered a descendant of itself.
+    ///
+    /// If one of the nodes is not in the tree, `None` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(0);
+    /// let child_1 = tree.insert(1, root);
+    /// let child_2 = tree.insert(2, root);
+    /// let grandchild = tree.insert(3, child_1);
+    ///
+    /// assert_eq!(tree.lca(grandchild, child_2), Some(root));
+    /// assert_eq!(tree.lca(grandchild, child_1), Some(child_1));
+    /// ```
+    pub fn lca(&self, a: Index, b: Index) -> Option<Index> {
+        let mut depth_a = self.depth(a)?;
+        let mut depth_b = self.depth(b)?;
+        let mut a = a;
+        let mut b = b;
+
+        while depth_a > depth_b {
+            a = self.nodes[a].parent?;
+            depth_a -= 1;
+        }
+
+        while depth_b > depth_a {
+            b = self.nodes[b].parent?;
+            depth_b -= 1;
+        }
+
+        while a != b {
+            a = self.nodes[a].parent?;
+            b = self.nodes[b].parent?;
+        }
+
+        Some(a)
+    }
+
+    /// Return the lowest common ancestor of each pair of nodes in `queries`, using Tarjan’s
+    /// offline algorithm in a single traversal of the tree.
+    ///
+    /// The result at a given position is the same as `lca` would return for the query at that
+    /// position, `None` if one of the nodes is not in the tree. This is much faster than calling
+    /// `lca` repeatedly when there are many queries.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(0);
+    /// let child_1 = tree.insert(1, root);
+    /// let child_2 = tree.insert(2, root);
+    /// let grandchild = tree.insert(3, child_1);
+    ///
+    /// assert_eq!(
+    ///     tree.lca_batch(&[(grandchild, child_2), (grandchild, child_1)]),
+    ///     [Some(root), Some(child_1)]
+    /// );
+    /// ```
+    pub fn lca_batch(&self, queries: &[(Index, Index)]) -> Vec<Option<Index>> {
+        let mut results = vec![None; queries.len()];
+
+        let root_index = match self.root_index {
+            Some(root_index) => root_index,
+            None => return results,
+        };
+
+        // Give each node a dense id, in tree order, for the disjoint-set forest.
+        let node_ids = self.descendants(root_index).collect::<Vec<_>>();
+        let dense_ids = node_ids
+            .iter()
+            .enumerate()
+            .map(|(dense_id, &node_id)| (node_id, dense_id))
+            .collect::<HashMap<_, _>>();
+
+        let mut node_queries = vec![Vec::new(); node_ids.len()];
+        for (query_id, &(a, b)) in queries.iter().enumerate() {
+            if let (Some(&a), Some(&b)) = (dense_ids.get(&a), dense_ids.get(&b)) {
+                node_queries[a].push((b, query_id));
+                node_queries[b].push((a, query_id));
+            }
+        }
+
+        let mut sets = (0..node_ids.len()).collect::<Vec<_>>();
+        let mut ancestors = (0..node_ids.len()).collect::<Vec<_>>();
+        let mut visited = vec![false; node_ids.len()];
+
+        fn find(sets: &mut [usize], mut id: usize) -> usize {
+            while sets[id] != id {
+                sets[id] = sets[sets[id]];
+                id = sets[id];
+            }
+            id
+        }
+
+        for edge in self.traverse(root_index) {
+            // At the end of a node, all its descendants have been merged into its set.
+            if let NodeEdge::End(node_id) = edge {
+                let id = dense_ids[&node_id];
+                visited[id] = true;
+
+                for &(other_id, query_id) in &node_queries[id] {
+                    if visited[other_id] {
+                        let set = find(&mut sets, other_id);
+                        results[query_id] = Some(node_ids[ancestors[set]]);
+                    }
+                }
+
+                if let Some(parent) = self.nodes[node_id].parent {
+                    let parent_id = dense_ids[&parent];
+                    let set = find(&mut sets, id);
+                    let parent_set = find(&mut sets, parent_id);
+                    sets[set] = parent_set;
+                    ancestors[parent_set] = parent_id;
+                }
+            }
+        }
+
+        results
+    }
+
+    /// Is the node at index `ancestor_id` a strict ancestor of the node at index `node_id`?
+    ///
+    /// Returns `false` if one of the nodes is not in the tree.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(0);
+    /// let child = tree.insert(1, root);
+    /// let grandchild = tree.insert(2, child);
+    ///
+    /// assert!(tree.is_ancestor_of(root, grandchild));
+    /// assert!(!tree.is_ancestor_of(grandchild, root));
+    /// assert!(!tree.is_ancestor_of(child, child));
+    /// ```
+    pub fn is_ancestor_of(&self, ancestor_id: Index, node_id: Index) -> bool {
+        if !self.contains(ancestor_id) || !self.contains(node_id) {
+            return false;
+        }
+
+        self.ancestors(node_id)
+            .skip(1)
+            .any(|node_id| node_id == ancestor_id)
+    }
+
+    /// Is the node at index `descendant_id` a strict descendant of the node at index `node_id`?
+    ///
+    /// Returns `false` if one of the nodes is not in the tree.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(0);
+    /// let child = tree.insert(1, root);
+    ///
+    /// assert!(tree.is_descendant_of(child, root));
+    /// assert!(!tree.is_descendant_of(root, child));
+    /// ```
+    pub fn is_descendant_of(&self, descendant_id: Index, node_id: Index) -> bool {
+        self.is_ancestor_of(node_id, descendant_id)
+    }
+
+    /// Return the number of edges on the path between the nodes at index `a` and `b`.
+    ///
+    /// If one of the nodes is not in the tree, `None` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(0);
+    /// let child_1 = tree.insert(1, root);
+    /// let child_2 = tree.insert(2, root);
+    /// let grandchild = tree.insert(3, child_1);
+    ///
+    /// assert_eq!(tree.distance(grandchild, child_2), Some(3));
+    /// assert_eq!(tree.distance(root, root), Some(0));
+    /// ```
+    pub fn distance(&self, a: Index, b: Index) -> Option<u32> {
+        let lca = self.lca(a, b)?;
+
+        Some(self.depth(a)? + self.depth(b)? - 2 * self.depth(lca)?)
+    }
+
+    /// Return the node reached by starting at the root and following, for each element of
+    /// `path`, the child at that position.
+    ///
+    /// An empty path addresses the root. If the tree has no root node or the path does not lead
+    /// to a node, `None` is returned. This is the inverse of `path_of`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(0);
+    /// tree.insert(1, root);
+    /// let child = tree.insert(2, root);
+    /// let grandchild = tree.insert(3, child);
+    ///
+    /// assert_eq!(tree.get_by_path(&[]), Some(root));
+    /// assert_eq!(tree.get_by_path(&[1, 0]), Some(grandchild));
+    /// assert_eq!(tree.get_by_path(&[0, 0]), None);
+    /// ```
+    pub fn get_by_path(&self, path: &[usize]) -> Option<Index> {
+        let mut node_id = self.root_index?;
+        for &position in path {
+            node_id = self.nth_child(node_id, position)?;
+        }
+        Some(node_id)
+    }
+
+    /// Return the positions of the children to follow from the root to reach the node at index
+    /// `node_id`.
+    ///
+    /// If the node is not in the tree, `None` is returned. This is the inverse of
+    /// `get_by_path`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(0);
+    /// tree.insert(1, root);
+    /// let child = tree.insert(2, root);
+    /// let grandchild = tree.insert(3, child);
+    ///
+    /// assert_eq!(tree.path_of(root), Some(vec![]));
+    /// assert_eq!(tree.path_of(grandchild), Some(vec![1, 0]));
+    /// ```
+    pub fn path_of(&self, node_id: Index) -> Option<Vec<usize>> {
+        if !self.contains(node_id) {
+            return None;
+        }
+
+        let mut path = self
+            .ancestors(node_id)
+            .filter_map(|node_id| self.sibling_position(node_id))
+            .collect::<Vec<_>>();
+        path.reverse();
+
+        Some(path)
+    }
+
+    /// Return an iterator of references to this node’s children.
+    ///
+    /// The iterator is double-ended, so the children can be iterated from right to left with
+    /// `rev()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(0);
+    /// tree.insert(1, root);
+    /// tree.insert(2, root);
+    /// tree.insert(3, root);
+    ///
+    /// let values = tree
+    ///     .children(root)
+    ///     .rev()
+    ///     .map(|node_id| tree[node_id])
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(values, [3, 2, 1]);
+    /// ```
+    pub fn children(&self, node_id: Index) -> ChildrenIter<T> {
+        let node = &self.nodes[node_id];
+        ChildrenIter {
+            tree: self,
+            node_id: node.first_child,
+            back_node_id: node.last_child,
+        }
+    }
+
+    /// Return an iterator of references to this node and the siblings before it.
+    ///
+    /// Call `.next().unwrap()` once on the iterator to skip the node itself.
+    pub fn preceding_siblings(&self, node_id: Index) -> PrecedingSiblingsIter<T> {
+        PrecedingSiblingsIter {
+            tree: self,
+            node_id: Some(node_id),
+        }
+    }
+
+    /// Return an iterator of references to this node and the siblings after it.
+    ///
+    /// Call `.next().unwrap()` once on the iterator to skip the node itself.
+    pub fn following_siblings(&self, node_id: Index) -> FollowingSiblingsIter<T> {
+        FollowingSiblingsIter {
+            tree: self,
+            node_id: Some(node_id),
+        }
+    }
+
+    /// Return an iterator of references to this node and its ancestors.
+    ///
+    /// Call `.next().unwrap()` once on the iterator to skip the node itself.
     pub fn ancestors(&self, node_id: Index) -> AncestorsIter<T> {
         AncestorsIter {
             tree: self,
-            node_id: Some(node_id),
+            node_id: Some(node_id),
+        }
+    }
+
+    /// Return an iterator of references to this node and its descendants, in tree order.
+    ///
+    /// Each node is yielded twice, as a `NodeEdge::Start` before its descendants and as a
+    /// `NodeEdge::End` after them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::{NodeEdge, VecTree};
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(0);
+    /// let child = tree.insert(1, root);
+    ///
+    /// let edges = tree.traverse(root).collect::<Vec<_>>();
+    ///
+    /// assert_eq!(
+    ///     edges,
+    ///     [
+    ///         NodeEdge::Start(root),
+    ///         NodeEdge::Start(child),
+    ///         NodeEdge::End(child),
+    ///         NodeEdge::End(root),
+    ///     ]
+    /// );
+    /// ```
+    pub fn traverse(&self, node_id: Index) -> TraverseIter<'_, T> {
+        TraverseIter {
+            tree: self,
+            root: node_id,
+            next: Some(NodeEdge::Start(node_id)),
+            next_back: Some(NodeEdge::End(node_id)),
+        }
+    }
+
+    /// Resume a traversal from a cursor saved with `TraverseIter::cursor`.
+    ///
+    /// The traversal continues from the position of the nodes in the tree at the time it is
+    /// resumed. If the root of the traversal or one of the next nodes to visit has been removed
+    /// from the tree since, `None` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(0);
+    /// tree.insert(1, root);
+    /// tree.insert(2, root);
+    ///
+    /// // Process a first slice of the traversal, then save its progress.
+    /// let mut traverse = tree.traverse(root);
+    /// let first_slice = traverse.by_ref().take(2).collect::<Vec<_>>();
+    /// let cursor = traverse.cursor();
+    ///
+    /// let rest = tree.resume_traverse(cursor).unwrap().collect::<Vec<_>>();
+    ///
+    /// assert_eq!(
+    ///     first_slice.into_iter().chain(rest).collect::<Vec<_>>(),
+    ///     tree.traverse(root).collect::<Vec<_>>()
+    /// );
+    /// ```
+    pub fn resume_traverse(&self, cursor: TraverseCursor) -> Option<TraverseIter<'_, T>> {
+        let is_valid = |edge: Option<NodeEdge<Index>>| match edge {
+            Some(NodeEdge::Start(node_id)) | Some(NodeEdge::End(node_id)) => self.contains(node_id),
+            None => true,
+        };
+
+        if !self.contains(cursor.root) || !is_valid(cursor.next) || !is_valid(cursor.next_back) {
+            return None;
+        }
+
+        Some(TraverseIter {
+            tree: self,
+            root: cursor.root,
+            next: cursor.next,
+            next_back: cursor.next_back,
+        })
+    }
+
+    /// Return an iterator of references to this node and its descendants, with depth relative to
+    /// the node, in tree order.
+    ///
+    /// Each node is yielded twice, as a `NodeEdgeWithDepth::Start` before its descendants and as
+    /// a `NodeEdgeWithDepth::End` after them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::{NodeEdgeWithDepth, VecTree};
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(0);
+    /// let child = tree.insert(1, root);
+    ///
+    /// let edges = tree.traverse_with_depth(root).collect::<Vec<_>>();
+    ///
+    /// assert_eq!(
+    ///     edges,
+    ///     [
+    ///         NodeEdgeWithDepth::Start(root, 0),
+    ///         NodeEdgeWithDepth::Start(child, 1),
+    ///         NodeEdgeWithDepth::End(child, 1),
+    ///         NodeEdgeWithDepth::End(root, 0),
+    ///     ]
+    /// );
+    /// ```
+    pub fn traverse_with_depth(&self, node_id: Index) -> TraverseWithDepthIter<'_, T> {
+        TraverseWithDepthIter {
+            tree: self,
+            root: node_id,
+            max_depth: u32::MAX,
+            next: Some(NodeEdgeWithDepth::Start(node_id, 0)),
+        }
+    }
+
+    /// Return an iterator of references to this node and its descendants, in tree order.
+    ///
+    /// Parent nodes appear before the descendants.
+    /// Call `.next().unwrap()` once on the iterator to skip the node itself.
+    ///
+    /// The iterator is double-ended, `rev()` yields the nodes in reverse tree order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(0);
+    /// let child = tree.insert(1, root);
+    /// tree.insert(2, child);
+    /// tree.insert(3, root);
+    ///
+    /// let values = tree
+    ///     .descendants(root)
+    ///     .rev()
+    ///     .map(|node_id| tree[node_id])
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(values, [3, 2, 1, 0]);
+    /// ```
+    pub fn descendants(&self, node_id: Index) -> DescendantsIter<T> {
+        DescendantsIter(self.traverse(node_id))
+    }
+
+    /// Return an iterator of references to this node and its descendants, with deoth in the tree,
+    /// in tree order.
+    ///
+    /// Parent nodes appear before the descendants.
+    /// Call `.next().unwrap()` once on the iterator to skip the node itself.
+    pub fn descendants_with_depth(&self, node_id: Index) -> DescendantsWithDepthIter<T> {
+        DescendantsWithDepthIter(self.traverse_with_depth(node_id))
+    }
+
+    /// Return an iterator of references to this node and its descendants, in tree order, without
+    /// going deeper than `max_depth` levels below the node.
+    ///
+    /// The nodes below `max_depth` are not visited at all. Parent nodes appear before the
+    /// descendants. Call `.next().unwrap()` once on the iterator to skip the node itself.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(0);
+    /// let child = tree.insert(1, root);
+    /// tree.insert(2, child);
+    /// tree.insert(3, root);
+    ///
+    /// let descendants = tree
+    ///     .descendants_until_depth(root, 1)
+    ///     .map(|node_id| tree[node_id])
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(descendants, [0, 1, 3]);
+    /// ```
+    pub fn descendants_until_depth(
+        &self,
+        node_id: Index,
+        max_depth: u32,
+    ) -> DescendantsUntilDepthIter<'_, T> {
+        let mut traverse = self.traverse_with_depth(node_id);
+        traverse.max_depth = max_depth;
+        DescendantsUntilDepthIter(traverse)
+    }
+
+    /// Return an iterator of references to the leaves of the subtree rooted at this node, in
+    /// tree order.
+    ///
+    /// If the node has no children, it is its own only leaf.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(0);
+    /// let child = tree.insert(1, root);
+    /// tree.insert(2, child);
+    /// tree.insert(3, root);
+    ///
+    /// let leaves = tree
+    ///     .leaves(root)
+    ///     .map(|node_id| tree[node_id])
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(leaves, [2, 3]);
+    /// ```
+    pub fn leaves(&self, node_id: Index) -> LeavesIter<'_, T> {
+        LeavesIter(self.traverse(node_id))
+    }
+
+    /// Return an iterator of references to this node and its descendants, with depth relative
+    /// to the node, in breadth-first order.
+    ///
+    /// All the nodes at a given depth appear before the nodes at the next depth.
+    /// Call `.next().unwrap()` once on the iterator to skip the node itself.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(0);
+    /// let child = tree.insert(1, root);
+    /// tree.insert(2, child);
+    /// tree.insert(3, root);
+    ///
+    /// let nodes = tree
+    ///     .breadth_first_with_depth(root)
+    ///     .map(|(node_id, depth)| (tree[node_id], depth))
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(nodes, [(0, 0), (1, 1), (3, 1), (2, 2)]);
+    /// ```
+    pub fn breadth_first_with_depth(&self, node_id: Index) -> BreadthFirstWithDepthIter<'_, T> {
+        let mut queue = VecDeque::new();
+        queue.push_back((node_id, 0));
+
+        BreadthFirstWithDepthIter { tree: self, queue }
+    }
+
+    /// Return an iterator over the levels of the subtree rooted at this node, each level being
+    /// the nodes at the same depth, in tree order.
+    ///
+    /// The first level only contains the node itself.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(0);
+    /// let child = tree.insert(1, root);
+    /// tree.insert(2, child);
+    /// tree.insert(3, root);
+    ///
+    /// let levels = tree
+    ///     .levels(root)
+    ///     .map(|level| level.iter().map(|&node_id| tree[node_id]).collect::<Vec<_>>())
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(levels, [vec![0], vec![1, 3], vec![2]]);
+    /// ```
+    pub fn levels(&self, node_id: Index) -> LevelsIter<'_, T> {
+        LevelsIter {
+            tree: self,
+            level: vec![node_id],
+        }
+    }
+
+    /// Return an iterator of references to this node and its descendants using an
+    /// iterative-deepening depth-first search.
+    ///
+    /// Nodes are yielded in the same order as a breadth-first search, shallowest first, but
+    /// memory usage only grows with the depth of the tree instead of its width. This comes at the
+    /// cost of visiting the upper levels again for each depth.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(0);
+    /// let child = tree.insert(1, root);
+    /// tree.insert(2, child);
+    /// tree.insert(3, root);
+    ///
+    /// let nodes = tree
+    ///     .iddfs(root)
+    ///     .map(|node_id| tree[node_id])
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(nodes, [0, 1, 3, 2]);
+    /// ```
+    pub fn iddfs(&self, node_id: Index) -> IddfsIter<'_, T> {
+        let mut traverse = self.traverse_with_depth(node_id);
+        traverse.max_depth = 0;
+
+        IddfsIter {
+            traverse,
+            found: false,
+        }
+    }
+
+    /// Return an iterator of references to this node’s children, along with their data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(0);
+    /// let child = tree.insert(1, root);
+    ///
+    /// assert_eq!(tree.children_values(root).collect::<Vec<_>>(), [(child, &1)]);
+    /// ```
+    pub fn children_values(&self, node_id: Index) -> ValuesIter<'_, T, ChildrenIter<'_, T>> {
+        ValuesIter {
+            tree: self,
+            iter: self.children(node_id),
+        }
+    }
+
+    /// Return an iterator of references to this node and its ancestors, along with their data.
+    ///
+    /// Call `.next().unwrap()` once on the iterator to skip the node itself.
+    pub fn ancestors_values(&self, node_id: Index) -> ValuesIter<'_, T, AncestorsIter<'_, T>> {
+        ValuesIter {
+            tree: self,
+            iter: self.ancestors(node_id),
+        }
+    }
+
+    /// Return an iterator of references to this node and its descendants, along with their
+    /// data, in tree order.
+    ///
+    /// Parent nodes appear before the descendants.
+    /// Call `.next().unwrap()` once on the iterator to skip the node itself.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(0);
+    /// tree.insert(1, root);
+    /// tree.insert(2, root);
+    ///
+    /// let sum: i32 = tree.descendants_values(root).map(|(_, value)| value).sum();
+    /// assert_eq!(sum, 3);
+    /// ```
+    pub fn descendants_values(&self, node_id: Index) -> ValuesIter<'_, T, DescendantsIter<'_, T>> {
+        ValuesIter {
+            tree: self,
+            iter: self.descendants(node_id),
+        }
+    }
+
+    /// Call `f` with an exclusive reference to the data of each of this node’s children, in
+    /// order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node is not in the tree.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(0);
+    /// let child_1 = tree.insert(1, root);
+    /// let child_2 = tree.insert(2, root);
+    ///
+    /// tree.for_each_child_mut(root, |_, value| *value += 10);
+    ///
+    /// assert_eq!(tree[root], 0);
+    /// assert_eq!(tree[child_1], 11);
+    /// assert_eq!(tree[child_2], 12);
+    /// ```
+    pub fn for_each_child_mut<F>(&mut self, node_id: Index, mut f: F)
+    where
+        F: FnMut(Index, &mut T),
+    {
+        let mut next = self.nodes[node_id].first_child;
+        while let Some(child_id) = next {
+            let child = &mut self.nodes[child_id];
+            f(child_id, &mut child.data);
+            next = child.next_sibling;
+        }
+    }
+
+    /// Call `f` with an exclusive reference to the data of this node and each of its
+    /// descendants, in tree order.
+    ///
+    /// Parent nodes are visited before the descendants.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node is not in the tree.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(0);
+    /// let child = tree.insert(1, root);
+    /// let grandchild = tree.insert(2, child);
+    ///
+    /// tree.for_each_descendant_mut(child, |_, value| *value *= 10);
+    ///
+    /// assert_eq!(tree[root], 0);
+    /// assert_eq!(tree[child], 10);
+    /// assert_eq!(tree[grandchild], 20);
+    /// ```
+    pub fn for_each_descendant_mut<F>(&mut self, node_id: Index, mut f: F)
+    where
+        F: FnMut(Index, &mut T),
+    {
+        let mut next = Some(node_id);
+        while let Some(current_id) = next {
+            let node = &mut self.nodes[current_id];
+            f(current_id, &mut node.data);
+
+            next = match node.first_child {
+                Some(first_child) => Some(first_child),
+                None => self.next_after_subtree_within(current_id, node_id),
+            };
+        }
+    }
+
+    /// Call `f` for each edge of this node and its descendants in tree order, with an exclusive
+    /// reference to the data of the node.
+    ///
+    /// Each node is visited twice, as a `NodeEdge::Start` before its descendants and as a
+    /// `NodeEdge::End` after them. This allows single-pass transformations that need to propagate
+    /// data down the tree, or to aggregate it up.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node is not in the tree.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::{NodeEdge, VecTree};
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(0);
+    /// let child_1 = tree.insert(0, root);
+    /// let grandchild = tree.insert(0, child_1);
+    /// let child_2 = tree.insert(0, root);
+    ///
+    /// // Number the nodes in tree order.
+    /// let mut number = 0;
+    /// tree.traverse_mut(root, |edge, value| {
+    ///     if let NodeEdge::Start(_) = edge {
+    ///         *value = number;
+    ///         number += 1;
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(tree[child_1], 1);
+    /// assert_eq!(tree[grandchild], 2);
+    /// assert_eq!(tree[child_2], 3);
+    /// ```
+    pub fn traverse_mut<F>(&mut self, node_id: Index, mut f: F)
+    where
+        F: FnMut(NodeEdge<Index>, &mut T),
+    {
+        let mut next = Some(NodeEdge::Start(node_id));
+        while let Some(edge) = next {
+            next = match edge {
+                NodeEdge::Start(current_id) => {
+                    let node = &mut self.nodes[current_id];
+                    f(edge, &mut node.data);
+
+                    match node.first_child {
+                        Some(first_child) => Some(NodeEdge::Start(first_child)),
+                        None => Some(NodeEdge::End(current_id)),
+                    }
+                }
+                NodeEdge::End(current_id) => {
+                    let node = &mut self.nodes[current_id];
+                    f(edge, &mut node.data);
+
+                    if current_id == node_id {
+                        None
+                    } else {
+                        match node.next_sibling {
+                            Some(next_sibling) => Some(NodeEdge::Start(next_sibling)),
+                            None => node.parent.map(NodeEdge::End),
+                        }
+                    }
+                }
+            };
+        }
+    }
+
+    /// Compute a value for this node from its data and the values computed for its children,
+    /// evaluating `f` bottom-up over the subtree (in post-order).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node is not in the tree.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// enum Expr {
+    ///     Num(i32),
+    ///     Add,
+    ///     Mul,
+    /// }
+    ///
+    /// // (1 + 2) * 4
+    /// let mut tree = VecTree::new();
+    /// let mul = tree.insert_root(Expr::Mul);
+    /// let add = tree.insert(Expr::Add, mul);
+    /// tree.insert(Expr::Num(1), add);
+    /// tree.insert(Expr::Num(2), add);
+    /// tree.insert(Expr::Num(4), mul);
+    ///
+    /// let result = tree.fold_subtree(mul, |expr, operands: &[i32]| match expr {
+    ///     Expr::Num(n) => *n,
+    ///     Expr::Add => operands.iter().sum(),
+    ///     Expr::Mul => operands.iter().product(),
+    /// });
+    ///
+    /// assert_eq!(result, 12);
+    /// ```
+    pub fn fold_subtree<R, F>(&self, node_id: Index, mut f: F) -> R
+    where
+        F: FnMut(&T, &[R]) -> R,
+    {
+        // The results of the children of each node being folded, from the root to the deepest.
+        let mut stack: Vec<Vec<R>> = Vec::new();
+
+        for edge in self.traverse(node_id) {
+            match edge {
+                NodeEdge::Start(_) => stack.push(Vec::new()),
+                NodeEdge::End(current_id) => {
+                    let children_results = stack.pop().unwrap();
+                    let result = f(&self.nodes[current_id].data, &children_results);
+
+                    match stack.last_mut() {
+                        Some(parent_results) => parent_results.push(result),
+                        None => return result,
+                    }
+                }
+            }
+        }
+
+        unreachable!("The traversal always ends with the node itself")
+    }
+
+    /// Compute a value for this node and each of its descendants from the value computed for
+    /// their parent, evaluating `f` top-down over the subtree (in pre-order) with an exclusive
+    /// reference to the data of each node.
+    ///
+    /// The value of the parent of the node itself is `seed`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node is not in the tree.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// // Local offsets, to be turned into absolute positions.
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(10);
+    /// let child = tree.insert(5, root);
+    /// let grandchild = tree.insert(1, child);
+    ///
+    /// tree.propagate_down(root, 100, |parent_position, offset| {
+    ///     *offset += parent_position;
+    ///     *offset
+    /// });
+    ///
+    /// assert_eq!(tree[root], 110);
+    /// assert_eq!(tree[child], 115);
+    /// assert_eq!(tree[grandchild], 116);
+    /// ```
+    pub fn propagate_down<R, F>(&mut self, node_id: Index, seed: R, mut f: F)
+    where
+        F: FnMut(&R, &mut T) -> R,
+    {
+        // The values of the ancestors of the current node, from the root to the deepest.
+        let mut stack = Vec::new();
+
+        self.traverse_mut(node_id, |edge, data| match edge {
+            NodeEdge::Start(_) => {
+                let result = f(stack.last().unwrap_or(&seed), data);
+                stack.push(result);
+            }
+            NodeEdge::End(_) => {
+                stack.pop();
+            }
+        });
+    }
+
+    /// Create a new tree with the same structure as this one, the data of each node being
+    /// computed by `f` from the index and the data of the corresponding node of this tree.
+    ///
+    /// The nodes of the new tree have different indices, `map_with_remap` also returns the new
+    /// index of each node, and `zip` pairs them with the nodes of this tree.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(1);
+    /// tree.insert(2, root);
+    /// tree.insert(3, root);
+    ///
+    /// let labels = tree.map(|_, value| format!("node {}", value));
+    ///
+    /// let labels_root = labels.get_root_index().unwrap();
+    /// let names = labels
+    ///     .children(labels_root)
+    ///     .map(|node_id| labels[node_id].as_str())
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(labels[labels_root], "node 1");
+    /// assert_eq!(names, ["node 2", "node 3"]);
+    /// ```
+    pub fn map<U, F>(&self, f: F) -> VecTree<U>
+    where
+        F: FnMut(Index, &T) -> U,
+    {
+        self.map_nodes(f, |_, _| {})
+    }
+
+    /// Create a new tree with the same structure as this one, like `map`, along with a map from
+    /// the index of each node of this tree to the index of the corresponding node of the new
+    /// tree.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(1);
+    /// let child = tree.insert(2, root);
+    ///
+    /// let (doubled, remap) = tree.map_with_remap(|_, value| value * 2);
+    ///
+    /// assert_eq!(doubled[remap[&child]], 4);
+    /// assert_eq!(doubled.parent(remap[&child]), Some(remap[&root]));
+    /// ```
+    pub fn map_with_remap<U, F>(&self, f: F) -> (VecTree<U>, HashMap<Index, Index>)
+    where
+        F: FnMut(Index, &T) -> U,
+    {
+        let mut remap = HashMap::with_capacity(self.nodes.len());
+        let mapped = self.map_nodes(f, |node_id, new_node_id| {
+            remap.insert(node_id, new_node_id);
+        });
+
+        (mapped, remap)
+    }
+
+    // Create the tree of `map`, calling `inserted` with the old and new index of each node.
+    fn map_nodes<U, F, I>(&self, mut f: F, mut inserted: I) -> VecTree<U>
+    where
+        F: FnMut(Index, &T) -> U,
+        I: FnMut(Index, Index),
+    {
+        let mut mapped = VecTree::with_capacity(self.nodes.len());
+
+        let root_index = match self.root_index {
+            Some(root_index) => root_index,
+            None => return mapped,
+        };
+
+        // The new indices of the ancestors of the current node.
+        let mut parents = Vec::new();
+
+        for edge in self.traverse(root_index) {
+            match edge {
+                NodeEdge::Start(node_id) => {
+                    let data = f(node_id, &self.nodes[node_id].data);
+                    let new_node_id = match parents.last() {
+                        Some(&parent_id) => mapped.insert(data, parent_id),
+                        None => mapped.insert_root(data),
+                    };
+                    inserted(node_id, new_node_id);
+                    parents.push(new_node_id);
+                }
+                NodeEdge::End(_) => {
+                    parents.pop();
+                }
+            }
+        }
+
+        mapped
+    }
+
+    /// Create a new tree with the structure of this one, the data of each node being computed by
+    /// `f` from the index and the data of the corresponding node of this tree.
+    ///
+    /// Nodes for which `f` returns `None` are left out along with their descendants, `f` is not
+    /// called for their descendants. The nodes of the new tree are inserted in tree order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(("root", true));
+    /// let hidden = tree.insert(("hidden", false), root);
+    /// tree.insert(("child of hidden", true), hidden);
+    /// tree.insert(("visible", true), root);
+    ///
+    /// let visible = tree.filter_map(|_, &(name, visible)| if visible { Some(name) } else { None });
+    ///
+    /// let visible_root = visible.get_root_index().unwrap();
+    /// let names = visible
+    ///     .descendants(visible_root)
+    ///     .map(|node_id| visible[node_id])
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(names, ["root", "visible"]);
+    /// ```
+    pub fn filter_map<U, F>(&self, mut f: F) -> VecTree<U>
+    where
+        F: FnMut(Index, &T) -> Option<U>,
+    {
+        let mut mapped = VecTree::new();
+
+        let root_index = match self.root_index {
+            Some(root_index) => root_index,
+            None => return mapped,
+        };
+
+        // The indices of the kept ancestors of the current node, in this tree and the new one.
+        let mut parents: Vec<(Index, Index)> = Vec::new();
+        let mut traverse = self.traverse(root_index);
+
+        while let Some(edge) = traverse.next() {
+            match edge {
+                NodeEdge::Start(node_id) => match f(node_id, &self.nodes[node_id].data) {
+                    Some(data) => {
+                        let new_node_id = match parents.last() {
+                            Some(&(_, parent_id)) => mapped.insert(data, parent_id),
+                            None => mapped.insert_root(data),
+                        };
+                        parents.push((node_id, new_node_id));
+                    }
+                    None => traverse.skip_subtree(),
+                },
+                NodeEdge::End(node_id) => {
+                    if let Some(&(parent_id, _)) = parents.last() {
+                        if parent_id == node_id {
+                            parents.pop();
+                        }
+                    }
+                }
+            }
+        }
+
+        mapped
+    }
+
+    /// Return an iterator of references to the nodes on the path from the node at index `a` up
+    /// to the lowest common ancestor of `a` and `b`, and then down to the node at index `b`.
+    ///
+    /// Both ends of the path are included. If one of the nodes is not in the tree, `None` is
+    /// returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(0);
+    /// let child_1 = tree.insert(1, root);
+    /// let child_2 = tree.insert(2, root);
+    /// let grandchild = tree.insert(3, child_1);
+    ///
+    /// let path = tree
+    ///     .path_between(grandchild, child_2)
+    ///     .unwrap()
+    ///     .map(|node_id| tree[node_id])
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(path, [3, 1, 0, 2]);
+    /// ```
+    pub fn path_between(&self, a: Index, b: Index) -> Option<PathBetweenIter<'_, T>> {
+        let lca = self.lca(a, b)?;
+
+        Some(PathBetweenIter {
+            tree: self,
+            lca,
+            up: Some(a),
+            down: self
+                .ancestors(b)
+                .take_while(|&node_id| node_id != lca)
+                .collect(),
+        })
+    }
+
+    /// Return an iterator of references to the nodes on the path from the root down to the node
+    /// at index `node_id`, the node itself included.
+    ///
+    /// This is the same sequence as `ancestors`, in reverse order. If the node is not in the
+    /// tree, the iterator is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(0);
+    /// let child = tree.insert(1, root);
+    /// let grandchild = tree.insert(2, child);
+    ///
+    /// let path = tree
+    ///     .path_from_root(grandchild)
+    ///     .map(|node_id| tree[node_id])
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(path, [0, 1, 2]);
+    /// ```
+    pub fn path_from_root(&self, node_id: Index) -> PathFromRootIter<'_, T> {
+        let ancestors = if self.contains(node_id) {
+            self.ancestors(node_id).collect()
+        } else {
+            Vec::new()
+        };
+
+        PathFromRootIter {
+            ancestors,
+            marker: PhantomData,
+        }
+    }
+
+    /// Return an iterator of references to the nodes after this node in tree order, its
+    /// descendants excluded.
+    ///
+    /// This is the XPath `following` axis. If the node is not in the tree, the iterator is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(0);
+    /// let child_1 = tree.insert(1, root);
+    /// tree.insert(2, child_1);
+    /// let child_3 = tree.insert(3, root);
+    /// tree.insert(4, child_3);
+    ///
+    /// let following = tree
+    ///     .following(child_1)
+    ///     .map(|node_id| tree[node_id])
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(following, [3, 4]);
+    /// ```
+    pub fn following(&self, node_id: Index) -> FollowingIter<'_, T> {
+        FollowingIter {
+            tree: self,
+            node_id: if self.contains(node_id) {
+                self.next_after_subtree(node_id)
+            } else {
+                None
+            },
+        }
+    }
+
+    /// Return an iterator of references to the nodes before this node in tree order, its
+    /// ancestors excluded, in reverse tree order.
+    ///
+    /// This is the XPath `preceding` axis. If the node is not in the tree, the iterator is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(0);
+    /// let child_1 = tree.insert(1, root);
+    /// tree.insert(2, child_1);
+    /// let child_3 = tree.insert(3, root);
+    /// let grandchild = tree.insert(4, child_3);
+    ///
+    /// let preceding = tree
+    ///     .preceding(grandchild)
+    ///     .map(|node_id| tree[node_id])
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(preceding, [2, 1]);
+    /// ```
+    pub fn preceding(&self, node_id: Index) -> PrecedingIter<'_, T> {
+        let mut iter = PrecedingIter {
+            tree: self,
+            node_id: None,
+            ancestor: self.parent(node_id),
+        };
+        if self.contains(node_id) {
+            iter.node_id = iter.step(node_id);
+        }
+        iter
+    }
+
+    /// Return the node right after the node at index `node_id` in tree order, that is in
+    /// depth-first search pre-order.
+    ///
+    /// If the node is the last one of the tree, or is not in the tree, `None` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(0);
+    /// let child_1 = tree.insert(1, root);
+    /// let grandchild = tree.insert(2, child_1);
+    /// let child_2 = tree.insert(3, root);
+    ///
+    /// assert_eq!(tree.next_preorder(root), Some(child_1));
+    /// assert_eq!(tree.next_preorder(grandchild), Some(child_2));
+    /// assert_eq!(tree.next_preorder(child_2), None);
+    /// ```
+    pub fn next_preorder(&self, node_id: Index) -> Option<Index> {
+        match self.nodes.get(node_id)?.first_child {
+            Some(first_child) => Some(first_child),
+            None => self.next_after_subtree(node_id),
         }
     }
 
-    /// Return an iterator of references to this node and its descendants, in tree order.
-    fn traverse(&self, node_id: Index) -> TraverseIter<T> {
-        TraverseIter {
-            tree: self,
-            root: node_id,
-            next: Some(NodeEdge::Start(node_id)),
+    /// Return the node right before the node at index `node_id` in tree order, that is in
+    /// depth-first search pre-order.
+    ///
+    /// If the node is the root of the tree, or is not in the tree, `None` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(0);
+    /// let child_1 = tree.insert(1, root);
+    /// let grandchild = tree.insert(2, child_1);
+    /// let child_2 = tree.insert(3, root);
+    ///
+    /// assert_eq!(tree.prev_preorder(child_2), Some(grandchild));
+    /// assert_eq!(tree.prev_preorder(child_1), Some(root));
+    /// assert_eq!(tree.prev_preorder(root), None);
+    /// ```
+    pub fn prev_preorder(&self, node_id: Index) -> Option<Index> {
+        let node = self.nodes.get(node_id)?;
+        match node.previous_sibling {
+            Some(previous_sibling) => Some(self.last_in_subtree(previous_sibling)),
+            None => node.parent,
         }
     }
 
-    /// Return an iterator of references to this node and its descendants, with deoth in the tree,
-    /// in tree order.
-    fn traverse_with_depth(&self, node_id: Index) -> TraverseWithDepthIter<T> {
-        TraverseWithDepthIter {
-            tree: self,
-            root: node_id,
-            next: Some(NodeEdgeWithDepth::Start(node_id, 0)),
+    /// Return the node following the subtree rooted at `node_id` in tree order.
+    fn next_after_subtree(&self, node_id: Index) -> Option<Index> {
+        let mut node_id = node_id;
+        loop {
+            let node = &self.nodes[node_id];
+            if let Some(next_sibling) = node.next_sibling {
+                return Some(next_sibling);
+            }
+            node_id = node.parent?;
         }
     }
 
-    /// Return an iterator of references to this node and its descendants, in tree order.
+    /// Return the node right after the subtree rooted at `node_id` in tree order, without leaving
+    /// the subtree rooted at `root`.
+    fn next_after_subtree_within(&self, node_id: Index, root: Index) -> Option<Index> {
+        let mut node_id = node_id;
+        while node_id != root {
+            let node = &self.nodes[node_id];
+            if let Some(next_sibling) = node.next_sibling {
+                return Some(next_sibling);
+            }
+            node_id = node.parent?;
+        }
+        None
+    }
+
+    /// Return the last node in tree order of the subtree rooted at `node_id`.
+    fn last_in_subtree(&self, node_id: Index) -> Index {
+        let mut node_id = node_id;
+        while let Some(last_child) = self.nodes[node_id].last_child {
+            node_id = last_child;
+        }
+        node_id
+    }
+
+    /// Return an iterator of references to this node and its descendants whose data matches
+    /// `predicate`, in tree order.
     ///
-    /// Parent nodes appear before the descendants.
-    /// Call `.next().unwrap()` once on the iterator to skip the node itself.
-    pub fn descendants(&self, node_id: Index) -> DescendantsIter<T> {
-        DescendantsIter(self.traverse(node_id))
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(0);
+    /// let child = tree.insert(1, root);
+    /// tree.insert(2, child);
+    /// tree.insert(3, root);
+    ///
+    /// let odd = tree
+    ///     .find_descendants(root, |value| value % 2 == 1)
+    ///     .map(|node_id| tree[node_id])
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(odd, [1, 3]);
+    /// ```
+    pub fn find_descendants<P>(&self, node_id: Index, predicate: P) -> FindDescendantsIter<'_, T, P>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        FindDescendantsIter {
+            descendants: self.descendants(node_id),
+            predicate,
+        }
     }
 
-    /// Return an iterator of references to this node and its descendants, with deoth in the tree,
-    /// in tree order.
+    /// Return the first node, in tree order, among this node and its descendants whose data
+    /// matches `predicate`.
     ///
-    /// Parent nodes appear before the descendants.
-    /// Call `.next().unwrap()` once on the iterator to skip the node itself.
-    pub fn descendants_with_depth(&self, node_id: Index) -> DescendantsWithDepthIter<T> {
-        DescendantsWithDepthIter(self.traverse_with_depth(node_id))
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(0);
+    /// let child = tree.insert(1, root);
+    ///
+    /// assert_eq!(tree.find_first(root, |&value| value > 0), Some(child));
+    /// assert_eq!(tree.find_first(root, |&value| value > 1), None);
+    /// ```
+    pub fn find_first<P>(&self, node_id: Index, predicate: P) -> Option<Index>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.find_descendants(node_id, predicate).next()
+    }
+
+    /// Return an iterator of pairs of references to the nodes of this tree and `other` at the
+    /// same position, walking both trees in lockstep in tree order.
+    ///
+    /// The roots are paired, then the children of paired nodes are paired in order. Nodes without
+    /// a counterpart, like the extra children of a node having more children than its pair, are
+    /// skipped along with their descendants, so trees sharing a common shape are compared on
+    /// that shape.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut expected = VecTree::new();
+    /// let root = expected.insert_root("a");
+    /// expected.insert("b", root);
+    ///
+    /// let mut actual = VecTree::new();
+    /// let root = actual.insert_root("a");
+    /// actual.insert("c", root);
+    /// actual.insert("d", root);
+    ///
+    /// let differences = expected
+    ///     .zip(&actual)
+    ///     .filter(|&(a, b)| expected[a] != actual[b])
+    ///     .map(|(a, b)| (expected[a], actual[b]))
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(differences, [("b", "c")]);
+    /// ```
+    pub fn zip<'b, U>(&self, other: &'b VecTree<U>) -> ZipIter<'_, 'b, T, U> {
+        let roots = match (self.root_index, other.root_index) {
+            (Some(root_index), Some(other_root_index)) => Some((root_index, other_root_index)),
+            _ => None,
+        };
+
+        ZipIter {
+            tree: self,
+            other,
+            roots,
+            next: roots,
+        }
+    }
+
+    /// Return an iterator of the edges of the tree, as pairs of references to a parent and one of
+    /// its children, in tree order of the children.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(0);
+    /// let child = tree.insert(1, root);
+    /// let grandchild = tree.insert(2, child);
+    ///
+    /// let edges = tree.edges().collect::<Vec<_>>();
+    ///
+    /// assert_eq!(edges, [(root, child), (child, grandchild)]);
+    /// ```
+    pub fn edges(&self) -> EdgesIter<'_, T> {
+        EdgesIter {
+            tree: self,
+            descendants: self
+                .root_index
+                .map(|root_index| self.descendants(root_index)),
+        }
     }
 }
 
@@ -762,6 +3270,28 @@ impl<T> fmt::Display for Node<T> {
     }
 }
 
+/// Statistics about the shape of a tree, as returned by `VecTree::stats`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TreeStats {
+    /// The number of nodes in the tree.
+    pub node_count: usize,
+
+    /// The number of nodes without children.
+    pub leaf_count: usize,
+
+    /// The depth of the deepest node.
+    pub max_depth: u32,
+
+    /// The average depth of the nodes.
+    pub average_depth: f64,
+
+    /// The number of nodes at each depth, indexed by depth.
+    pub depth_histogram: Vec<usize>,
+
+    /// The number of nodes having a given number of children, indexed by number of children.
+    pub arity_histogram: Vec<usize>,
+}
+
 impl<T> ops::Index<Index> for VecTree<T> {
     type Output = T;
 
@@ -776,6 +3306,34 @@ impl<T> ops::IndexMut<Index> for VecTree<T> {
     }
 }
 
+impl<T> IntoIterator for VecTree<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    /// Consume the tree, returning an iterator of its data in tree order (depth-first pre-order).
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter(self.into_iter_with_depth())
+    }
+}
+
+impl<'a, T> IntoIterator for &'a VecTree<T> {
+    type Item = (Index, &'a T);
+    type IntoIter = TreeIter<'a, T>;
+
+    fn into_iter(self) -> TreeIter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut VecTree<T> {
+    type Item = (Index, &'a mut T);
+    type IntoIter = TreeIterMut<'a, T>;
+
+    fn into_iter(self) -> TreeIterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
 macro_rules! impl_node_iterator {
     ($name:ident, $next:expr) => {
         impl<'a, T> Iterator for $name<'a, T> {
@@ -790,7 +3348,16 @@ macro_rules! impl_node_iterator {
                     None => None,
                 }
             }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                (
+                    usize::from(self.node_id.is_some()),
+                    Some(self.tree.nodes.len()),
+                )
+            }
         }
+
+        impl<'a, T> FusedIterator for $name<'a, T> {}
     };
 }
 
@@ -798,8 +3365,43 @@ macro_rules! impl_node_iterator {
 pub struct ChildrenIter<'a, T: 'a> {
     tree: &'a VecTree<T>,
     node_id: Option<Index>,
+    back_node_id: Option<Index>,
+}
+
+impl<'a, T> Iterator for ChildrenIter<'a, T> {
+    type Item = Index;
+
+    fn next(&mut self) -> Option<Index> {
+        let node_id = self.node_id.take()?;
+        if Some(node_id) == self.back_node_id {
+            self.back_node_id = None;
+        } else {
+            self.node_id = self.tree.nodes[node_id].next_sibling;
+        }
+        Some(node_id)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (
+            usize::from(self.node_id.is_some()),
+            Some(self.tree.nodes.len()),
+        )
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for ChildrenIter<'a, T> {
+    fn next_back(&mut self) -> Option<Index> {
+        let node_id = self.back_node_id.take()?;
+        if Some(node_id) == self.node_id {
+            self.node_id = None;
+        } else {
+            self.back_node_id = self.tree.nodes[node_id].previous_sibling;
+        }
+        Some(node_id)
+    }
 }
-impl_node_iterator!(ChildrenIter, |node: &Node<T>| node.next_sibling);
+
+impl<'a, T> FusedIterator for ChildrenIter<'a, T> {}
 
 /// An iterator of references to the siblings before a given node.
 pub struct PrecedingSiblingsIter<'a, T: 'a> {
@@ -809,29 +3411,262 @@ pub struct PrecedingSiblingsIter<'a, T: 'a> {
 impl_node_iterator!(PrecedingSiblingsIter, |node: &Node<T>| node
     .previous_sibling);
 
-/// An iterator of references to the siblings after a given node.
-pub struct FollowingSiblingsIter<'a, T: 'a> {
-    tree: &'a VecTree<T>,
-    node_id: Option<Index>,
+/// An iterator of references to the siblings after a given node.
+pub struct FollowingSiblingsIter<'a, T: 'a> {
+    tree: &'a VecTree<T>,
+    node_id: Option<Index>,
+}
+impl_node_iterator!(FollowingSiblingsIter, |node: &Node<T>| node.next_sibling);
+
+/// An iterator of references to the ancestors a given node.
+pub struct AncestorsIter<'a, T: 'a> {
+    tree: &'a VecTree<T>,
+    node_id: Option<Index>,
+}
+impl_node_iterator!(AncestorsIter, |node: &Node<T>| node.parent);
+
+/// An iterator of references to the nodes on the path between two given nodes.
+pub struct PathBetweenIter<'a, T: 'a> {
+    tree: &'a VecTree<T>,
+    lca: Index,
+    up: Option<Index>,
+    down: Vec<Index>,
+}
+
+impl<'a, T> Iterator for PathBetweenIter<'a, T> {
+    type Item = Index;
+
+    fn next(&mut self) -> Option<Index> {
+        if let Some(node_id) = self.up.take() {
+            if node_id != self.lca {
+                self.up = self.tree.nodes[node_id].parent;
+            }
+            return Some(node_id);
+        }
+
+        // The downward half is stored from the last node up, so it is consumed from the end.
+        self.down.pop()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let lower = usize::from(self.up.is_some()) + self.down.len();
+        (lower, Some(self.tree.nodes.len()))
+    }
+}
+
+impl<'a, T> FusedIterator for PathBetweenIter<'a, T> {}
+
+/// An iterator of references to the nodes after a given node in tree order, its descendants
+/// excluded.
+pub struct FollowingIter<'a, T: 'a> {
+    tree: &'a VecTree<T>,
+    node_id: Option<Index>,
+}
+
+impl<'a, T> Iterator for FollowingIter<'a, T> {
+    type Item = Index;
+
+    fn next(&mut self) -> Option<Index> {
+        let node_id = self.node_id.take()?;
+        self.node_id = self.tree.next_preorder(node_id);
+        Some(node_id)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (
+            usize::from(self.node_id.is_some()),
+            Some(self.tree.nodes.len()),
+        )
+    }
+}
+
+impl<'a, T> FusedIterator for FollowingIter<'a, T> {}
+
+/// An iterator of references to the nodes before a given node in reverse tree order, its
+/// ancestors excluded.
+pub struct PrecedingIter<'a, T: 'a> {
+    tree: &'a VecTree<T>,
+    node_id: Option<Index>,
+    ancestor: Option<Index>,
+}
+
+impl<'a, T> PrecedingIter<'a, T> {
+    fn step(&mut self, node_id: Index) -> Option<Index> {
+        let mut node_id = node_id;
+        loop {
+            let node = &self.tree.nodes[node_id];
+            if let Some(previous_sibling) = node.previous_sibling {
+                return Some(self.tree.last_in_subtree(previous_sibling));
+            }
+
+            let parent = node.parent?;
+            if Some(parent) != self.ancestor {
+                return Some(parent);
+            }
+
+            // Ancestors of the starting node are skipped.
+            self.ancestor = self.tree.nodes[parent].parent;
+            node_id = parent;
+        }
+    }
+}
+
+impl<'a, T> Iterator for PrecedingIter<'a, T> {
+    type Item = Index;
+
+    fn next(&mut self) -> Option<Index> {
+        let node_id = self.node_id.take()?;
+        self.node_id = self.step(node_id);
+        Some(node_id)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (
+            usize::from(self.node_id.is_some()),
+            Some(self.tree.nodes.len()),
+        )
+    }
+}
+
+impl<'a, T> FusedIterator for PrecedingIter<'a, T> {}
+
+/// An iterator of pairs of references to the nodes at the same position in two trees.
+pub struct ZipIter<'a, 'b, T: 'a, U: 'b> {
+    tree: &'a VecTree<T>,
+    other: &'b VecTree<U>,
+    roots: Option<(Index, Index)>,
+    next: Option<(Index, Index)>,
+}
+
+impl<'a, 'b, T, U> ZipIter<'a, 'b, T, U> {
+    fn step(&self, (node_id, other_node_id): (Index, Index)) -> Option<(Index, Index)> {
+        let node = &self.tree.nodes[node_id];
+        let other_node = &self.other.nodes[other_node_id];
+        if let (Some(first_child), Some(other_first_child)) =
+            (node.first_child, other_node.first_child)
+        {
+            return Some((first_child, other_first_child));
+        }
+
+        // Look for the next pair of siblings, walking up both trees in lockstep.
+        let (mut node, mut other_node) = (node, other_node);
+        let (mut node_id, mut other_node_id) = (node_id, other_node_id);
+        while Some((node_id, other_node_id)) != self.roots {
+            if let (Some(next_sibling), Some(other_next_sibling)) =
+                (node.next_sibling, other_node.next_sibling)
+            {
+                return Some((next_sibling, other_next_sibling));
+            }
+
+            node_id = node.parent?;
+            other_node_id = other_node.parent?;
+            node = &self.tree.nodes[node_id];
+            other_node = &self.other.nodes[other_node_id];
+        }
+        None
+    }
+}
+
+impl<'a, 'b, T, U> Iterator for ZipIter<'a, 'b, T, U> {
+    type Item = (Index, Index);
+
+    fn next(&mut self) -> Option<(Index, Index)> {
+        let pair = self.next.take()?;
+        self.next = self.step(pair);
+        Some(pair)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let upper = self.tree.nodes.len().min(self.other.nodes.len());
+        (usize::from(self.next.is_some()), Some(upper))
+    }
+}
+
+impl<'a, 'b, T, U> FusedIterator for ZipIter<'a, 'b, T, U> {}
+
+/// An iterator of the edges of a tree.
+pub struct EdgesIter<'a, T: 'a> {
+    tree: &'a VecTree<T>,
+    descendants: Option<DescendantsIter<'a, T>>,
+}
+
+impl<'a, T> Iterator for EdgesIter<'a, T> {
+    type Item = (Index, Index);
+
+    fn next(&mut self) -> Option<(Index, Index)> {
+        let tree = self.tree;
+        self.descendants
+            .as_mut()?
+            .find_map(|node_id| Some((tree.nodes[node_id].parent?, node_id)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.tree.nodes.len()))
+    }
+}
+
+impl<'a, T> FusedIterator for EdgesIter<'a, T> {}
+
+/// An iterator of references to the nodes on the path from the root to a given node.
+pub struct PathFromRootIter<'a, T: 'a> {
+    ancestors: Vec<Index>,
+    marker: PhantomData<&'a VecTree<T>>,
+}
+
+impl<'a, T> Iterator for PathFromRootIter<'a, T> {
+    type Item = Index;
+
+    fn next(&mut self) -> Option<Index> {
+        self.ancestors.pop()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.ancestors.len(), Some(self.ancestors.len()))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for PathFromRootIter<'a, T> {}
+
+impl<'a, T> FusedIterator for PathFromRootIter<'a, T> {}
+
+/// An iterator of references to a given node and its descendants matching a predicate, in tree
+/// order.
+pub struct FindDescendantsIter<'a, T: 'a, P> {
+    descendants: DescendantsIter<'a, T>,
+    predicate: P,
+}
+
+impl<'a, T, P> Iterator for FindDescendantsIter<'a, T, P>
+where
+    P: FnMut(&T) -> bool,
+{
+    type Item = Index;
+
+    fn next(&mut self) -> Option<Index> {
+        let tree = self.descendants.0.tree;
+        let predicate = &mut self.predicate;
+
+        self.descendants
+            .find(|&node_id| predicate(&tree.nodes[node_id].data))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.descendants.size_hint().1)
+    }
 }
-impl_node_iterator!(FollowingSiblingsIter, |node: &Node<T>| node.next_sibling);
 
-/// An iterator of references to the ancestors a given node.
-pub struct AncestorsIter<'a, T: 'a> {
-    tree: &'a VecTree<T>,
-    node_id: Option<Index>,
-}
-impl_node_iterator!(AncestorsIter, |node: &Node<T>| node.parent);
+impl<'a, T, P> FusedIterator for FindDescendantsIter<'a, T, P> where P: FnMut(&T) -> bool {}
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Indicator if the node is at a start or endpoint of the tree
 pub enum NodeEdge<T> {
-    /// Indicates that start of a node that has children. Yielded by `TraverseIter::next` before the
-    /// node’s descendants.
+    /// Indicates the start of a node. Yielded by `TraverseIter::next` before the node’s
+    /// descendants.
     Start(T),
 
-    /// Indicates that end of a node that has children. Yielded by `TraverseIter::next` after the
-    /// node’s descendants.
+    /// Indicates the end of a node. Yielded by `TraverseIter::next` after the node’s
+    /// descendants.
     End(T),
 }
 
@@ -842,6 +3677,42 @@ pub struct TraverseIter<'a, T: 'a> {
     tree: &'a VecTree<T>,
     root: Index,
     next: Option<NodeEdge<Index>>,
+    next_back: Option<NodeEdge<Index>>,
+}
+
+impl<'a, T> TraverseIter<'a, T> {
+    /// Skip the descendants of the node whose `NodeEdge::Start` was yielded last, the next item
+    /// will be its `NodeEdge::End`.
+    ///
+    /// This has no effect if nothing was yielded yet, if the last yielded item was a
+    /// `NodeEdge::End`, or if the node has no children. The skipped subtree must not have been
+    /// reached from the back of the iterator.
+    pub fn skip_subtree(&mut self) {
+        if let Some(NodeEdge::Start(node_id)) = self.next {
+            // The traversal has not started yet.
+            if node_id == self.root {
+                return;
+            }
+
+            let node = &self.tree.nodes[node_id];
+
+            // Only a first child can come right after the start of its parent.
+            if node.previous_sibling.is_none() {
+                if let Some(parent) = node.parent {
+                    self.next = Some(NodeEdge::End(parent));
+                }
+            }
+        }
+    }
+
+    /// Return the state of the traversal, to be resumed later with `VecTree::resume_traverse`.
+    pub fn cursor(&self) -> TraverseCursor {
+        TraverseCursor {
+            root: self.root,
+            next: self.next,
+            next_back: self.next_back,
+        }
+    }
 }
 
 impl<'a, T> Iterator for TraverseIter<'a, T> {
@@ -849,6 +3720,10 @@ impl<'a, T> Iterator for TraverseIter<'a, T> {
 
     fn next(&mut self) -> Option<NodeEdge<Index>> {
         match self.next.take() {
+            Some(item) if Some(item) == self.next_back => {
+                self.next_back = None;
+                Some(item)
+            }
             Some(item) => {
                 self.next = match item {
                     NodeEdge::Start(node_id) => match self.tree.nodes[node_id].first_child {
@@ -881,11 +3756,102 @@ impl<'a, T> Iterator for TraverseIter<'a, T> {
             None => None,
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (
+            usize::from(self.next.is_some()),
+            self.tree.nodes.len().checked_mul(2),
+        )
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for TraverseIter<'a, T> {
+    fn next_back(&mut self) -> Option<NodeEdge<Index>> {
+        let item = self.next_back.take()?;
+        if Some(item) == self.next {
+            self.next = None;
+            return Some(item);
+        }
+
+        self.next_back = match item {
+            NodeEdge::End(node_id) => match self.tree.nodes[node_id].last_child {
+                Some(last_child) => Some(NodeEdge::End(last_child)),
+                None => Some(NodeEdge::Start(node_id)),
+            },
+            NodeEdge::Start(node_id) if node_id == self.root => None,
+            NodeEdge::Start(node_id) => {
+                let node = &self.tree.nodes[node_id];
+                match node.previous_sibling {
+                    Some(previous_sibling) => Some(NodeEdge::End(previous_sibling)),
+                    None => node.parent.map(NodeEdge::Start),
+                }
+            }
+        };
+        Some(item)
+    }
+}
+
+impl<'a, T> FusedIterator for TraverseIter<'a, T> {}
+
+/// The saved state of a traversal, which does not borrow the tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TraverseCursor {
+    root: Index,
+    next: Option<NodeEdge<Index>>,
+    next_back: Option<NodeEdge<Index>>,
+}
+
+impl TraverseCursor {
+    /// Return the index of the node the traversal started from.
+    pub fn root(&self) -> Index {
+        self.root
+    }
+
+    /// Return the next edge the traversal will yield, or `None` if it is finished.
+    pub fn next_edge(&self) -> Option<NodeEdge<Index>> {
+        self.next
+    }
+
+    /// Is the traversal finished?
+    pub fn is_finished(&self) -> bool {
+        self.next.is_none()
+    }
 }
 
 /// An iterator of references to a given node and its descendants, in tree order.
 pub struct DescendantsIter<'a, T: 'a>(pub TraverseIter<'a, T>);
 
+impl<'a, T> DescendantsIter<'a, T> {
+    /// Skip the descendants of the node yielded last.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(0);
+    /// let child = tree.insert(1, root);
+    /// tree.insert(2, child);
+    /// tree.insert(3, root);
+    ///
+    /// let mut descendants = tree.descendants(root);
+    /// let mut values = Vec::new();
+    ///
+    /// while let Some(node_id) = descendants.next() {
+    ///     values.push(tree[node_id]);
+    ///     if node_id == child {
+    ///         descendants.skip_subtree();
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(values, [0, 1, 3]);
+    /// ```
+    pub fn skip_subtree(&mut self) {
+        self.0.skip_subtree();
+    }
+}
+
 impl<'a, T> Iterator for DescendantsIter<'a, T> {
     type Item = Index;
 
@@ -898,17 +3864,39 @@ impl<'a, T> Iterator for DescendantsIter<'a, T> {
             }
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.0.next {
+            Some(NodeEdge::Start(_)) => (1, Some(self.0.tree.nodes.len())),
+            _ => (0, Some(self.0.tree.nodes.len())),
+        }
+    }
 }
 
-#[derive(Debug, Clone)]
-/// Indicator if the node is at a start or endpoint of the tree
+impl<'a, T> DoubleEndedIterator for DescendantsIter<'a, T> {
+    fn next_back(&mut self) -> Option<Index> {
+        loop {
+            match self.0.next_back() {
+                Some(NodeEdge::Start(node_id)) => return Some(node_id),
+                Some(NodeEdge::End(_)) => {}
+                None => return None,
+            }
+        }
+    }
+}
+
+impl<'a, T> FusedIterator for DescendantsIter<'a, T> {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Indicator if the node is at a start or endpoint of the tree, with its depth
 pub enum NodeEdgeWithDepth<T> {
-    /// Indicates that start of a node that has children. Yielded by `TraverseIter::next` before the
+    /// Indicates the start of a node. Yielded by `TraverseWithDepthIter::next` before the
     /// node’s descendants.
     Start(T, u32),
 
-    /// Indicates that end of a node that has children. Yielded by `TraverseIter::next` after the
-    /// node’s descendants.
+    /// Indicates the end of a node. Yielded by `TraverseWithDepthIter::next` after the node’s
+    /// descendants.
     End(T, u32),
 }
 
@@ -918,9 +3906,35 @@ pub enum NodeEdgeWithDepth<T> {
 pub struct TraverseWithDepthIter<'a, T: 'a> {
     tree: &'a VecTree<T>,
     root: Index,
+    max_depth: u32,
     next: Option<NodeEdgeWithDepth<Index>>,
 }
 
+impl<'a, T> TraverseWithDepthIter<'a, T> {
+    /// Skip the descendants of the node whose `NodeEdgeWithDepth::Start` was yielded last, the
+    /// next item will be its `NodeEdgeWithDepth::End`.
+    ///
+    /// This has no effect if nothing was yielded yet, if the last yielded item was a
+    /// `NodeEdgeWithDepth::End`, or if the node has no children.
+    pub fn skip_subtree(&mut self) {
+        if let Some(NodeEdgeWithDepth::Start(node_id, depth)) = self.next {
+            // The traversal has not started yet.
+            if node_id == self.root {
+                return;
+            }
+
+            let node = &self.tree.nodes[node_id];
+
+            // Only a first child can come right after the start of its parent.
+            if node.previous_sibling.is_none() {
+                if let Some(parent) = node.parent {
+                    self.next = Some(NodeEdgeWithDepth::End(parent, depth - 1));
+                }
+            }
+        }
+    }
+}
+
 impl<'a, T> Iterator for TraverseWithDepthIter<'a, T> {
     type Item = NodeEdgeWithDepth<Index>;
 
@@ -930,10 +3944,10 @@ impl<'a, T> Iterator for TraverseWithDepthIter<'a, T> {
                 self.next = match item {
                     NodeEdgeWithDepth::Start(node_id, depth) => {
                         match self.tree.nodes[node_id].first_child {
-                            Some(first_child) => {
+                            Some(first_child) if depth < self.max_depth => {
                                 Some(NodeEdgeWithDepth::Start(first_child, depth + 1))
                             }
-                            None => Some(NodeEdgeWithDepth::End(node_id, depth)),
+                            _ => Some(NodeEdgeWithDepth::End(node_id, depth)),
                         }
                     }
                     NodeEdgeWithDepth::End(node_id, depth) => {
@@ -966,11 +3980,27 @@ impl<'a, T> Iterator for TraverseWithDepthIter<'a, T> {
             None => None,
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (
+            usize::from(self.next.is_some()),
+            self.tree.nodes.len().checked_mul(2),
+        )
+    }
 }
 
+impl<'a, T> FusedIterator for TraverseWithDepthIter<'a, T> {}
+
 /// An iterator of references to a given node and its descendants, with depth, in tree order.
 pub struct DescendantsWithDepthIter<'a, T: 'a>(pub TraverseWithDepthIter<'a, T>);
 
+impl<'a, T> DescendantsWithDepthIter<'a, T> {
+    /// Skip the descendants of the node yielded last.
+    pub fn skip_subtree(&mut self) {
+        self.0.skip_subtree();
+    }
+}
+
 impl<'a, T> Iterator for DescendantsWithDepthIter<'a, T> {
     type Item = (Index, u32);
 
@@ -983,4 +4013,329 @@ impl<'a, T> Iterator for DescendantsWithDepthIter<'a, T> {
             }
         }
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.0.next {
+            Some(NodeEdgeWithDepth::Start(_, _)) => (1, Some(self.0.tree.nodes.len())),
+            _ => (0, Some(self.0.tree.nodes.len())),
+        }
+    }
+}
+
+impl<'a, T> FusedIterator for DescendantsWithDepthIter<'a, T> {}
+
+/// An iterator of references to a given node and its descendants down to a maximum depth, in tree
+/// order.
+pub struct DescendantsUntilDepthIter<'a, T: 'a>(TraverseWithDepthIter<'a, T>);
+
+impl<'a, T> Iterator for DescendantsUntilDepthIter<'a, T> {
+    type Item = Index;
+
+    fn next(&mut self) -> Option<Index> {
+        loop {
+            match self.0.next() {
+                Some(NodeEdgeWithDepth::Start(node_id, _)) => return Some(node_id),
+                Some(NodeEdgeWithDepth::End(_, _)) => {}
+                None => return None,
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.0.next {
+            Some(NodeEdgeWithDepth::Start(_, _)) => (1, Some(self.0.tree.nodes.len())),
+            _ => (0, Some(self.0.tree.nodes.len())),
+        }
+    }
+}
+
+impl<'a, T> FusedIterator for DescendantsUntilDepthIter<'a, T> {}
+
+/// An iterator of references to the leaves of the subtree rooted at a given node, in tree order.
+pub struct LeavesIter<'a, T: 'a>(TraverseIter<'a, T>);
+
+impl<'a, T> Iterator for LeavesIter<'a, T> {
+    type Item = Index;
+
+    fn next(&mut self) -> Option<Index> {
+        loop {
+            match self.0.next() {
+                Some(NodeEdge::Start(node_id)) => {
+                    if self.0.tree.nodes[node_id].first_child.is_none() {
+                        return Some(node_id);
+                    }
+                }
+                Some(NodeEdge::End(_)) => {}
+                None => return None,
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.0.tree.nodes.len()))
+    }
+}
+
+impl<'a, T> FusedIterator for LeavesIter<'a, T> {}
+
+/// An iterator of references to a given node and its descendants, with depth, in breadth-first
+/// order.
+pub struct BreadthFirstWithDepthIter<'a, T: 'a> {
+    tree: &'a VecTree<T>,
+    queue: VecDeque<(Index, u32)>,
+}
+
+impl<'a, T> Iterator for BreadthFirstWithDepthIter<'a, T> {
+    type Item = (Index, u32);
+
+    fn next(&mut self) -> Option<(Index, u32)> {
+        let (node_id, depth) = self.queue.pop_front()?;
+        for child_id in self.tree.children(node_id) {
+            self.queue.push_back((child_id, depth + 1));
+        }
+        Some((node_id, depth))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.queue.len(), Some(self.tree.nodes.len()))
+    }
+}
+
+impl<'a, T> FusedIterator for BreadthFirstWithDepthIter<'a, T> {}
+
+/// An iterator over the levels of the subtree rooted at a given node.
+pub struct LevelsIter<'a, T: 'a> {
+    tree: &'a VecTree<T>,
+    level: Vec<Index>,
+}
+
+impl<'a, T> Iterator for LevelsIter<'a, T> {
+    type Item = Vec<Index>;
+
+    fn next(&mut self) -> Option<Vec<Index>> {
+        if self.level.is_empty() {
+            return None;
+        }
+
+        let next_level = self
+            .level
+            .iter()
+            .flat_map(|&node_id| self.tree.children(node_id))
+            .collect();
+
+        Some(mem::replace(&mut self.level, next_level))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (
+            usize::from(!self.level.is_empty()),
+            Some(self.tree.nodes.len()),
+        )
+    }
+}
+
+impl<'a, T> FusedIterator for LevelsIter<'a, T> {}
+
+/// An iterator of references to a given node and its descendants, in iterative-deepening
+/// depth-first search order.
+pub struct IddfsIter<'a, T: 'a> {
+    traverse: TraverseWithDepthIter<'a, T>,
+    found: bool,
+}
+
+impl<'a, T> Iterator for IddfsIter<'a, T> {
+    type Item = Index;
+
+    fn next(&mut self) -> Option<Index> {
+        loop {
+            match self.traverse.next() {
+                Some(NodeEdgeWithDepth::Start(node_id, depth)) => {
+                    if depth == self.traverse.max_depth {
+                        self.found = true;
+                        return Some(node_id);
+                    }
+                }
+                Some(NodeEdgeWithDepth::End(_, _)) => {}
+                None => {
+                    // Stop when the last pass did not reach any node at its maximum depth.
+                    if !self.found {
+                        return None;
+                    }
+
+                    let root = self.traverse.root;
+                    let max_depth = self.traverse.max_depth + 1;
+                    self.traverse = self.traverse.tree.traverse_with_depth(root);
+                    self.traverse.max_depth = max_depth;
+                    self.found = false;
+                }
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.traverse.tree.nodes.len()))
+    }
+}
+
+impl<'a, T> FusedIterator for IddfsIter<'a, T> {}
+
+/// An iterator of references to nodes along with their data, wrapping another node iterator.
+pub struct ValuesIter<'a, T: 'a, I> {
+    tree: &'a VecTree<T>,
+    iter: I,
+}
+
+impl<'a, T, I> Iterator for ValuesIter<'a, T, I>
+where
+    I: Iterator<Item = Index>,
+{
+    type Item = (Index, &'a T);
+
+    fn next(&mut self) -> Option<(Index, &'a T)> {
+        let node_id = self.iter.next()?;
+        Some((node_id, &self.tree.nodes[node_id].data))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, T, I> ExactSizeIterator for ValuesIter<'a, T, I> where I: ExactSizeIterator<Item = Index> {}
+
+impl<'a, T, I> FusedIterator for ValuesIter<'a, T, I> where I: FusedIterator<Item = Index> {}
+
+/// An iterator of shared references to the data of all the nodes of a tree.
+pub struct TreeValuesIter<'a, T: 'a>(lazy_arena::Iter<'a, Node<T>>);
+
+impl<'a, T> Iterator for TreeValuesIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.0.next().map(|(_, node)| &node.data)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<'a, T> ExactSizeIterator for TreeValuesIter<'a, T> {}
+
+impl<'a, T> FusedIterator for TreeValuesIter<'a, T> {}
+
+/// An iterator of exclusive references to the data of all the nodes of a tree.
+pub struct TreeValuesMutIter<'a, T: 'a>(lazy_arena::IterMut<'a, Node<T>>);
+
+impl<'a, T> Iterator for TreeValuesMutIter<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<&'a mut T> {
+        self.0.next().map(|(_, node)| &mut node.data)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<'a, T> ExactSizeIterator for TreeValuesMutIter<'a, T> {}
+
+impl<'a, T> FusedIterator for TreeValuesMutIter<'a, T> {}
+
+/// An iterator of shared references to all the nodes of a tree, along with their data.
+pub struct TreeIter<'a, T: 'a>(lazy_arena::Iter<'a, Node<T>>);
+
+impl<'a, T> Iterator for TreeIter<'a, T> {
+    type Item = (Index, &'a T);
+
+    fn next(&mut self) -> Option<(Index, &'a T)> {
+        self.0.next().map(|(node_id, node)| (node_id, &node.data))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<'a, T> ExactSizeIterator for TreeIter<'a, T> {}
+
+impl<'a, T> FusedIterator for TreeIter<'a, T> {}
+
+/// An iterator of exclusive references to all the nodes of a tree, along with their data.
+pub struct TreeIterMut<'a, T: 'a>(lazy_arena::IterMut<'a, Node<T>>);
+
+impl<'a, T> Iterator for TreeIterMut<'a, T> {
+    type Item = (Index, &'a mut T);
+
+    fn next(&mut self) -> Option<(Index, &'a mut T)> {
+        self.0
+            .next()
+            .map(|(node_id, node)| (node_id, &mut node.data))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<'a, T> ExactSizeIterator for TreeIterMut<'a, T> {}
+
+impl<'a, T> FusedIterator for TreeIterMut<'a, T> {}
+
+/// An iterator consuming a tree, yielding its data in tree order.
+pub struct IntoIter<T>(IntoIterWithDepth<T>);
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.0.next().map(|(data, _)| data)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<T> ExactSizeIterator for IntoIter<T> {}
+
+impl<T> FusedIterator for IntoIter<T> {}
+
+/// An iterator consuming a tree, yielding its data along with the depth of each node in tree
+/// order.
+pub struct IntoIterWithDepth<T> {
+    nodes: LazyArena<Node<T>>,
+    next: Option<(Index, u32)>,
+    // The next siblings of the ancestors of the next node, to resume from once a subtree has been
+    // consumed.
+    pending_siblings: Vec<(Index, u32)>,
+}
+
+impl<T> Iterator for IntoIterWithDepth<T> {
+    type Item = (T, u32);
+
+    fn next(&mut self) -> Option<(T, u32)> {
+        let (node_id, depth) = self.next.take()?;
+        let node = self.nodes.remove(node_id)?;
+
+        if let Some(next_sibling) = node.next_sibling {
+            self.pending_siblings.push((next_sibling, depth));
+        }
+        self.next = match node.first_child {
+            Some(first_child) => Some((first_child, depth + 1)),
+            None => self.pending_siblings.pop(),
+        };
+
+        Some((node.data, depth))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // Only the nodes not yielded yet are left in the arena.
+        (self.nodes.len(), Some(self.nodes.len()))
+    }
 }
+
+impl<T> ExactSizeIterator for IntoIterWithDepth<T> {}
+
+impl<T> FusedIterator for IntoIterWithDepth<T> {}
@@ -0,0 +1,130 @@
+use super::{index_slot, VecTree};
+
+use std::fmt::{self, Write};
+
+impl<T> VecTree<T> {
+    /// Return a value formatting the tree as a Mermaid flowchart, going from the root at the top
+    /// down to the leaves, with the text returned by `label` for each node.
+    ///
+    /// The flowchart can be embedded in Markdown, in a `mermaid` code block. Nodes can be styled
+    /// with `Mermaid::style`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(("main", false));
+    /// tree.insert(("parse \"args\"", true), root);
+    /// tree.insert(("run", false), root);
+    ///
+    /// let chart = tree
+    ///     .mermaid(|(name, _)| *name)
+    ///     .style(|(_, failed)| *failed, "fill:#f66")
+    ///     .to_string();
+    ///
+    /// assert_eq!(
+    ///     chart,
+    ///     "flowchart TD\n    \
+    ///      n0[\"main\"]\n    \
+    ///      n0 --> n1[\"parse #quot;args#quot;\"]\n    \
+    ///      n0 --> n2[\"run\"]\n    \
+    ///      classDef style0 fill:#f66\n    \
+    ///      class n1 style0\n"
+    /// );
+    /// ```
+    pub fn mermaid<L, D>(&self, label: L) -> Mermaid<'_, T, L>
+    where
+        L: Fn(&T) -> D,
+        D: fmt::Display,
+    {
+        Mermaid {
+            tree: self,
+            label,
+            styles: Vec::new(),
+        }
+    }
+}
+
+/// A value formatting a tree as a Mermaid flowchart with `Display`, created by
+/// `VecTree::mermaid`.
+pub struct Mermaid<'a, T: 'a, L> {
+    tree: &'a VecTree<T>,
+    label: L,
+    // The style of the nodes matching each predicate, in the order they were added.
+    styles: Vec<(Predicate<'a, T>, String)>,
+}
+
+type Predicate<'a, T> = Box<dyn Fn(&T) -> bool + 'a>;
+
+impl<'a, T, L> Mermaid<'a, T, L> {
+    /// Apply the CSS `style`, such as `"fill:#f96,stroke:#333"`, to the nodes for which
+    /// `predicate` returns `true`.
+    ///
+    /// When several styles apply to a node, the last one added wins for the properties they
+    /// both set.
+    pub fn style<P>(mut self, predicate: P, style: &str) -> Mermaid<'a, T, L>
+    where
+        P: Fn(&T) -> bool + 'a,
+    {
+        self.styles.push((Box::new(predicate), style.to_string()));
+        self
+    }
+}
+
+impl<'a, T, L, D> fmt::Display for Mermaid<'a, T, L>
+where
+    L: Fn(&T) -> D,
+    D: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("flowchart TD\n")?;
+
+        let root_id = match self.tree.root_index {
+            Some(root_id) => root_id,
+            None => return Ok(()),
+        };
+
+        let mut label = String::new();
+        for node_id in self.tree.descendants(root_id) {
+            f.write_str("    ")?;
+            if let Some(parent_id) = self.tree.parent(node_id) {
+                write!(f, "n{} --> ", index_slot(parent_id))?;
+            }
+
+            label.clear();
+            write!(label, "{}", (self.label)(&self.tree[node_id]))?;
+            // Quotes would end the label, so they are written as an entity code.
+            writeln!(
+                f,
+                "n{}[\"{}\"]",
+                index_slot(node_id),
+                label.replace('"', "#quot;")
+            )?;
+        }
+
+        for (position, (predicate, style)) in self.styles.iter().enumerate() {
+            let mut node_ids = self
+                .tree
+                .descendants(root_id)
+                .filter(|&node_id| predicate(&self.tree[node_id]))
+                .peekable();
+            if node_ids.peek().is_none() {
+                continue;
+            }
+
+            writeln!(f, "    classDef style{} {}", position, style)?;
+            f.write_str("    class ")?;
+            for (i, node_id) in node_ids.enumerate() {
+                if i > 0 {
+                    f.write_str(",")?;
+                }
+                write!(f, "n{}", index_slot(node_id))?;
+            }
+            writeln!(f, " style{}", position)?;
+        }
+
+        Ok(())
+    }
+}
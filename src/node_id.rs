@@ -0,0 +1,129 @@
+use super::VecTree;
+use generational_arena::Index;
+
+use std::ops;
+
+/// An index tagged with the id of the tree it belongs to, created by `VecTree::node_id`.
+///
+/// Methods taking a `NodeId` panic if it belongs to another tree, instead of silently returning
+/// `None` or another node.
+///
+/// A `NodeId` cannot be serialized, since the id of a tree only identifies it within the running
+/// process. Persist its `Index` instead, and tag it again with `VecTree::node_id` once the tree is
+/// restored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NodeId {
+    tree_id: u64,
+    index: Index,
+}
+
+impl NodeId {
+    /// Return the id of the tree the node belongs to.
+    pub fn tree_id(self) -> u64 {
+        self.tree_id
+    }
+
+    /// Return the untagged index of the node.
+    pub fn index(self) -> Index {
+        self.index
+    }
+}
+
+impl<T> VecTree<T> {
+    /// Return the unique id of the tree.
+    ///
+    /// A clone of a tree keeps the id of the original, as indices are valid in both.
+    pub fn tree_id(&self) -> u64 {
+        self.id
+    }
+
+    /// Tag `index` with the id of the tree, so using it with another tree is caught.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(0);
+    /// let root = tree.node_id(root);
+    ///
+    /// assert_eq!(tree.get_checked(root), Some(&0));
+    /// assert_eq!(tree[root], 0);
+    /// ```
+    ///
+    /// ```should_panic
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(0);
+    /// let root = tree.node_id(root);
+    ///
+    /// let mut other_tree = VecTree::new();
+    /// other_tree.insert_root(1);
+    ///
+    /// // Panics, the index belongs to `tree`.
+    /// other_tree.get_checked(root);
+    /// ```
+    pub fn node_id(&self, index: Index) -> NodeId {
+        NodeId {
+            tree_id: self.id,
+            index,
+        }
+    }
+
+    /// Get a shared reference to the element at `node_id` if it is in the tree.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `node_id` belongs to another tree.
+    pub fn get_checked(&self, node_id: NodeId) -> Option<&T> {
+        self.get(self.check(node_id))
+    }
+
+    /// Get an exclusive reference to the element at `node_id` if it is in the tree.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `node_id` belongs to another tree.
+    pub fn get_mut_checked(&mut self, node_id: NodeId) -> Option<&mut T> {
+        let index = self.check(node_id);
+        self.get_mut(index)
+    }
+
+    /// Move the node at `new_child_id`, and its descendants, to the last child position of the
+    /// node at `node_id`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if one of the nodes belongs to another tree, or in the same cases as
+    /// `append_child`.
+    pub fn append_child_checked(&mut self, node_id: NodeId, new_child_id: NodeId) {
+        let node_id = self.check(node_id);
+        let new_child_id = self.check(new_child_id);
+        self.append_child(node_id, new_child_id);
+    }
+
+    fn check(&self, node_id: NodeId) -> Index {
+        if node_id.tree_id != self.id {
+            panic!("The index belongs to another tree");
+        }
+
+        node_id.index
+    }
+}
+
+impl<T> ops::Index<NodeId> for VecTree<T> {
+    type Output = T;
+
+    fn index(&self, node_id: NodeId) -> &T {
+        &self[self.check(node_id)]
+    }
+}
+
+impl<T> ops::IndexMut<NodeId> for VecTree<T> {
+    fn index_mut(&mut self, node_id: NodeId) -> &mut T {
+        let index = self.check(node_id);
+        &mut self[index]
+    }
+}
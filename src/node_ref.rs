@@ -0,0 +1,316 @@
+use super::{AncestorsIter, ChildrenIter, DescendantsIter, VecTree};
+use generational_arena::Index;
+
+use std::fmt;
+use std::iter::FusedIterator;
+
+impl<T> VecTree<T> {
+    /// Return a handle to the node at index `node_id`, or `None` if it is not in the tree.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(0);
+    /// let child = tree.insert(1, root);
+    ///
+    /// let node = tree.node(child).unwrap();
+    /// assert_eq!(*node.value(), 1);
+    /// assert_eq!(*node.parent().unwrap().value(), 0);
+    /// ```
+    pub fn node(&self, node_id: Index) -> Option<NodeRef<'_, T>> {
+        if !self.contains(node_id) {
+            return None;
+        }
+
+        Some(NodeRef {
+            tree: self,
+            node_id,
+        })
+    }
+
+    /// Return an exclusive handle to the node at index `node_id`, or `None` if it is not in the
+    /// tree.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(0);
+    ///
+    /// let mut node = tree.node_mut(root).unwrap();
+    /// *node.value_mut() = 10;
+    /// node.append(1).append(2);
+    ///
+    /// let values = tree
+    ///     .descendants(root)
+    ///     .map(|node_id| tree[node_id])
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(values, [10, 1, 2]);
+    /// ```
+    pub fn node_mut(&mut self, node_id: Index) -> Option<NodeMut<'_, T>> {
+        if !self.contains(node_id) {
+            return None;
+        }
+
+        Some(NodeMut {
+            tree: self,
+            node_id,
+        })
+    }
+
+    /// Return a handle to the root node, or `None` if the tree has no root.
+    pub fn root_node(&self) -> Option<NodeRef<'_, T>> {
+        self.get_root_index().and_then(|root_id| self.node(root_id))
+    }
+
+    /// Return an exclusive handle to the root node, or `None` if the tree has no root.
+    pub fn root_node_mut(&mut self) -> Option<NodeMut<'_, T>> {
+        self.get_root_index()
+            .and_then(move |root_id| self.node_mut(root_id))
+    }
+}
+
+/// A shared handle to a node, bundling the tree it belongs to with its index.
+pub struct NodeRef<'a, T: 'a> {
+    tree: &'a VecTree<T>,
+    node_id: Index,
+}
+
+impl<'a, T> Clone for NodeRef<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T> Copy for NodeRef<'a, T> {}
+
+impl<'a, T: fmt::Debug> fmt::Debug for NodeRef<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("NodeRef")
+            .field("node_id", &self.node_id)
+            .field("value", self.value())
+            .finish()
+    }
+}
+
+impl<'a, T> NodeRef<'a, T> {
+    /// Return the index of the node.
+    pub fn id(&self) -> Index {
+        self.node_id
+    }
+
+    /// Return the tree the node belongs to.
+    pub fn tree(&self) -> &'a VecTree<T> {
+        self.tree
+    }
+
+    /// Return a shared reference to the data of the node.
+    pub fn value(&self) -> &'a T {
+        &self.tree[self.node_id]
+    }
+
+    /// Return the parent of the node, or `None` if it is the root.
+    pub fn parent(&self) -> Option<NodeRef<'a, T>> {
+        self.to(self.tree.parent(self.node_id))
+    }
+
+    /// Return the first child of the node, if any.
+    pub fn first_child(&self) -> Option<NodeRef<'a, T>> {
+        self.to(self.tree.first_child(self.node_id))
+    }
+
+    /// Return the last child of the node, if any.
+    pub fn last_child(&self) -> Option<NodeRef<'a, T>> {
+        self.to(self.tree.last_child(self.node_id))
+    }
+
+    /// Return the next sibling of the node, if any.
+    pub fn next_sibling(&self) -> Option<NodeRef<'a, T>> {
+        self.to(self.tree.next_sibling(self.node_id))
+    }
+
+    /// Return the previous sibling of the node, if any.
+    pub fn previous_sibling(&self) -> Option<NodeRef<'a, T>> {
+        self.to(self.tree.previous_sibling(self.node_id))
+    }
+
+    /// Is the node the root of the tree?
+    pub fn is_root(&self) -> bool {
+        self.tree.is_root(self.node_id)
+    }
+
+    /// Does the node have children?
+    pub fn has_children(&self) -> bool {
+        self.tree.has_children(self.node_id)
+    }
+
+    /// Return an iterator of handles to the children of the node.
+    pub fn children(&self) -> NodeRefsIter<'a, T, ChildrenIter<'a, T>> {
+        NodeRefsIter {
+            tree: self.tree,
+            iter: self.tree.children(self.node_id),
+        }
+    }
+
+    /// Return an iterator of handles to the node and its ancestors.
+    pub fn ancestors(&self) -> NodeRefsIter<'a, T, AncestorsIter<'a, T>> {
+        NodeRefsIter {
+            tree: self.tree,
+            iter: self.tree.ancestors(self.node_id),
+        }
+    }
+
+    /// Return an iterator of handles to the node and its descendants, in tree order.
+    pub fn descendants(&self) -> NodeRefsIter<'a, T, DescendantsIter<'a, T>> {
+        NodeRefsIter {
+            tree: self.tree,
+            iter: self.tree.descendants(self.node_id),
+        }
+    }
+
+    fn to(&self, node_id: Option<Index>) -> Option<NodeRef<'a, T>> {
+        node_id.map(|node_id| NodeRef {
+            tree: self.tree,
+            node_id,
+        })
+    }
+}
+
+/// An exclusive handle to a node, bundling the tree it belongs to with its index.
+#[derive(Debug)]
+pub struct NodeMut<'a, T: 'a> {
+    tree: &'a mut VecTree<T>,
+    node_id: Index,
+}
+
+impl<'a, T> NodeMut<'a, T> {
+    /// Return the index of the node.
+    pub fn id(&self) -> Index {
+        self.node_id
+    }
+
+    /// Return a shared reference to the data of the node.
+    pub fn value(&self) -> &T {
+        &self.tree[self.node_id]
+    }
+
+    /// Return an exclusive reference to the data of the node.
+    pub fn value_mut(&mut self) -> &mut T {
+        &mut self.tree[self.node_id]
+    }
+
+    /// Consume the handle and return an exclusive reference to the data of the node, borrowing
+    /// the tree for as long as the handle did.
+    pub fn into_value(self) -> &'a mut T {
+        &mut self.tree[self.node_id]
+    }
+
+    /// Return a shared handle to the node.
+    pub fn as_ref(&self) -> NodeRef<'_, T> {
+        NodeRef {
+            tree: self.tree,
+            node_id: self.node_id,
+        }
+    }
+
+    /// Consume the handle and return a shared handle to the node.
+    pub fn into_ref(self) -> NodeRef<'a, T> {
+        NodeRef {
+            tree: self.tree,
+            node_id: self.node_id,
+        }
+    }
+
+    /// Return an exclusive handle to the parent of the node, or `None` if it is the root.
+    pub fn parent(&mut self) -> Option<NodeMut<'_, T>> {
+        let node_id = self.tree.parent(self.node_id);
+        self.to(node_id)
+    }
+
+    /// Return an exclusive handle to the first child of the node, if any.
+    pub fn first_child(&mut self) -> Option<NodeMut<'_, T>> {
+        let node_id = self.tree.first_child(self.node_id);
+        self.to(node_id)
+    }
+
+    /// Return an exclusive handle to the last child of the node, if any.
+    pub fn last_child(&mut self) -> Option<NodeMut<'_, T>> {
+        let node_id = self.tree.last_child(self.node_id);
+        self.to(node_id)
+    }
+
+    /// Return an exclusive handle to the next sibling of the node, if any.
+    pub fn next_sibling(&mut self) -> Option<NodeMut<'_, T>> {
+        let node_id = self.tree.next_sibling(self.node_id);
+        self.to(node_id)
+    }
+
+    /// Return an exclusive handle to the previous sibling of the node, if any.
+    pub fn previous_sibling(&mut self) -> Option<NodeMut<'_, T>> {
+        let node_id = self.tree.previous_sibling(self.node_id);
+        self.to(node_id)
+    }
+
+    /// Insert `data` as the last child of the node, and return an exclusive handle to the new
+    /// child.
+    pub fn append(&mut self, data: T) -> NodeMut<'_, T> {
+        let node_id = self.tree.insert(data, self.node_id);
+        NodeMut {
+            tree: self.tree,
+            node_id,
+        }
+    }
+
+    fn to(&mut self, node_id: Option<Index>) -> Option<NodeMut<'_, T>> {
+        node_id.map(move |node_id| NodeMut {
+            tree: self.tree,
+            node_id,
+        })
+    }
+}
+
+/// An iterator of handles to nodes, adapting an iterator of indices.
+pub struct NodeRefsIter<'a, T: 'a, I> {
+    tree: &'a VecTree<T>,
+    iter: I,
+}
+
+impl<'a, T, I> Iterator for NodeRefsIter<'a, T, I>
+where
+    I: Iterator<Item = Index>,
+{
+    type Item = NodeRef<'a, T>;
+
+    fn next(&mut self) -> Option<NodeRef<'a, T>> {
+        let node_id = self.iter.next()?;
+        Some(NodeRef {
+            tree: self.tree,
+            node_id,
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, T, I> DoubleEndedIterator for NodeRefsIter<'a, T, I>
+where
+    I: DoubleEndedIterator<Item = Index>,
+{
+    fn next_back(&mut self) -> Option<NodeRef<'a, T>> {
+        let node_id = self.iter.next_back()?;
+        Some(NodeRef {
+            tree: self.tree,
+            node_id,
+        })
+    }
+}
+
+impl<'a, T, I> FusedIterator for NodeRefsIter<'a, T, I> where I: FusedIterator<Item = Index> {}
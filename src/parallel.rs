@@ -0,0 +1,268 @@
+use super::{SubtreeMut, VecTree};
+use generational_arena::Index;
+
+use rayon::iter::plumbing::{bridge_unindexed, Folder, UnindexedConsumer, UnindexedProducer};
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+use std::ops::Range;
+
+impl<T: Sync> VecTree<T> {
+    /// Return a parallel iterator of shared references to all the nodes of the tree, along with
+    /// their data, in no particular order.
+    ///
+    /// The slots of the arena are split in halves between the threads of the rayon pool, so no
+    /// work is done upfront.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rayon::prelude::*;
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(1);
+    /// tree.insert(2, root);
+    /// tree.insert(3, root);
+    ///
+    /// let even = tree.par_iter().filter(|&(_, value)| value % 2 == 0).count();
+    /// assert_eq!(even, 1);
+    /// ```
+    pub fn par_iter(&self) -> ParIter<'_, T> {
+        ParIter(SlotsProducer {
+            tree: self,
+            slots: 0..self.nodes.capacity(),
+        })
+    }
+
+    /// Return a parallel iterator of shared references to the data of all the nodes of the tree,
+    /// in no particular order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rayon::prelude::*;
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(1);
+    /// tree.insert(2, root);
+    /// tree.insert(3, root);
+    ///
+    /// assert_eq!(tree.par_values().sum::<i32>(), 6);
+    /// ```
+    pub fn par_values(&self) -> ParValuesIter<'_, T> {
+        ParValuesIter(self.par_iter())
+    }
+
+    /// Return a parallel iterator of references to this node and its descendants.
+    ///
+    /// The subtrees of the children are split between the threads of the rayon pool as they are
+    /// reached, items keep the tree order when collected.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node is not in the tree.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rayon::prelude::*;
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(1);
+    /// let child = tree.insert(2, root);
+    /// tree.insert(3, child);
+    ///
+    /// let values = tree
+    ///     .par_descendants(child)
+    ///     .map(|node_id| tree[node_id] * 10)
+    ///     .collect::<Vec<_>>();
+    ///
+    /// assert_eq!(values, [20, 30]);
+    /// ```
+    pub fn par_descendants(&self, node_id: Index) -> ParDescendantsIter<'_, T> {
+        if !self.contains(node_id) {
+            panic!("The node you are trying to iterate from is invalid");
+        }
+
+        ParDescendantsIter(SubtreesProducer {
+            tree: self,
+            node_id: None,
+            roots: vec![node_id],
+        })
+    }
+}
+
+impl<T: Send> VecTree<T> {
+    /// Call `f` in parallel with an exclusive view over each of the subtrees rooted at `roots`.
+    ///
+    /// See `VecTree::subtrees_mut`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if one of the roots is not in the tree, or if the subtrees overlap.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(0);
+    /// let child_1 = tree.insert(1, root);
+    /// let child_2 = tree.insert(2, root);
+    /// let grandchild = tree.insert(3, child_2);
+    ///
+    /// tree.par_subtrees_mut(&[child_1, child_2], |mut subtree| {
+    ///     for (_, value) in subtree.iter_mut() {
+    ///         *value *= 10;
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(tree[child_1], 10);
+    /// assert_eq!(tree[grandchild], 30);
+    /// ```
+    pub fn par_subtrees_mut<F>(&mut self, roots: &[Index], f: F)
+    where
+        F: Fn(SubtreeMut<'_, T>) + Sync + Send,
+    {
+        self.subtrees_mut(roots).into_par_iter().for_each(f);
+    }
+}
+
+/// A parallel iterator of shared references to all the nodes of a tree, along with their data.
+pub struct ParIter<'a, T: 'a>(SlotsProducer<'a, T>);
+
+impl<'a, T: Sync + 'a> ParallelIterator for ParIter<'a, T> {
+    type Item = (Index, &'a T);
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<Self::Item>,
+    {
+        bridge_unindexed(self.0, consumer)
+    }
+}
+
+/// A parallel iterator of shared references to the data of all the nodes of a tree.
+pub struct ParValuesIter<'a, T: 'a>(ParIter<'a, T>);
+
+impl<'a, T: Sync + 'a> ParallelIterator for ParValuesIter<'a, T> {
+    type Item = &'a T;
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<Self::Item>,
+    {
+        self.0.map(|(_, data)| data).drive_unindexed(consumer)
+    }
+}
+
+/// A parallel iterator of references to a given node and its descendants.
+pub struct ParDescendantsIter<'a, T: 'a>(SubtreesProducer<'a, T>);
+
+impl<'a, T: Sync + 'a> ParallelIterator for ParDescendantsIter<'a, T> {
+    type Item = Index;
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: UnindexedConsumer<Self::Item>,
+    {
+        bridge_unindexed(self.0, consumer)
+    }
+}
+
+// Produces the nodes in a range of slots of the arena, which is split in halves.
+struct SlotsProducer<'a, T: 'a> {
+    tree: &'a VecTree<T>,
+    slots: Range<usize>,
+}
+
+impl<'a, T: Sync + 'a> UnindexedProducer for SlotsProducer<'a, T> {
+    type Item = (Index, &'a T);
+
+    fn split(self) -> (Self, Option<Self>) {
+        let Range { start, end } = self.slots;
+        if end - start < 2 {
+            return (self, None);
+        }
+
+        let middle = start + (end - start) / 2;
+        let right = SlotsProducer {
+            tree: self.tree,
+            slots: middle..end,
+        };
+
+        (
+            SlotsProducer {
+                tree: self.tree,
+                slots: start..middle,
+            },
+            Some(right),
+        )
+    }
+
+    fn fold_with<F>(self, folder: F) -> F
+    where
+        F: Folder<Self::Item>,
+    {
+        let nodes = &self.tree.nodes;
+
+        folder.consume_iter(
+            self.slots
+                .filter_map(move |slot| nodes.get_unknown_gen(slot))
+                .map(|(node, node_id)| (node_id, &node.data)),
+        )
+    }
+}
+
+// Produces a node, if any, followed by the nodes of consecutive sibling subtrees in tree order.
+// The subtrees are split in halves, and a single subtree is split between its root and the
+// subtrees of its children.
+struct SubtreesProducer<'a, T: 'a> {
+    tree: &'a VecTree<T>,
+    node_id: Option<Index>,
+    roots: Vec<Index>,
+}
+
+impl<'a, T: Sync + 'a> UnindexedProducer for SubtreesProducer<'a, T> {
+    type Item = Index;
+
+    fn split(mut self) -> (Self, Option<Self>) {
+        let tree = self.tree;
+
+        if self.node_id.is_none() && self.roots.len() == 1 {
+            let root = self.roots[0];
+            self.node_id = Some(root);
+            self.roots = tree.children(root).collect();
+        }
+
+        if self.roots.len() < 2 {
+            return (self, None);
+        }
+
+        let right = SubtreesProducer {
+            tree,
+            node_id: None,
+            roots: self.roots.split_off(self.roots.len() / 2),
+        };
+
+        (self, Some(right))
+    }
+
+    fn fold_with<F>(self, folder: F) -> F
+    where
+        F: Folder<Self::Item>,
+    {
+        let tree = self.tree;
+
+        folder.consume_iter(
+            self.node_id.into_iter().chain(
+                self.roots
+                    .into_iter()
+                    .flat_map(move |root| tree.descendants(root)),
+            ),
+        )
+    }
+}
@@ -0,0 +1,384 @@
+use super::VecTree;
+
+use std::fmt;
+use std::iter::FusedIterator;
+use std::ops;
+use std::sync::Arc;
+
+/// A persistent tree, where nodes are addressed by their path from the root, as in
+/// `VecTree::get_by_path`.
+///
+/// Cloning the tree is cheap and gives an independent version of it: editing a node only copies
+/// the nodes on its path from the root, every other subtree stays shared between the versions.
+/// This makes it cheap to keep snapshots, for instance to undo edits, or to hand a version to
+/// readers while editing another one.
+///
+/// # Examples
+///
+/// ```
+/// use vec_tree::PersistentVecTree;
+///
+/// let mut tree = PersistentVecTree::new();
+/// tree.insert_root("root");
+/// let left = tree.insert(&[], "left");
+/// tree.insert(&left, "left child");
+/// tree.insert(&[], "right");
+///
+/// let snapshot = tree.clone();
+/// tree.set(&[1], "new right");
+///
+/// assert_eq!(tree.get(&[1]), Some(&"new right"));
+/// assert_eq!(snapshot.get(&[1]), Some(&"right"));
+///
+/// // The left subtree was not edited, so it is shared by both versions.
+/// assert!(tree.shares_subtree(&snapshot, &left));
+/// ```
+pub struct PersistentVecTree<T> {
+    pub(crate) root: Option<Arc<PersistentNode<T>>>,
+}
+
+#[derive(Clone)]
+pub(crate) struct PersistentNode<T> {
+    pub(crate) data: T,
+    pub(crate) children: PersistentChildren<T>,
+}
+
+impl<T> PersistentNode<T> {
+    pub(crate) fn new(data: T) -> PersistentNode<T> {
+        PersistentNode {
+            data,
+            children: PersistentChildren(Vec::new()),
+        }
+    }
+}
+
+// The children of a node, dropped without recursing so deep trees do not overflow the stack.
+pub(crate) struct PersistentChildren<T>(Vec<Arc<PersistentNode<T>>>);
+
+impl<T> Clone for PersistentChildren<T> {
+    fn clone(&self) -> Self {
+        PersistentChildren(self.0.clone())
+    }
+}
+
+impl<T> ops::Deref for PersistentChildren<T> {
+    type Target = Vec<Arc<PersistentNode<T>>>;
+
+    fn deref(&self) -> &Vec<Arc<PersistentNode<T>>> {
+        &self.0
+    }
+}
+
+impl<T> ops::DerefMut for PersistentChildren<T> {
+    fn deref_mut(&mut self) -> &mut Vec<Arc<PersistentNode<T>>> {
+        &mut self.0
+    }
+}
+
+impl<T> Drop for PersistentChildren<T> {
+    fn drop(&mut self) {
+        // Take the children of the nodes owned only by this tree before dropping them, so each
+        // node is dropped without children.
+        let mut pending = std::mem::take(&mut self.0);
+        while let Some(node) = pending.pop() {
+            if let Ok(mut node) = Arc::try_unwrap(node) {
+                pending.append(&mut node.children);
+            }
+        }
+    }
+}
+
+impl<T> Clone for PersistentVecTree<T> {
+    fn clone(&self) -> Self {
+        PersistentVecTree {
+            root: self.root.clone(),
+        }
+    }
+}
+
+impl<T> Default for PersistentVecTree<T> {
+    fn default() -> Self {
+        PersistentVecTree::new()
+    }
+}
+
+impl<T> PersistentVecTree<T> {
+    /// Constructs a new, empty `PersistentVecTree`.
+    pub fn new() -> PersistentVecTree<T> {
+        PersistentVecTree { root: None }
+    }
+
+    /// Does the tree have no root node?
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    /// Get a shared reference to the data of the node at `path`, if it is in the tree.
+    pub fn get(&self, path: &[usize]) -> Option<&T> {
+        self.node(path).map(|node| &node.data)
+    }
+
+    /// Return the number of children of the node at `path`, if it is in the tree.
+    pub fn child_count(&self, path: &[usize]) -> Option<usize> {
+        self.node(path).map(|node| node.children.len())
+    }
+
+    /// Is the subtree at `path` shared between this version of the tree and `other`, that is
+    /// was it left untouched since they diverged?
+    pub fn shares_subtree(&self, other: &PersistentVecTree<T>, path: &[usize]) -> bool {
+        match (self.node_arc(path), other.node_arc(path)) {
+            (Some(node), Some(other_node)) => Arc::ptr_eq(node, other_node),
+            _ => false,
+        }
+    }
+
+    /// Return an iterator of references to the data of the nodes of the tree, in tree order.
+    pub fn values(&self) -> PersistentValuesIter<'_, T> {
+        PersistentValuesIter {
+            stack: self.root.iter().map(|root| &**root).collect(),
+        }
+    }
+
+    fn node(&self, path: &[usize]) -> Option<&PersistentNode<T>> {
+        self.node_arc(path).map(|node| &**node)
+    }
+
+    fn node_arc(&self, path: &[usize]) -> Option<&Arc<PersistentNode<T>>> {
+        let mut node = self.root.as_ref()?;
+        for &position in path {
+            node = node.children.get(position)?;
+        }
+        Some(node)
+    }
+}
+
+impl<T: Clone> PersistentVecTree<T> {
+    /// Insert `data` into the tree as a root node.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a root node already exists.
+    pub fn insert_root(&mut self, data: T) {
+        if self.root.is_some() {
+            panic!("A root node already exists");
+        }
+
+        self.root = Some(Arc::new(PersistentNode::new(data)));
+    }
+
+    /// Insert `data` into the tree as the last child of the node at `parent_path`, and return
+    /// the path of the new node.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the parent node is not in the tree.
+    pub fn insert(&mut self, parent_path: &[usize], data: T) -> Vec<usize> {
+        let parent = match self.node_mut(parent_path) {
+            Some(parent) => parent,
+            None => panic!("The parent node you are trying to insert into is invalid"),
+        };
+
+        parent.children.push(Arc::new(PersistentNode::new(data)));
+
+        let mut path = parent_path.to_vec();
+        path.push(parent.children.len() - 1);
+        path
+    }
+
+    /// Get an exclusive reference to the data of the node at `path`, if it is in the tree.
+    ///
+    /// The nodes on the path that are shared with other versions of the tree are copied.
+    pub fn get_mut(&mut self, path: &[usize]) -> Option<&mut T> {
+        self.node_mut(path).map(|node| &mut node.data)
+    }
+
+    /// Replace the data of the node at `path` by `data`, and return the previous data.
+    ///
+    /// If the node is not in the tree, then `None` is returned.
+    pub fn set(&mut self, path: &[usize], data: T) -> Option<T> {
+        self.node_mut(path)
+            .map(|node| std::mem::replace(&mut node.data, data))
+    }
+
+    /// Remove the node at `path` and its descendants from the tree, and return its data.
+    ///
+    /// The following siblings of the node shift back by one position. If the node is not in the
+    /// tree, then `None` is returned.
+    pub fn remove(&mut self, path: &[usize]) -> Option<T> {
+        let node = match path.split_last() {
+            Some((&position, parent_path)) => {
+                if position >= self.child_count(parent_path)? {
+                    return None;
+                }
+                self.node_mut(parent_path)?.children.remove(position)
+            }
+            None => self.root.take()?,
+        };
+
+        Some(match Arc::try_unwrap(node) {
+            Ok(node) => node.data,
+            Err(node) => node.data.clone(),
+        })
+    }
+
+    /// Return a `VecTree` with the content of the tree.
+    pub fn to_vec_tree(&self) -> VecTree<T> {
+        let mut tree = VecTree::new();
+        let root = match self.root {
+            Some(ref root) => root,
+            None => return tree,
+        };
+
+        let root_id = tree.insert_root(root.data.clone());
+        let mut pending = root
+            .children
+            .iter()
+            .rev()
+            .map(|child| (child, root_id))
+            .collect::<Vec<_>>();
+
+        while let Some((node, parent_id)) = pending.pop() {
+            let node_id = tree.insert(node.data.clone(), parent_id);
+            pending.extend(node.children.iter().rev().map(|child| (child, node_id)));
+        }
+
+        tree
+    }
+
+    // Return an exclusive reference to the node at `path`, copying the nodes on the path that are
+    // shared with other versions of the tree.
+    fn node_mut(&mut self, path: &[usize]) -> Option<&mut PersistentNode<T>> {
+        // Check the path first so no node is copied in vain.
+        self.node(path)?;
+
+        let mut node = Arc::make_mut(self.root.as_mut().unwrap());
+        for &position in path {
+            node = Arc::make_mut(&mut node.children[position]);
+        }
+        Some(node)
+    }
+}
+
+/// Two versions of a tree are equal if their nodes have equal data and are laid out the same way.
+/// Subtrees shared between the versions are not compared again.
+impl<T: PartialEq> PartialEq for PersistentVecTree<T> {
+    fn eq(&self, other: &PersistentVecTree<T>) -> bool {
+        let mut pending = match (&self.root, &other.root) {
+            (Some(root), Some(other_root)) => vec![(root, other_root)],
+            (None, None) => return true,
+            _ => return false,
+        };
+
+        while let Some((node, other_node)) = pending.pop() {
+            if Arc::ptr_eq(node, other_node) {
+                continue;
+            }
+            if node.data != other_node.data || node.children.len() != other_node.children.len() {
+                return false;
+            }
+            pending.extend(node.children.iter().zip(other_node.children.iter()));
+        }
+
+        true
+    }
+}
+
+impl<T: Eq> Eq for PersistentVecTree<T> {}
+
+/// Show the nodes of the tree in tree order, each one with its depth.
+///
+/// # Examples
+///
+/// ```
+/// use vec_tree::PersistentVecTree;
+///
+/// let mut tree = PersistentVecTree::new();
+/// tree.insert_root(0);
+/// let child = tree.insert(&[], 1);
+/// tree.insert(&child, 2);
+/// tree.insert(&[], 3);
+///
+/// assert_eq!(
+///     format!("{:?}", tree),
+///     "PersistentVecTree([(0, 0), (1, 1), (2, 2), (1, 3)])"
+/// );
+/// ```
+impl<T: fmt::Debug> fmt::Debug for PersistentVecTree<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("PersistentVecTree")
+            .field(&DebugNodes(self))
+            .finish()
+    }
+}
+
+struct DebugNodes<'a, T: 'a>(&'a PersistentVecTree<T>);
+
+impl<'a, T: fmt::Debug> fmt::Debug for DebugNodes<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut nodes = f.debug_list();
+        let mut pending: Vec<_> = self.0.root.iter().map(|root| (root, 0)).collect();
+        while let Some((node, depth)) = pending.pop() {
+            nodes.entry(&(depth, &node.data));
+            pending.extend(node.children.iter().rev().map(|child| (child, depth + 1)));
+        }
+        nodes.finish()
+    }
+}
+
+impl<'a, T: Clone> From<&'a VecTree<T>> for PersistentVecTree<T> {
+    fn from(tree: &'a VecTree<T>) -> PersistentVecTree<T> {
+        PersistentVecTree {
+            root: tree.get_root_index().map(|root_id| {
+                tree.fold_subtree(root_id, |data, children: &[Arc<PersistentNode<T>>]| {
+                    Arc::new(PersistentNode {
+                        data: data.clone(),
+                        children: PersistentChildren(children.to_vec()),
+                    })
+                })
+            }),
+        }
+    }
+}
+
+impl<T> From<VecTree<T>> for PersistentVecTree<T> {
+    fn from(tree: VecTree<T>) -> PersistentVecTree<T> {
+        // The nodes on the path from the root to the last node, whose children are being built.
+        let mut open: Vec<PersistentNode<T>> = Vec::new();
+
+        for (data, depth) in tree.into_iter_with_depth() {
+            close_nodes(&mut open, depth as usize);
+            open.push(PersistentNode::new(data));
+        }
+        close_nodes(&mut open, 1);
+
+        PersistentVecTree {
+            root: open.pop().map(Arc::new),
+        }
+    }
+}
+
+// Move the last open nodes into the children of their parent until only `depth` are left.
+fn close_nodes<T>(open: &mut Vec<PersistentNode<T>>, depth: usize) {
+    while open.len() > depth {
+        let node = open.pop().unwrap();
+        open.last_mut().unwrap().children.push(Arc::new(node));
+    }
+}
+
+/// An iterator of references to the data of the nodes of a `PersistentVecTree`, in tree order.
+pub struct PersistentValuesIter<'a, T: 'a> {
+    stack: Vec<&'a PersistentNode<T>>,
+}
+
+impl<'a, T> Iterator for PersistentValuesIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let node = self.stack.pop()?;
+        self.stack
+            .extend(node.children.iter().rev().map(|child| &**child));
+        Some(&node.data)
+    }
+}
+
+impl<'a, T> FusedIterator for PersistentValuesIter<'a, T> {}
@@ -0,0 +1,195 @@
+//! Strategies generating trees for property testing with [proptest](https://docs.rs/proptest).
+
+use super::VecTree;
+use generational_arena::Index;
+
+use proptest::collection::SizeRange;
+use proptest::strategy::{NewTree, Strategy, ValueTree};
+use proptest::test_runner::TestRunner;
+
+/// Create a strategy generating trees whose number of nodes is within `size`, and whose data is
+/// generated by `element`.
+///
+/// Each node after the root is inserted as the last child of a random node inserted before it.
+/// Trees shrink by removing subtrees, as long as the number of nodes stays within `size`, then
+/// by shrinking the data of the remaining nodes.
+///
+/// # Examples
+///
+/// ```
+/// use proptest::prelude::*;
+/// use vec_tree::VecTree;
+///
+/// proptest! {
+///     fn descendants_visit_every_node(tree in vec_tree::proptest::tree(any::<u8>(), 1..32)) {
+///         let root = tree.get_root_index().unwrap();
+///         prop_assert_eq!(tree.descendants(root).count(), tree.iter().count());
+///     }
+/// }
+/// # descendants_visit_every_node();
+/// ```
+pub fn tree<S: Strategy>(element: S, size: impl Into<SizeRange>) -> TreeStrategy<S> {
+    TreeStrategy {
+        element,
+        size: size.into(),
+    }
+}
+
+/// A strategy generating trees, created by `tree`.
+#[derive(Clone, Debug)]
+pub struct TreeStrategy<S> {
+    element: S,
+    size: SizeRange,
+}
+
+impl<S: Strategy> Strategy for TreeStrategy<S> {
+    type Tree = TreeValueTree<S::Tree>;
+    type Value = VecTree<S::Value>;
+
+    fn new_tree(&self, runner: &mut TestRunner) -> NewTree<Self> {
+        let (min_size, max_size) = self.size.start_end_incl();
+        let len = (min_size..=max_size).new_tree(runner)?.current();
+
+        let mut elements = Vec::with_capacity(len);
+        let mut parents = Vec::with_capacity(len);
+        for i in 0..len {
+            elements.push(self.element.new_tree(runner)?);
+            parents.push(if i == 0 {
+                0
+            } else {
+                (0..i).new_tree(runner)?.current()
+            });
+        }
+
+        Ok(TreeValueTree {
+            elements,
+            parents,
+            included: vec![true; len],
+            min_size,
+            shrink: Shrink::RemoveSubtree(1),
+            prev_shrink: None,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Shrink {
+    RemoveSubtree(usize),
+    ShrinkElement(usize),
+}
+
+/// The value tree of `TreeStrategy`, shrinking a generated tree.
+#[derive(Clone, Debug)]
+pub struct TreeValueTree<E> {
+    elements: Vec<E>,
+    // The position of the parent of each node, always before the node.
+    parents: Vec<usize>,
+    // Whether each node was kept while shrinking, a node is only in the tree if its ancestors are
+    // kept too.
+    included: Vec<bool>,
+    min_size: usize,
+    shrink: Shrink,
+    prev_shrink: Option<Shrink>,
+}
+
+impl<E: ValueTree> TreeValueTree<E> {
+    fn in_tree(&self) -> Vec<bool> {
+        let mut in_tree = Vec::with_capacity(self.included.len());
+        for (i, &included) in self.included.iter().enumerate() {
+            in_tree.push(included && (i == 0 || in_tree[self.parents[i]]));
+        }
+        in_tree
+    }
+
+    fn subtree_size(&self, in_tree: &[bool], position: usize) -> usize {
+        let mut in_subtree = vec![false; in_tree.len()];
+        in_subtree[position] = true;
+
+        let mut size = 1;
+        for i in position + 1..in_tree.len() {
+            if in_tree[i] && in_subtree[self.parents[i]] {
+                in_subtree[i] = true;
+                size += 1;
+            }
+        }
+        size
+    }
+}
+
+impl<E: ValueTree> ValueTree for TreeValueTree<E> {
+    type Value = VecTree<E::Value>;
+
+    fn current(&self) -> VecTree<E::Value> {
+        let mut tree = VecTree::with_capacity(self.elements.len());
+        let mut node_ids: Vec<Option<Index>> = Vec::with_capacity(self.elements.len());
+
+        for (i, element) in self.elements.iter().enumerate() {
+            let node_id = if !self.included[i] {
+                None
+            } else if i == 0 {
+                Some(tree.insert_root(element.current()))
+            } else {
+                node_ids[self.parents[i]].map(|parent_id| tree.insert(element.current(), parent_id))
+            };
+            node_ids.push(node_id);
+        }
+
+        tree
+    }
+
+    fn simplify(&mut self) -> bool {
+        loop {
+            match self.shrink {
+                Shrink::RemoveSubtree(position) if position >= self.elements.len() => {
+                    self.shrink = Shrink::ShrinkElement(0);
+                }
+                Shrink::RemoveSubtree(position) => {
+                    self.shrink = Shrink::RemoveSubtree(position + 1);
+
+                    let in_tree = self.in_tree();
+                    if !in_tree[position] {
+                        continue;
+                    }
+
+                    let size = in_tree.iter().filter(|&&in_tree| in_tree).count();
+                    if size - self.subtree_size(&in_tree, position) < self.min_size {
+                        continue;
+                    }
+
+                    self.included[position] = false;
+                    self.prev_shrink = Some(Shrink::RemoveSubtree(position));
+                    return true;
+                }
+                Shrink::ShrinkElement(position) if position >= self.elements.len() => {
+                    return false;
+                }
+                Shrink::ShrinkElement(position) => {
+                    if self.in_tree()[position] && self.elements[position].simplify() {
+                        self.prev_shrink = Some(Shrink::ShrinkElement(position));
+                        return true;
+                    }
+                    self.shrink = Shrink::ShrinkElement(position + 1);
+                }
+            }
+        }
+    }
+
+    fn complicate(&mut self) -> bool {
+        match self.prev_shrink {
+            Some(Shrink::RemoveSubtree(position)) => {
+                self.included[position] = true;
+                self.prev_shrink = None;
+                true
+            }
+            Some(Shrink::ShrinkElement(position)) => {
+                if self.elements[position].complicate() {
+                    true
+                } else {
+                    self.prev_shrink = None;
+                    false
+                }
+            }
+            None => false,
+        }
+    }
+}
@@ -0,0 +1,177 @@
+use super::VecTree;
+use generational_arena::Index;
+
+use std::collections::HashSet;
+use std::fmt;
+use std::iter::FusedIterator;
+use std::vec;
+
+enum Step<T> {
+    Children,
+    Descendants,
+    Parent,
+    Ancestors,
+    NthChild(usize),
+    Filter(Box<dyn Fn(&T) -> bool>),
+}
+
+/// A query selecting nodes of a tree, in the spirit of CSS selectors or XPath.
+///
+/// A query is built from a sequence of steps. Starting from a set containing a single node, each
+/// step produces a new set of nodes from the previous one. Nodes are kept in the order they are
+/// first reached and a node matched several times only appears once.
+///
+/// # Examples
+///
+/// ```
+/// use vec_tree::{Query, VecTree};
+///
+/// let mut tree = VecTree::new();
+/// let root = tree.insert_root(0);
+/// let child_1 = tree.insert(1, root);
+/// tree.insert(10, child_1);
+/// tree.insert(11, child_1);
+/// let child_2 = tree.insert(2, root);
+/// tree.insert(20, child_2);
+/// tree.insert(21, child_2);
+///
+/// // The second child of every descendant of `root` whose value is odd.
+/// let query = Query::new()
+///     .descendants()
+///     .filter(|value: &i32| value % 2 == 1)
+///     .nth_child(1);
+///
+/// let matches = query
+///     .run(&tree, root)
+///     .map(|node_id| tree[node_id])
+///     .collect::<Vec<_>>();
+///
+/// assert_eq!(matches, [11]);
+/// ```
+pub struct Query<T> {
+    steps: Vec<Step<T>>,
+}
+
+impl<T> Query<T> {
+    /// Constructs a new query matching only the node it is run from.
+    pub fn new() -> Query<T> {
+        Query { steps: Vec::new() }
+    }
+
+    /// Select the children of the matched nodes.
+    pub fn children(mut self) -> Query<T> {
+        self.steps.push(Step::Children);
+        self
+    }
+
+    /// Select the descendants of the matched nodes, the nodes themselves excluded.
+    pub fn descendants(mut self) -> Query<T> {
+        self.steps.push(Step::Descendants);
+        self
+    }
+
+    /// Select the parents of the matched nodes.
+    pub fn parent(mut self) -> Query<T> {
+        self.steps.push(Step::Parent);
+        self
+    }
+
+    /// Select the ancestors of the matched nodes, the nodes themselves excluded.
+    pub fn ancestors(mut self) -> Query<T> {
+        self.steps.push(Step::Ancestors);
+        self
+    }
+
+    /// Select the `n`-th child (zero-based) of the matched nodes.
+    pub fn nth_child(mut self, n: usize) -> Query<T> {
+        self.steps.push(Step::NthChild(n));
+        self
+    }
+
+    /// Keep only the matched nodes whose data matches `predicate`.
+    pub fn filter<P>(mut self, predicate: P) -> Query<T>
+    where
+        P: Fn(&T) -> bool + 'static,
+    {
+        self.steps.push(Step::Filter(Box::new(predicate)));
+        self
+    }
+
+    /// Run the query on `tree`, starting from the node at index `node_id`, and return an
+    /// iterator over the matched nodes.
+    ///
+    /// If the node is not in the tree, the iterator is empty.
+    pub fn run(&self, tree: &VecTree<T>, node_id: Index) -> QueryIter {
+        let mut matches = Vec::new();
+        if tree.contains(node_id) {
+            matches.push(node_id);
+        }
+
+        for step in &self.steps {
+            let mut seen = HashSet::new();
+            let mut next_matches = Vec::new();
+
+            {
+                let mut push = |node_id: Index| {
+                    if seen.insert(node_id) {
+                        next_matches.push(node_id);
+                    }
+                };
+
+                for &node_id in &matches {
+                    match *step {
+                        Step::Children => tree.children(node_id).for_each(&mut push),
+                        Step::Descendants => tree.descendants(node_id).skip(1).for_each(&mut push),
+                        Step::Parent => tree.parent(node_id).into_iter().for_each(&mut push),
+                        Step::Ancestors => tree.ancestors(node_id).skip(1).for_each(&mut push),
+                        Step::NthChild(n) => {
+                            tree.nth_child(node_id, n).into_iter().for_each(&mut push)
+                        }
+                        Step::Filter(ref predicate) => {
+                            if predicate(&tree[node_id]) {
+                                push(node_id);
+                            }
+                        }
+                    }
+                }
+            }
+
+            matches = next_matches;
+        }
+
+        QueryIter(matches.into_iter())
+    }
+}
+
+impl<T> Default for Query<T> {
+    fn default() -> Self {
+        Query::new()
+    }
+}
+
+impl<T> fmt::Debug for Query<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Query")
+            .field("steps", &self.steps.len())
+            .finish()
+    }
+}
+
+/// An iterator over the nodes matched by a `Query`.
+pub struct QueryIter(vec::IntoIter<Index>);
+
+impl Iterator for QueryIter {
+    type Item = Index;
+
+    fn next(&mut self) -> Option<Index> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl ExactSizeIterator for QueryIter {}
+
+impl FusedIterator for QueryIter {}
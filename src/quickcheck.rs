@@ -0,0 +1,55 @@
+use super::VecTree;
+use generational_arena::Index;
+
+use quickcheck::{Arbitrary, Gen};
+
+/// Generate a tree with up to `g.size()` nodes, each node after the root being inserted as the
+/// last child of a random node inserted before it.
+///
+/// A tree shrinks by removing one of its subtrees, starting from the root, then by shrinking the
+/// data of one of its nodes. The indices of the remaining nodes are kept.
+impl<T: Arbitrary> Arbitrary for VecTree<T> {
+    fn arbitrary(g: &mut Gen) -> VecTree<T> {
+        let len = usize::arbitrary(g) % (g.size() + 1);
+        let mut tree = VecTree::with_capacity(len);
+        if len == 0 {
+            return tree;
+        }
+
+        let mut node_ids = Vec::with_capacity(len);
+        node_ids.push(tree.insert_root(T::arbitrary(g)));
+
+        for _ in 1..len {
+            let parent_id = *g.choose(&node_ids).unwrap();
+            node_ids.push(tree.insert(T::arbitrary(g), parent_id));
+        }
+
+        tree
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = VecTree<T>>> {
+        let node_ids = match self.root_index {
+            Some(root_id) => self.descendants(root_id).collect::<Vec<Index>>(),
+            None => Vec::new(),
+        };
+
+        let tree = self.clone();
+        let removals = node_ids.clone().into_iter().map(move |node_id| {
+            let mut tree = tree.clone();
+            tree.remove(node_id);
+            tree
+        });
+
+        let tree = self.clone();
+        let shrunk_values = node_ids.into_iter().flat_map(move |node_id| {
+            let tree = tree.clone();
+            tree[node_id].shrink().map(move |data| {
+                let mut tree = tree.clone();
+                tree[node_id] = data;
+                tree
+            })
+        });
+
+        Box::new(removals.chain(shrunk_values))
+    }
+}
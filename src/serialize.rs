@@ -0,0 +1,231 @@
+use super::VecTree;
+use generational_arena::Index;
+
+use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeSeq, Serializer};
+use std::collections::HashMap;
+use std::fmt;
+use std::marker::PhantomData;
+
+// The most nodes to allocate room for from the length announced by the input, which cannot be
+// trusted.
+const MAX_RESERVED_NODES: usize = 1 << 16;
+
+/// A tree is serialized as the sequence of its nodes in tree order, each node being a pair of its
+/// data and its number of children. A tree with no root gives an empty sequence.
+///
+/// This flat form does not nest nodes, so trees of any depth can be serialized and deserialized
+/// without reaching the recursion limit of a format. Only the structure and the data are
+/// serialized, the indices of the nodes are not preserved.
+///
+/// # Examples
+///
+/// ```
+/// use vec_tree::VecTree;
+///
+/// let mut tree = VecTree::new();
+/// let root = tree.insert_root(0);
+/// let child = tree.insert(1, root);
+/// tree.insert(2, child);
+/// tree.insert(3, root);
+///
+/// let json = serde_json::to_string(&tree).unwrap();
+/// assert_eq!(json, "[[0,2],[1,1],[2,0],[3,0]]");
+///
+/// let copy: VecTree<i32> = serde_json::from_str(&json).unwrap();
+/// assert_eq!(copy, tree);
+/// ```
+impl<T: Serialize> Serialize for VecTree<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut nodes = serializer.serialize_seq(Some(self.nodes.len()))?;
+        if let Some(root_id) = self.root_index {
+            for node_id in self.descendants(root_id) {
+                nodes.serialize_element(&(&self[node_id], self.child_count(node_id)))?;
+            }
+        }
+        nodes.end()
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for VecTree<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<VecTree<T>, D::Error> {
+        deserializer.deserialize_seq(TreeVisitor(PhantomData))
+    }
+}
+
+struct TreeVisitor<T>(PhantomData<T>);
+
+impl<'de, T: Deserialize<'de>> Visitor<'de> for TreeVisitor<T> {
+    type Value = VecTree<T>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a sequence of nodes with their number of children")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<VecTree<T>, A::Error> {
+        let mut builder = FlatTreeBuilder::new(seq.size_hint());
+        while let Some((data, child_count)) = seq.next_element::<(T, usize)>()? {
+            builder.push(data, child_count)?;
+        }
+        builder.finish()
+    }
+}
+
+// Builds a tree from its nodes in tree order, each with its number of children.
+struct FlatTreeBuilder<T> {
+    tree: VecTree<T>,
+    // The nodes still expecting children, with the number of children left to read.
+    open_nodes: Vec<(Index, usize)>,
+}
+
+impl<T> FlatTreeBuilder<T> {
+    fn new(size_hint: Option<usize>) -> FlatTreeBuilder<T> {
+        FlatTreeBuilder {
+            tree: VecTree::with_capacity(size_hint.unwrap_or(0).min(MAX_RESERVED_NODES)),
+            open_nodes: Vec::new(),
+        }
+    }
+
+    fn push<E: de::Error>(&mut self, data: T, child_count: usize) -> Result<Index, E> {
+        while let Some(&(_, 0)) = self.open_nodes.last() {
+            self.open_nodes.pop();
+        }
+
+        let node_id = match self.open_nodes.last_mut() {
+            Some((parent_id, remaining)) => {
+                *remaining -= 1;
+                self.tree.insert(data, *parent_id)
+            }
+            None if self.tree.get_root_index().is_none() => self.tree.insert_root(data),
+            None => return Err(E::custom("more nodes than children")),
+        };
+
+        self.open_nodes.push((node_id, child_count));
+        Ok(node_id)
+    }
+
+    fn finish<E: de::Error>(self) -> Result<VecTree<T>, E> {
+        if self.open_nodes.iter().any(|&(_, remaining)| remaining > 0) {
+            return Err(E::custom("fewer nodes than children"));
+        }
+
+        Ok(self.tree)
+    }
+}
+
+impl<T> VecTree<T> {
+    /// Return a value serializing the tree like the tree itself, with the index of each node
+    /// added, so the indices can be remapped when deserializing it as a `RemappedVecTree`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::{RemappedVecTree, VecTree};
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(0);
+    /// let removed = tree.insert(1, root);
+    /// tree.remove(removed);
+    /// let child = tree.insert(2, root);
+    ///
+    /// let json = serde_json::to_string(&tree.with_indices()).unwrap();
+    /// let remapped: RemappedVecTree<i32> = serde_json::from_str(&json).unwrap();
+    ///
+    /// let child = remapped.remap(child).unwrap();
+    /// assert_eq!(remapped.tree()[child], 2);
+    /// ```
+    pub fn with_indices(&self) -> WithIndices<'_, T> {
+        WithIndices(self)
+    }
+}
+
+/// A value serializing a tree along with the index of each node, created by
+/// `VecTree::with_indices`.
+///
+/// The nodes are serialized in tree order as for the tree itself, each node being a triple of
+/// its index, as a pair of slot and generation, its data and its number of children.
+pub struct WithIndices<'a, T: 'a>(&'a VecTree<T>);
+
+impl<'a, T: Serialize> Serialize for WithIndices<'a, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let tree = self.0;
+        let mut nodes = serializer.serialize_seq(Some(tree.nodes.len()))?;
+        if let Some(root_id) = tree.root_index {
+            for node_id in tree.descendants(root_id) {
+                nodes.serialize_element(&(
+                    node_id.into_raw_parts(),
+                    &tree[node_id],
+                    tree.child_count(node_id),
+                ))?;
+            }
+        }
+        nodes.end()
+    }
+}
+
+/// A tree deserialized from the output of `VecTree::with_indices`, along with a table mapping
+/// the indices of the serialized tree to the indices of the new one.
+///
+/// This allows to restore references to nodes that were persisted alongside the tree.
+#[derive(Clone, Debug)]
+pub struct RemappedVecTree<T> {
+    tree: VecTree<T>,
+    remap: HashMap<Index, Index>,
+}
+
+impl<T> RemappedVecTree<T> {
+    /// Return the deserialized tree.
+    pub fn tree(&self) -> &VecTree<T> {
+        &self.tree
+    }
+
+    /// Return the index in the deserialized tree of the node at index `old_id` in the serialized
+    /// tree, if it was in the serialized tree.
+    pub fn remap(&self, old_id: Index) -> Option<Index> {
+        self.remap.get(&old_id).cloned()
+    }
+
+    /// Return the deserialized tree and the table mapping old indices to new ones.
+    pub fn into_parts(self) -> (VecTree<T>, HashMap<Index, Index>) {
+        (self.tree, self.remap)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for RemappedVecTree<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<RemappedVecTree<T>, D::Error> {
+        deserializer.deserialize_seq(RemappedTreeVisitor(PhantomData))
+    }
+}
+
+struct RemappedTreeVisitor<T>(PhantomData<T>);
+
+impl<'de, T: Deserialize<'de>> Visitor<'de> for RemappedTreeVisitor<T> {
+    type Value = RemappedVecTree<T>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a sequence of nodes with their index and number of children")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<RemappedVecTree<T>, A::Error> {
+        let mut builder = FlatTreeBuilder::new(seq.size_hint());
+        let mut remap = HashMap::new();
+
+        while let Some((index, data, child_count)) =
+            seq.next_element::<((usize, u64), T, usize)>()?
+        {
+            let node_id = builder.push(data, child_count)?;
+            if remap.insert(index_from_parts(index), node_id).is_some() {
+                return Err(de::Error::custom("duplicate node index"));
+            }
+        }
+
+        Ok(RemappedVecTree {
+            tree: builder.finish()?,
+            remap,
+        })
+    }
+}
+
+fn index_from_parts((slot, generation): (usize, u64)) -> Index {
+    Index::from_raw_parts(slot, generation)
+}
@@ -0,0 +1,110 @@
+use super::{DescendantsIter, TraverseIter, VecTree};
+use generational_arena::Index;
+
+use futures_core::task::{Context, Poll};
+use futures_core::Stream;
+use std::pin::Pin;
+
+impl<T> VecTree<T> {
+    /// Return a stream of references to this node and its descendants, in tree order.
+    ///
+    /// The stream yields control back to the executor every `chunk_size` nodes, so walking a
+    /// very large tree does not block other tasks.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use futures::executor::block_on;
+    /// use futures::StreamExt;
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(0);
+    /// tree.insert(1, root);
+    /// tree.insert(2, root);
+    ///
+    /// let values = block_on(
+    ///     tree.descendants_stream(root, 2)
+    ///         .map(|node_id| tree[node_id])
+    ///         .collect::<Vec<_>>(),
+    /// );
+    ///
+    /// assert_eq!(values, [0, 1, 2]);
+    /// ```
+    pub fn descendants_stream(
+        &self,
+        node_id: Index,
+        chunk_size: usize,
+    ) -> TreeStream<DescendantsIter<'_, T>> {
+        TreeStream::new(self.descendants(node_id), chunk_size)
+    }
+
+    /// Return a stream of references to this node and its descendants, in tree order, each node
+    /// being yielded as a `NodeEdge::Start` before its descendants and as a `NodeEdge::End` after
+    /// them.
+    ///
+    /// The stream yields control back to the executor every `chunk_size` edges.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is 0.
+    pub fn traverse_stream(
+        &self,
+        node_id: Index,
+        chunk_size: usize,
+    ) -> TreeStream<TraverseIter<'_, T>> {
+        TreeStream::new(self.traverse(node_id), chunk_size)
+    }
+}
+
+/// A stream adapting a tree iterator, yielding control back to the executor between chunks of
+/// items.
+#[derive(Debug)]
+pub struct TreeStream<I> {
+    iter: I,
+    chunk_size: usize,
+    // The number of items yielded since control was last given back to the executor.
+    yielded: usize,
+}
+
+impl<I> TreeStream<I> {
+    fn new(iter: I, chunk_size: usize) -> TreeStream<I> {
+        assert!(chunk_size > 0, "The chunk size must be greater than 0");
+
+        TreeStream {
+            iter,
+            chunk_size,
+            yielded: 0,
+        }
+    }
+
+    /// Return the underlying iterator.
+    pub fn into_inner(self) -> I {
+        self.iter
+    }
+}
+
+impl<I: Iterator + Unpin> Stream for TreeStream<I> {
+    type Item = I::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<I::Item>> {
+        let this = self.get_mut();
+
+        if this.yielded == this.chunk_size {
+            this.yielded = 0;
+            cx.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+
+        this.yielded += 1;
+        Poll::Ready(this.iter.next())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
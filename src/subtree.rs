@@ -0,0 +1,224 @@
+use super::VecTree;
+use generational_arena::Index;
+
+use std::collections::HashMap;
+use std::iter::FusedIterator;
+use std::{ops, slice};
+
+impl<T> VecTree<T> {
+    /// Split the tree into exclusive views over the subtrees rooted at `roots`, so they can be
+    /// modified at the same time, for instance from different threads.
+    ///
+    /// The views are returned in the order of `roots`, and borrow the tree until they are
+    /// dropped. They only give access to the data of the nodes, the structure of the tree cannot
+    /// be modified through them.
+    ///
+    /// This takes time linear in the capacity of the tree, whatever the size of the subtrees: the
+    /// exclusive references can only be taken safely by walking the whole arena. Splitting a large
+    /// tree into many subtrees at once is much cheaper than calling this repeatedly.
+    ///
+    /// # Panics
+    ///
+    /// Panics if one of the roots is not in the tree, or if the subtrees overlap, that is if a
+    /// root is given twice or is in the subtree of another one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::thread;
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(0);
+    /// let child_1 = tree.insert(1, root);
+    /// let child_2 = tree.insert(2, root);
+    /// let grandchild = tree.insert(3, child_2);
+    ///
+    /// thread::scope(|scope| {
+    ///     for mut subtree in tree.subtrees_mut(&[child_1, child_2]) {
+    ///         scope.spawn(move || {
+    ///             for (_, value) in subtree.iter_mut() {
+    ///                 *value *= 10;
+    ///             }
+    ///         });
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(tree[root], 0);
+    /// assert_eq!(tree[child_1], 10);
+    /// assert_eq!(tree[grandchild], 30);
+    /// ```
+    pub fn subtrees_mut(&mut self, roots: &[Index]) -> Vec<SubtreeMut<'_, T>> {
+        // The subtree each node belongs to, and its position in the subtree.
+        let mut owners = HashMap::new();
+        let mut positions = Vec::with_capacity(roots.len());
+
+        for (subtree, &root) in roots.iter().enumerate() {
+            let mut subtree_positions = HashMap::new();
+            for (position, node_id) in self.descendants(root).enumerate() {
+                if owners.insert(node_id, (subtree, position)).is_some() {
+                    panic!("The subtrees overlap");
+                }
+                subtree_positions.insert(node_id, position);
+            }
+            positions.push(subtree_positions);
+        }
+
+        let mut nodes = positions
+            .iter()
+            .map(|subtree_positions| {
+                (0..subtree_positions.len())
+                    .map(|_| None)
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        for (node_id, data) in self.iter_mut() {
+            if let Some(&(subtree, position)) = owners.get(&node_id) {
+                nodes[subtree][position] = Some((node_id, data));
+            }
+        }
+
+        nodes
+            .into_iter()
+            .zip(positions)
+            .map(|(subtree_nodes, positions)| SubtreeMut {
+                nodes: subtree_nodes.into_iter().map(Option::unwrap).collect(),
+                positions,
+            })
+            .collect()
+    }
+
+    /// Split the tree into two exclusive views over the subtrees rooted at `a` and `b`.
+    ///
+    /// See `VecTree::subtrees_mut`, this also walks the whole arena.
+    ///
+    /// # Panics
+    ///
+    /// Panics if one of the roots is not in the tree, or if the subtrees overlap.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(0);
+    /// let child_1 = tree.insert(1, root);
+    /// let child_2 = tree.insert(2, root);
+    /// let grandchild = tree.insert(3, child_2);
+    ///
+    /// let (mut left, mut right) = tree.split_subtrees_mut(child_1, child_2);
+    /// std::mem::swap(&mut left[child_1], &mut right[grandchild]);
+    ///
+    /// assert_eq!(tree[child_1], 3);
+    /// assert_eq!(tree[grandchild], 1);
+    /// ```
+    pub fn split_subtrees_mut(
+        &mut self,
+        a: Index,
+        b: Index,
+    ) -> (SubtreeMut<'_, T>, SubtreeMut<'_, T>) {
+        let mut subtrees = self.subtrees_mut(&[a, b]);
+        let subtree_b = subtrees.pop().unwrap();
+        let subtree_a = subtrees.pop().unwrap();
+        (subtree_a, subtree_b)
+    }
+}
+
+/// An exclusive view over the data of the nodes of a subtree, created by
+/// `VecTree::subtrees_mut`.
+#[derive(Debug)]
+pub struct SubtreeMut<'a, T: 'a> {
+    // The nodes of the subtree in tree order.
+    nodes: Vec<(Index, &'a mut T)>,
+    positions: HashMap<Index, usize>,
+}
+
+impl<'a, T> SubtreeMut<'a, T> {
+    /// Return the index of the root of the subtree.
+    pub fn root(&self) -> Index {
+        self.nodes[0].0
+    }
+
+    /// Is the node at index `node_id` in the subtree?
+    pub fn contains(&self, node_id: Index) -> bool {
+        self.positions.contains_key(&node_id)
+    }
+
+    /// Get a shared reference to the element at index `node_id` if it is in the subtree.
+    pub fn get(&self, node_id: Index) -> Option<&T> {
+        let position = *self.positions.get(&node_id)?;
+        Some(&*self.nodes[position].1)
+    }
+
+    /// Get an exclusive reference to the element at index `node_id` if it is in the subtree.
+    pub fn get_mut(&mut self, node_id: Index) -> Option<&mut T> {
+        let position = *self.positions.get(&node_id)?;
+        Some(&mut *self.nodes[position].1)
+    }
+
+    /// Return an iterator of shared references to the nodes of the subtree, along with their
+    /// data, in tree order.
+    pub fn iter(&self) -> SubtreeIter<'_, T> {
+        SubtreeIter(self.nodes.iter())
+    }
+
+    /// Return an iterator of exclusive references to the nodes of the subtree, along with their
+    /// data, in tree order.
+    pub fn iter_mut(&mut self) -> SubtreeIterMut<'_, 'a, T> {
+        SubtreeIterMut(self.nodes.iter_mut())
+    }
+}
+
+impl<'a, T> ops::Index<Index> for SubtreeMut<'a, T> {
+    type Output = T;
+
+    fn index(&self, index: Index) -> &T {
+        self.get(index).unwrap()
+    }
+}
+
+impl<'a, T> ops::IndexMut<Index> for SubtreeMut<'a, T> {
+    fn index_mut(&mut self, index: Index) -> &mut T {
+        self.get_mut(index).unwrap()
+    }
+}
+
+/// An iterator of shared references to the nodes of a subtree view, along with their data.
+pub struct SubtreeIter<'b, T: 'b>(slice::Iter<'b, (Index, &'b mut T)>);
+
+impl<'b, T> Iterator for SubtreeIter<'b, T> {
+    type Item = (Index, &'b T);
+
+    fn next(&mut self) -> Option<(Index, &'b T)> {
+        self.0.next().map(|(node_id, data)| (*node_id, &**data))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<'b, T> ExactSizeIterator for SubtreeIter<'b, T> {}
+
+impl<'b, T> FusedIterator for SubtreeIter<'b, T> {}
+
+/// An iterator of exclusive references to the nodes of a subtree view, along with their data.
+pub struct SubtreeIterMut<'b, 'a: 'b, T: 'a>(slice::IterMut<'b, (Index, &'a mut T)>);
+
+impl<'b, 'a, T> Iterator for SubtreeIterMut<'b, 'a, T> {
+    type Item = (Index, &'b mut T);
+
+    fn next(&mut self) -> Option<(Index, &'b mut T)> {
+        self.0.next().map(|(node_id, data)| (*node_id, &mut **data))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<'b, 'a, T> ExactSizeIterator for SubtreeIterMut<'b, 'a, T> {}
+
+impl<'b, 'a, T> FusedIterator for SubtreeIterMut<'b, 'a, T> {}
@@ -0,0 +1,323 @@
+use super::{AncestorsIter, ChildrenIter, DescendantsIter, VecTree};
+use generational_arena::Index;
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::iter::FusedIterator;
+use std::marker::PhantomData;
+use std::ops;
+
+/// An index into a `TaggedVecTree` with the marker type `M`.
+///
+/// Indices of trees with different markers are different types, so using an index with the wrong
+/// tree does not compile.
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent, bound = "")
+)]
+pub struct TypedIndex<M> {
+    index: Index,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    marker: PhantomData<fn() -> M>,
+}
+
+impl<M> TypedIndex<M> {
+    fn new(index: Index) -> TypedIndex<M> {
+        TypedIndex {
+            index,
+            marker: PhantomData,
+        }
+    }
+
+    /// Return the untyped index, to be used with the underlying `VecTree`.
+    pub fn index(self) -> Index {
+        self.index
+    }
+}
+
+impl<M> Clone for TypedIndex<M> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<M> Copy for TypedIndex<M> {}
+
+impl<M> PartialEq for TypedIndex<M> {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index
+    }
+}
+
+impl<M> Eq for TypedIndex<M> {}
+
+impl<M> PartialOrd for TypedIndex<M> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<M> Ord for TypedIndex<M> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.index.cmp(&other.index)
+    }
+}
+
+impl<M> Hash for TypedIndex<M> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.index.hash(state);
+    }
+}
+
+impl<M> fmt::Debug for TypedIndex<M> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("TypedIndex").field(&self.index).finish()
+    }
+}
+
+/// A `VecTree` tagged with the marker type `M`, handing out `TypedIndex<M>` instead of `Index`.
+///
+/// The marker is usually an empty type declared for each kind of tree, so mixing up indices of
+/// two trees becomes a compile error instead of a silent `None` or a wrong node.
+///
+/// Read-only access to the underlying tree, with untyped indices, is provided through `Deref`.
+///
+/// # Examples
+///
+/// ```compile_fail
+/// use vec_tree::TaggedVecTree;
+///
+/// enum Scene {}
+/// enum Ui {}
+///
+/// let mut scene = TaggedVecTree::<_, Scene>::new();
+/// let mut ui = TaggedVecTree::<_, Ui>::new();
+/// scene.insert_root("camera");
+/// let button = ui.insert_root("button");
+///
+/// scene.get(button);
+/// ```
+///
+/// ```
+/// use vec_tree::TaggedVecTree;
+///
+/// enum Scene {}
+///
+/// let mut scene = TaggedVecTree::<_, Scene>::new();
+/// let root = scene.insert_root("root");
+/// let camera = scene.insert("camera", root);
+///
+/// assert_eq!(scene[camera], "camera");
+/// assert_eq!(scene.parent(camera), Some(root));
+/// ```
+pub struct TaggedVecTree<T, M> {
+    tree: VecTree<T>,
+    marker: PhantomData<fn() -> M>,
+}
+
+impl<T: Clone, M> Clone for TaggedVecTree<T, M> {
+    fn clone(&self) -> Self {
+        TaggedVecTree {
+            tree: self.tree.clone(),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T: fmt::Debug, M> fmt::Debug for TaggedVecTree<T, M> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("TaggedVecTree")
+            .field("tree", &self.tree)
+            .finish()
+    }
+}
+
+impl<T, M> Default for TaggedVecTree<T, M> {
+    fn default() -> Self {
+        TaggedVecTree::new()
+    }
+}
+
+impl<T, M> TaggedVecTree<T, M> {
+    /// Constructs a new, empty `TaggedVecTree`.
+    pub fn new() -> TaggedVecTree<T, M> {
+        TaggedVecTree {
+            tree: VecTree::new(),
+            marker: PhantomData,
+        }
+    }
+
+    /// Constructs a new, empty `TaggedVecTree` with the specified capacity.
+    pub fn with_capacity(n: usize) -> TaggedVecTree<T, M> {
+        TaggedVecTree {
+            tree: VecTree::with_capacity(n),
+            marker: PhantomData,
+        }
+    }
+
+    /// Insert `data` into the tree as a root node, allocating more capacity if necessary.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a root node already exists.
+    pub fn insert_root(&mut self, data: T) -> TypedIndex<M> {
+        TypedIndex::new(self.tree.insert_root(data))
+    }
+
+    /// Insert `data` into the tree as the last child of `parent_id`, allocating more capacity if
+    /// necessary.
+    pub fn insert(&mut self, data: T, parent_id: TypedIndex<M>) -> TypedIndex<M> {
+        TypedIndex::new(self.tree.insert(data, parent_id.index))
+    }
+
+    /// Remove the element at index `node_id` and its descendants from the tree.
+    ///
+    /// If the element at index `node_id` is still in the tree, then it is returned. If it is not
+    /// in the tree, then `None` is returned.
+    pub fn remove(&mut self, node_id: TypedIndex<M>) -> Option<T> {
+        self.tree.remove(node_id.index)
+    }
+
+    /// Move the node at index `new_child_id`, and its descendants, to the last child position of
+    /// the node at index `node_id`.
+    pub fn append_child(&mut self, node_id: TypedIndex<M>, new_child_id: TypedIndex<M>) {
+        self.tree.append_child(node_id.index, new_child_id.index);
+    }
+
+    /// Is the element at index `node_id` in the tree?
+    pub fn contains(&self, node_id: TypedIndex<M>) -> bool {
+        self.tree.contains(node_id.index)
+    }
+
+    /// Get a shared reference to the element at index `node_id` if it is in the tree.
+    pub fn get(&self, node_id: TypedIndex<M>) -> Option<&T> {
+        self.tree.get(node_id.index)
+    }
+
+    /// Get an exclusive reference to the element at index `node_id` if it is in the tree.
+    pub fn get_mut(&mut self, node_id: TypedIndex<M>) -> Option<&mut T> {
+        self.tree.get_mut(node_id.index)
+    }
+
+    /// Get the root node index from the tree.
+    pub fn get_root_index(&self) -> Option<TypedIndex<M>> {
+        self.tree.get_root_index().map(TypedIndex::new)
+    }
+
+    /// Return the index of the parent of the node at index `node_id`, if any.
+    pub fn parent(&self, node_id: TypedIndex<M>) -> Option<TypedIndex<M>> {
+        self.tree.parent(node_id.index).map(TypedIndex::new)
+    }
+
+    /// Return the index of the first child of the node at index `node_id`, if any.
+    pub fn first_child(&self, node_id: TypedIndex<M>) -> Option<TypedIndex<M>> {
+        self.tree.first_child(node_id.index).map(TypedIndex::new)
+    }
+
+    /// Return the index of the last child of the node at index `node_id`, if any.
+    pub fn last_child(&self, node_id: TypedIndex<M>) -> Option<TypedIndex<M>> {
+        self.tree.last_child(node_id.index).map(TypedIndex::new)
+    }
+
+    /// Return the index of the next sibling of the node at index `node_id`, if any.
+    pub fn next_sibling(&self, node_id: TypedIndex<M>) -> Option<TypedIndex<M>> {
+        self.tree.next_sibling(node_id.index).map(TypedIndex::new)
+    }
+
+    /// Return the index of the previous sibling of the node at index `node_id`, if any.
+    pub fn previous_sibling(&self, node_id: TypedIndex<M>) -> Option<TypedIndex<M>> {
+        self.tree
+            .previous_sibling(node_id.index)
+            .map(TypedIndex::new)
+    }
+
+    /// Return an iterator of typed references to this node’s children.
+    pub fn children(&self, node_id: TypedIndex<M>) -> TypedIter<M, ChildrenIter<'_, T>> {
+        TypedIter::new(self.tree.children(node_id.index))
+    }
+
+    /// Return an iterator of typed references to this node and its ancestors.
+    pub fn ancestors(&self, node_id: TypedIndex<M>) -> TypedIter<M, AncestorsIter<'_, T>> {
+        TypedIter::new(self.tree.ancestors(node_id.index))
+    }
+
+    /// Return an iterator of typed references to this node and its descendants, in tree order.
+    pub fn descendants(&self, node_id: TypedIndex<M>) -> TypedIter<M, DescendantsIter<'_, T>> {
+        TypedIter::new(self.tree.descendants(node_id.index))
+    }
+
+    /// Clear all the items inside the tree, but keep its allocation.
+    pub fn clear(&mut self) {
+        self.tree.clear();
+    }
+
+    /// Return the underlying untyped tree.
+    pub fn into_inner(self) -> VecTree<T> {
+        self.tree
+    }
+}
+
+impl<T, M> ops::Deref for TaggedVecTree<T, M> {
+    type Target = VecTree<T>;
+
+    fn deref(&self) -> &VecTree<T> {
+        &self.tree
+    }
+}
+
+impl<T, M> ops::Index<TypedIndex<M>> for TaggedVecTree<T, M> {
+    type Output = T;
+
+    fn index(&self, index: TypedIndex<M>) -> &T {
+        &self.tree[index.index]
+    }
+}
+
+impl<T, M> ops::IndexMut<TypedIndex<M>> for TaggedVecTree<T, M> {
+    fn index_mut(&mut self, index: TypedIndex<M>) -> &mut T {
+        &mut self.tree[index.index]
+    }
+}
+
+/// An iterator of typed indices, adapting an iterator of indices of a `TaggedVecTree`.
+pub struct TypedIter<M, I> {
+    iter: I,
+    marker: PhantomData<fn() -> M>,
+}
+
+impl<M, I> TypedIter<M, I> {
+    fn new(iter: I) -> TypedIter<M, I> {
+        TypedIter {
+            iter,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<M, I> Iterator for TypedIter<M, I>
+where
+    I: Iterator<Item = Index>,
+{
+    type Item = TypedIndex<M>;
+
+    fn next(&mut self) -> Option<TypedIndex<M>> {
+        self.iter.next().map(TypedIndex::new)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<M, I> DoubleEndedIterator for TypedIter<M, I>
+where
+    I: DoubleEndedIterator<Item = Index>,
+{
+    fn next_back(&mut self) -> Option<TypedIndex<M>> {
+        self.iter.next_back().map(TypedIndex::new)
+    }
+}
+
+impl<M, I> FusedIterator for TypedIter<M, I> where I: FusedIterator<Item = Index> {}
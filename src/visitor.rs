@@ -0,0 +1,96 @@
+use super::{NodeEdge, VecTree};
+use generational_arena::Index;
+
+use std::ops::ControlFlow;
+
+/// What to do after entering a node, returned by `Visitor::enter`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VisitAction {
+    /// Visit the descendants of the node.
+    Continue,
+
+    /// Skip the descendants of the node, its `exit` hook is called right away.
+    SkipChildren,
+}
+
+/// Hooks called by `VecTree::visit` when entering and exiting each node of a subtree.
+///
+/// # Examples
+///
+/// ```
+/// use std::ops::ControlFlow;
+/// use vec_tree::{VecTree, VisitAction, Visitor};
+///
+/// // Render the tree as nested parentheses, skipping negative subtrees.
+/// struct Printer(String);
+///
+/// impl Visitor<i32> for Printer {
+///     fn enter(&mut self, _: vec_tree::Index, data: &i32) -> ControlFlow<(), VisitAction> {
+///         self.0 += &format!("({}", data);
+///         if *data < 0 {
+///             ControlFlow::Continue(VisitAction::SkipChildren)
+///         } else {
+///             ControlFlow::Continue(VisitAction::Continue)
+///         }
+///     }
+///
+///     fn exit(&mut self, _: vec_tree::Index, _: &i32) {
+///         self.0 += ")";
+///     }
+/// }
+///
+/// let mut tree = VecTree::new();
+/// let root = tree.insert_root(0);
+/// let child_1 = tree.insert(-1, root);
+/// tree.insert(2, child_1);
+/// tree.insert(3, root);
+///
+/// let mut printer = Printer(String::new());
+/// assert_eq!(tree.visit(root, &mut printer), ControlFlow::Continue(()));
+///
+/// assert_eq!(printer.0, "(0(-1)(3))");
+/// ```
+pub trait Visitor<T> {
+    /// Called before the descendants of the node at index `node_id` are visited.
+    ///
+    /// Returning `ControlFlow::Break` stops the visit immediately, no other hook is called.
+    fn enter(&mut self, node_id: Index, data: &T) -> ControlFlow<(), VisitAction> {
+        let _ = (node_id, data);
+        ControlFlow::Continue(VisitAction::Continue)
+    }
+
+    /// Called after the descendants of the node at index `node_id` have been visited or skipped.
+    fn exit(&mut self, node_id: Index, data: &T) {
+        let _ = (node_id, data);
+    }
+}
+
+impl<T> VecTree<T> {
+    /// Visit this node and its descendants in tree order, calling the `enter` hook of `visitor`
+    /// before the descendants of each node and its `exit` hook after them.
+    ///
+    /// Returns `ControlFlow::Break` if the visit was stopped early by the visitor.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node is not in the tree.
+    pub fn visit<V>(&self, node_id: Index, visitor: &mut V) -> ControlFlow<()>
+    where
+        V: Visitor<T> + ?Sized,
+    {
+        let mut traverse = self.traverse(node_id);
+
+        while let Some(edge) = traverse.next() {
+            match edge {
+                NodeEdge::Start(node_id) => {
+                    if visitor.enter(node_id, &self[node_id])? == VisitAction::SkipChildren {
+                        traverse.skip_subtree();
+                    }
+                }
+                NodeEdge::End(node_id) => visitor.exit(node_id, &self[node_id]),
+            }
+        }
+
+        ControlFlow::Continue(())
+    }
+}
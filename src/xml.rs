@@ -0,0 +1,300 @@
+use super::{NodeEdge, VecTree};
+use generational_arena::Index;
+
+use quick_xml::errors::IllFormedError;
+use quick_xml::events::{BytesCData, BytesEnd, BytesStart, BytesText, Event};
+use quick_xml::{Reader, Writer};
+use std::error::Error;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+
+/// A node of an XML document, as stored in a `VecTree<XmlNode>`.
+///
+/// Only elements have children.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum XmlNode {
+    /// An element with its name and its attributes, in document order.
+    Element {
+        name: String,
+        attributes: Vec<(String, String)>,
+    },
+    /// Some text, unescaped.
+    Text(String),
+    /// The content of a CDATA section.
+    CData(String),
+    /// The content of a comment.
+    Comment(String),
+}
+
+impl XmlNode {
+    /// Return an element named `name`, without attributes.
+    pub fn element(name: &str) -> XmlNode {
+        XmlNode::Element {
+            name: name.to_string(),
+            attributes: Vec::new(),
+        }
+    }
+
+    /// Return the name of the element, or `None` if the node is not an element.
+    pub fn name(&self) -> Option<&str> {
+        match self {
+            XmlNode::Element { name, .. } => Some(name),
+            _ => None,
+        }
+    }
+
+    /// Return the value of the attribute `name` of the element, if any.
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        match self {
+            XmlNode::Element { attributes, .. } => attributes
+                .iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.as_str()),
+            _ => None,
+        }
+    }
+}
+
+impl VecTree<XmlNode> {
+    /// Parse an XML document, the root element of the document becoming the root of the tree.
+    ///
+    /// Text made only of whitespace, such as indentation, is skipped. The XML declaration,
+    /// processing instructions and the document type definition are skipped as well, like
+    /// comments outside the root element.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the document is not well-formed XML, or if it does not have exactly one root
+    /// element.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::{VecTree, XmlNode};
+    ///
+    /// let tree = VecTree::from_xml_str(r#"<list kind="todo"><item>milk</item><item/></list>"#)
+    ///     .unwrap();
+    ///
+    /// let root = tree.get_root_index().unwrap();
+    /// assert_eq!(tree[root].attribute("kind"), Some("todo"));
+    ///
+    /// let item = tree.first_child(root).unwrap();
+    /// assert_eq!(tree[item].name(), Some("item"));
+    ///
+    /// let text = tree.first_child(item).unwrap();
+    /// assert_eq!(tree[text], XmlNode::Text("milk".to_string()));
+    /// ```
+    pub fn from_xml_str(s: &str) -> Result<VecTree<XmlNode>, XmlError> {
+        VecTree::read_xml(s.as_bytes())
+    }
+
+    /// Parse an XML document from `reader`, like `from_xml_str`.
+    pub fn read_xml<R: BufRead>(reader: R) -> Result<VecTree<XmlNode>, XmlError> {
+        let mut reader = Reader::from_reader(reader);
+        let decoder = reader.decoder();
+        let mut tree = VecTree::new();
+        let mut buf = Vec::new();
+
+        // The elements which have started but not ended yet, from the root to the deepest.
+        let mut open_elements: Vec<Index> = Vec::new();
+
+        loop {
+            let (node, is_empty) = match reader.read_event_into(&mut buf)? {
+                Event::Start(start) => (decode_element(&start, &reader)?, false),
+                Event::Empty(start) => (decode_element(&start, &reader)?, true),
+                Event::End(_) => {
+                    // The reader checks that the end tags match the start tags.
+                    open_elements.pop();
+                    buf.clear();
+                    continue;
+                }
+                Event::Text(text) => {
+                    let text = text.unescape()?;
+                    if text.trim().is_empty() {
+                        buf.clear();
+                        continue;
+                    }
+                    (XmlNode::Text(text.into_owned()), true)
+                }
+                Event::CData(cdata) => (XmlNode::CData(cdata.decode()?.into_owned()), true),
+                Event::Comment(comment) => {
+                    if open_elements.is_empty() {
+                        buf.clear();
+                        continue;
+                    }
+                    (
+                        XmlNode::Comment(decoder.decode(&comment)?.into_owned()),
+                        true,
+                    )
+                }
+                Event::Decl(_) | Event::PI(_) | Event::DocType(_) => {
+                    buf.clear();
+                    continue;
+                }
+                Event::Eof => break,
+            };
+            buf.clear();
+
+            let node_id = match open_elements.last() {
+                Some(&parent_id) => tree.insert(node, parent_id),
+                None if tree.get_root_index().is_some() => return Err(XmlError::SeveralRoots),
+                None if node.name().is_some() => tree.insert_root(node),
+                None => return Err(XmlError::SeveralRoots),
+            };
+            if !is_empty {
+                open_elements.push(node_id);
+            }
+        }
+
+        if let Some(&node_id) = open_elements.last() {
+            let name = tree[node_id].name().unwrap_or_default().to_string();
+            return Err(XmlError::Xml(IllFormedError::MissingEndTag(name).into()));
+        }
+        if tree.get_root_index().is_none() {
+            return Err(XmlError::NoRoot);
+        }
+
+        Ok(tree)
+    }
+
+    /// Write the tree as an XML document, without declaration, the root being the root element.
+    ///
+    /// Elements without children are written as empty elements, and no whitespace is added
+    /// between the nodes. A tree with no root gives an empty document.
+    ///
+    /// # Errors
+    ///
+    /// Fails if `writer` fails.
+    pub fn write_xml<W: Write>(&self, writer: W) -> io::Result<()> {
+        let root_id = match self.root_index {
+            Some(root_id) => root_id,
+            None => return Ok(()),
+        };
+
+        let mut writer = Writer::new(writer);
+
+        for edge in self.traverse(root_id) {
+            match edge {
+                NodeEdge::Start(node_id) => {
+                    let has_children = self.has_children(node_id);
+                    match self[node_id] {
+                        XmlNode::Element {
+                            ref name,
+                            ref attributes,
+                        } => {
+                            let start = BytesStart::new(name.as_str()).with_attributes(
+                                attributes
+                                    .iter()
+                                    .map(|(key, value)| (key.as_str(), value.as_str())),
+                            );
+                            if has_children {
+                                writer.write_event(Event::Start(start))?;
+                            } else {
+                                writer.write_event(Event::Empty(start))?;
+                            }
+                        }
+                        XmlNode::Text(ref text) => {
+                            writer.write_event(Event::Text(BytesText::new(text)))?;
+                        }
+                        XmlNode::CData(ref cdata) => {
+                            writer.write_event(Event::CData(BytesCData::new(cdata.as_str())))?;
+                        }
+                        XmlNode::Comment(ref comment) => {
+                            let comment = BytesText::from_escaped(comment.as_str());
+                            writer.write_event(Event::Comment(comment))?;
+                        }
+                    }
+                }
+                NodeEdge::End(node_id) => {
+                    if let XmlNode::Element { ref name, .. } = self[node_id] {
+                        if self.has_children(node_id) {
+                            writer.write_event(Event::End(BytesEnd::new(name.as_str())))?;
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Return the tree as an XML document, like `write_xml`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::{VecTree, XmlNode};
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(XmlNode::element("p"));
+    /// tree.insert(XmlNode::Text("1 < 2".to_string()), root);
+    /// tree.insert(XmlNode::element("br"), root);
+    ///
+    /// assert_eq!(tree.to_xml_string(), "<p>1 &lt; 2<br/></p>");
+    /// ```
+    pub fn to_xml_string(&self) -> String {
+        let mut xml = Vec::new();
+        // Writing to a `Vec` cannot fail.
+        self.write_xml(&mut xml).unwrap();
+        String::from_utf8(xml).expect("The XML writer only writes UTF-8")
+    }
+}
+
+fn decode_element<R>(start: &BytesStart<'_>, reader: &Reader<R>) -> Result<XmlNode, XmlError> {
+    let decoder = reader.decoder();
+    let name = decoder.decode(start.name().as_ref())?.into_owned();
+
+    let mut attributes = Vec::new();
+    for attribute in start.attributes() {
+        let attribute = attribute.map_err(quick_xml::Error::from)?;
+        let key = decoder.decode(attribute.key.as_ref())?.into_owned();
+        let value = attribute.decode_and_unescape_value(decoder)?.into_owned();
+        attributes.push((key, value));
+    }
+
+    Ok(XmlNode::Element { name, attributes })
+}
+
+/// An error returned when an XML document cannot be parsed into a `VecTree<XmlNode>`.
+#[derive(Debug)]
+pub enum XmlError {
+    /// The document is not well-formed XML, or could not be read.
+    Xml(quick_xml::Error),
+    /// The document has no root element.
+    NoRoot,
+    /// The document has content outside its root element, such as another element or text.
+    SeveralRoots,
+}
+
+impl From<quick_xml::Error> for XmlError {
+    fn from(error: quick_xml::Error) -> XmlError {
+        XmlError::Xml(error)
+    }
+}
+
+impl From<quick_xml::encoding::EncodingError> for XmlError {
+    fn from(error: quick_xml::encoding::EncodingError) -> XmlError {
+        XmlError::Xml(error.into())
+    }
+}
+
+impl fmt::Display for XmlError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            XmlError::Xml(error) => error.fmt(f),
+            XmlError::NoRoot => f.write_str("the XML document has no root element"),
+            XmlError::SeveralRoots => {
+                f.write_str("the XML document has content outside its root element")
+            }
+        }
+    }
+}
+
+impl Error for XmlError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            XmlError::Xml(error) => Some(error),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,242 @@
+use super::persistent::PersistentNode;
+use super::{PersistentVecTree, VecTree};
+
+use std::fmt;
+use std::sync::Arc;
+
+impl<T> VecTree<T> {
+    /// Consume the tree and return a zipper focused on its root, or `None` if the tree has no
+    /// root.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_tree::VecTree;
+    ///
+    /// let mut tree = VecTree::new();
+    /// let root = tree.insert_root(0);
+    /// tree.insert(1, root);
+    ///
+    /// let before = tree.into_zipper().unwrap();
+    /// let after = before.first_child().unwrap().set(10).root();
+    ///
+    /// // The original version is left untouched.
+    /// assert_eq!(*before.first_child().unwrap().value(), 1);
+    /// assert_eq!(*after.first_child().unwrap().value(), 10);
+    /// ```
+    pub fn into_zipper(self) -> Option<Zipper<T>> {
+        Some(Zipper {
+            focus: PersistentVecTree::from(self).root?,
+            breadcrumbs: None,
+        })
+    }
+}
+
+/// An immutable handle to a tree focused on one of its nodes.
+///
+/// The zipper keeps the ancestors of the focused node, so moving the focus to a neighbouring node
+/// takes constant time and returns a new zipper sharing the same tree. Editing returns a new
+/// zipper over a new version of the tree, leaving every other zipper unchanged: the edited node
+/// is copied, and its ancestors are only copied as the focus moves back up through them, every
+/// other subtree stays shared between the versions. Zippers can be handed to other threads, which
+/// never observe a partial edit.
+pub struct Zipper<T> {
+    focus: Arc<PersistentNode<T>>,
+    breadcrumbs: Option<Arc<Breadcrumb<T>>>,
+}
+
+// The parent of a node on the path from the root to the focus, as it was when the focus moved
+// down from it, with the position of the node among its children.
+struct Breadcrumb<T> {
+    parent: Arc<PersistentNode<T>>,
+    position: usize,
+    up: Option<Arc<Breadcrumb<T>>>,
+}
+
+impl<T> Drop for Breadcrumb<T> {
+    fn drop(&mut self) {
+        // Unlink the breadcrumbs only owned by this one before dropping them, so the breadcrumbs
+        // of a deep focus are dropped without recursing.
+        let mut up = self.up.take();
+        while let Some(breadcrumb) = up {
+            up = match Arc::try_unwrap(breadcrumb) {
+                Ok(mut breadcrumb) => breadcrumb.up.take(),
+                Err(_) => None,
+            };
+        }
+    }
+}
+
+impl<T> Clone for Zipper<T> {
+    fn clone(&self) -> Self {
+        Zipper {
+            focus: self.focus.clone(),
+            breadcrumbs: self.breadcrumbs.clone(),
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Zipper<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Zipper")
+            .field("path", &self.path())
+            .field("value", self.value())
+            .finish()
+    }
+}
+
+impl<T> Zipper<T> {
+    /// Return the path from the root to the focused node, as in `PersistentVecTree`.
+    pub fn path(&self) -> Vec<usize> {
+        let mut path = Vec::new();
+        let mut breadcrumbs = &self.breadcrumbs;
+        while let Some(breadcrumb) = breadcrumbs {
+            path.push(breadcrumb.position);
+            breadcrumbs = &breadcrumb.up;
+        }
+
+        path.reverse();
+        path
+    }
+
+    /// Return a shared reference to the data of the focused node.
+    pub fn value(&self) -> &T {
+        &self.focus.data
+    }
+
+    /// Return a zipper focused on the first child of the focused node, if any.
+    pub fn first_child(&self) -> Option<Zipper<T>> {
+        self.child(0)
+    }
+
+    /// Return a zipper focused on the last child of the focused node, if any.
+    pub fn last_child(&self) -> Option<Zipper<T>> {
+        self.child(self.focus.children.len().checked_sub(1)?)
+    }
+
+    fn child(&self, position: usize) -> Option<Zipper<T>> {
+        Some(Zipper {
+            focus: self.focus.children.get(position)?.clone(),
+            breadcrumbs: Some(Arc::new(Breadcrumb {
+                parent: self.focus.clone(),
+                position,
+                up: self.breadcrumbs.clone(),
+            })),
+        })
+    }
+}
+
+impl<T: Clone> Zipper<T> {
+    /// Return the version of the tree this zipper sees.
+    ///
+    /// This moves up to the root, copying the ancestors of the edited nodes.
+    pub fn tree(&self) -> PersistentVecTree<T> {
+        PersistentVecTree {
+            root: Some(self.root().focus),
+        }
+    }
+
+    /// Return a zipper focused on the root of the tree.
+    pub fn root(&self) -> Zipper<T> {
+        let mut zipper = self.clone();
+        while let Some(parent) = zipper.parent() {
+            zipper = parent;
+        }
+        zipper
+    }
+
+    /// Return a zipper focused on the parent of the focused node, or `None` if it is the root.
+    pub fn parent(&self) -> Option<Zipper<T>> {
+        let breadcrumb = self.breadcrumbs.as_ref()?;
+        Some(Zipper {
+            focus: self.updated_parent(breadcrumb),
+            breadcrumbs: breadcrumb.up.clone(),
+        })
+    }
+
+    /// Return a zipper focused on the next sibling of the focused node, if any.
+    pub fn next_sibling(&self) -> Option<Zipper<T>> {
+        self.sibling(|position| position.checked_add(1))
+    }
+
+    /// Return a zipper focused on the previous sibling of the focused node, if any.
+    pub fn previous_sibling(&self) -> Option<Zipper<T>> {
+        self.sibling(|position| position.checked_sub(1))
+    }
+
+    fn sibling<F>(&self, f: F) -> Option<Zipper<T>>
+    where
+        F: FnOnce(usize) -> Option<usize>,
+    {
+        let breadcrumb = self.breadcrumbs.as_ref()?;
+        let position = f(breadcrumb.position)?;
+        let sibling = breadcrumb.parent.children.get(position)?.clone();
+
+        Some(Zipper {
+            focus: sibling,
+            breadcrumbs: Some(Arc::new(Breadcrumb {
+                parent: self.updated_parent(breadcrumb),
+                position,
+                up: breadcrumb.up.clone(),
+            })),
+        })
+    }
+
+    // Return the parent of the focused node, copied with the focused node in place of its
+    // previous version if it was edited.
+    fn updated_parent(&self, breadcrumb: &Breadcrumb<T>) -> Arc<PersistentNode<T>> {
+        let mut parent = breadcrumb.parent.clone();
+        if !Arc::ptr_eq(&parent.children[breadcrumb.position], &self.focus) {
+            Arc::make_mut(&mut parent).children[breadcrumb.position] = self.focus.clone();
+        }
+        parent
+    }
+
+    /// Return a zipper over a version of the tree where the data of the focused node is replaced
+    /// by `data`.
+    pub fn set(self, data: T) -> Zipper<T> {
+        self.modify(|value| *value = data)
+    }
+
+    /// Return a zipper over a version of the tree where the data of the focused node is modified
+    /// by `f`.
+    pub fn modify<F>(mut self, f: F) -> Zipper<T>
+    where
+        F: FnOnce(&mut T),
+    {
+        f(&mut Arc::make_mut(&mut self.focus).data);
+        self
+    }
+
+    /// Return a zipper focused on a new last child of the focused node, holding `data`.
+    pub fn insert_child(mut self, data: T) -> Zipper<T> {
+        let position = self.focus.children.len();
+        Arc::make_mut(&mut self.focus)
+            .children
+            .push(Arc::new(PersistentNode::new(data)));
+        self.child(position).unwrap()
+    }
+
+    /// Return a zipper focused on the parent of the focused node, over a version of the tree
+    /// where the focused node and its descendants are removed.
+    ///
+    /// Returns `None` if the focused node is the root.
+    pub fn remove(self) -> Option<Zipper<T>> {
+        let breadcrumb = self.breadcrumbs?;
+        let mut parent = breadcrumb.parent.clone();
+        Arc::make_mut(&mut parent)
+            .children
+            .remove(breadcrumb.position);
+
+        Some(Zipper {
+            focus: parent,
+            breadcrumbs: breadcrumb.up.clone(),
+        })
+    }
+
+    /// Consume the zipper and return a `VecTree` with the content of the version of the tree it
+    /// sees.
+    pub fn into_tree(self) -> VecTree<T> {
+        self.tree().to_vec_tree()
+    }
+}
@@ -1,5 +1,8 @@
 extern crate vec_tree;
-use vec_tree::VecTree;
+use vec_tree::{
+    AncestorTable, AugmentedVecTree, EulerTourVecTree, IndexedVecTree, NodeEdge, NodeEdgeWithDepth,
+    Query, TreeBuilder, TreeEvent, VecTree,
+};
 
 #[test]
 fn try_insert_root() {
@@ -84,7 +87,7 @@ fn try_insert_when_full() {
 fn insert_many_and_cause_doubling() {
     let mut tree = VecTree::new();
 
-    let root = tree.try_insert_root(0).unwrap();
+    let root = tree.insert_root(0);
 
     let indices: Vec<_> = (0..1000).map(|i| tree.insert(i * i, root)).collect();
     for (i, idx) in indices.iter().cloned().enumerate() {
@@ -169,7 +172,7 @@ fn check_remove_with_one_child() {
         tree.children(root)
             .map(|node_id| tree[node_id])
             .collect::<Vec<_>>(),
-        []
+        Vec::<usize>::new()
     );
 
     let child2 = tree.try_insert(2, root).unwrap();
@@ -187,7 +190,7 @@ fn check_remove_with_one_child() {
         tree.children(root)
             .map(|node_id| tree[node_id])
             .collect::<Vec<_>>(),
-        []
+        Vec::<usize>::new()
     );
 }
 
@@ -250,14 +253,14 @@ fn add_children_and_iterate_over_it() {
         tree.children(child_node_1)
             .map(|node_id| tree[node_id])
             .collect::<Vec<_>>(),
-        []
+        Vec::<i32>::new()
     );
 
     assert_eq!(
         tree.children(child_node_2)
             .map(|node_id| tree[node_id])
             .collect::<Vec<_>>(),
-        []
+        Vec::<i32>::new()
     );
 
     assert_eq!(
@@ -506,3 +509,4100 @@ fn move_a_node() {
 
     assert_eq!(descendants, [0, 2, 1]);
 }
+
+#[test]
+fn get_relationships() {
+    let mut tree = VecTree::new();
+
+    let root_node = tree.insert_root(0);
+    let child_node_1 = tree.insert(1, root_node);
+    let child_node_2 = tree.insert(2, root_node);
+    let child_node_3 = tree.insert(3, root_node);
+
+    assert_eq!(tree.first_child(root_node), Some(child_node_1));
+    assert_eq!(tree.last_child(root_node), Some(child_node_3));
+    assert_eq!(tree.first_child(child_node_1), None);
+    assert_eq!(tree.last_child(child_node_1), None);
+
+    assert_eq!(tree.next_sibling(child_node_1), Some(child_node_2));
+    assert_eq!(tree.next_sibling(child_node_3), None);
+    assert_eq!(tree.previous_sibling(child_node_2), Some(child_node_1));
+    assert_eq!(tree.previous_sibling(child_node_1), None);
+
+    tree.remove(child_node_2);
+    assert_eq!(tree.next_sibling(child_node_1), Some(child_node_3));
+    assert_eq!(tree.previous_sibling(child_node_3), Some(child_node_1));
+    assert_eq!(tree.next_sibling(child_node_2), None);
+}
+
+#[test]
+fn check_node_predicates() {
+    let mut tree = VecTree::new();
+
+    let root_node = tree.insert_root(0);
+    let child_node = tree.insert(1, root_node);
+
+    assert!(tree.is_root(root_node));
+    assert!(!tree.is_root(child_node));
+    assert!(tree.has_children(root_node));
+    assert!(!tree.has_children(child_node));
+    assert!(!tree.is_leaf(root_node));
+    assert!(tree.is_leaf(child_node));
+
+    tree.remove(child_node);
+    assert!(tree.is_leaf(root_node));
+    assert!(!tree.has_children(root_node));
+    assert!(!tree.is_leaf(child_node));
+
+    tree.remove(root_node);
+    assert!(!tree.is_root(root_node));
+}
+
+#[test]
+fn count_children() {
+    let mut tree = VecTree::new();
+
+    let root_node = tree.insert_root(0);
+    let child_node_1 = tree.insert(1, root_node);
+    let child_node_2 = tree.insert(2, root_node);
+    let _grandchild = tree.insert(3, child_node_1);
+
+    assert_eq!(tree.child_count(root_node), 2);
+    assert_eq!(tree.child_count(child_node_1), 1);
+    assert_eq!(tree.child_count(child_node_2), 0);
+
+    tree.remove(child_node_1);
+    assert_eq!(tree.child_count(root_node), 1);
+}
+
+#[test]
+fn get_nth_child() {
+    let mut tree = VecTree::new();
+
+    let root_node = tree.insert_root(0);
+    let child_node_1 = tree.insert(1, root_node);
+    let child_node_2 = tree.insert(2, root_node);
+    let child_node_3 = tree.insert(3, root_node);
+
+    assert_eq!(tree.nth_child(root_node, 0), Some(child_node_1));
+    assert_eq!(tree.nth_child(root_node, 1), Some(child_node_2));
+    assert_eq!(tree.nth_child(root_node, 2), Some(child_node_3));
+    assert_eq!(tree.nth_child(root_node, 3), None);
+    assert_eq!(tree.nth_child(child_node_1, 0), None);
+
+    tree.remove(child_node_2);
+    assert_eq!(tree.nth_child(root_node, 1), Some(child_node_3));
+    assert_eq!(tree.nth_child(child_node_2, 0), None);
+}
+
+#[test]
+fn get_sibling_position() {
+    let mut tree = VecTree::new();
+
+    let root_node = tree.insert_root(0);
+    let child_node_1 = tree.insert(1, root_node);
+    let child_node_2 = tree.insert(2, root_node);
+    let child_node_3 = tree.insert(3, root_node);
+
+    assert_eq!(tree.sibling_position(root_node), None);
+    assert_eq!(tree.sibling_position(child_node_1), Some(0));
+    assert_eq!(tree.sibling_position(child_node_2), Some(1));
+    assert_eq!(tree.sibling_position(child_node_3), Some(2));
+
+    tree.remove(child_node_1);
+    assert_eq!(tree.sibling_position(child_node_1), None);
+    assert_eq!(tree.sibling_position(child_node_3), Some(1));
+}
+
+#[test]
+fn get_depth() {
+    let mut tree = VecTree::new();
+
+    let root_node = tree.insert_root(0);
+    let child_node_1 = tree.insert(1, root_node);
+    let child_node_2 = tree.insert(2, root_node);
+    let grandchild = tree.insert(3, child_node_2);
+
+    assert_eq!(tree.depth(root_node), Some(0));
+    assert_eq!(tree.depth(child_node_1), Some(1));
+    assert_eq!(tree.depth(child_node_2), Some(1));
+    assert_eq!(tree.depth(grandchild), Some(2));
+
+    tree.append_child(child_node_1, child_node_2);
+    assert_eq!(tree.depth(grandchild), Some(3));
+
+    tree.remove(child_node_1);
+    assert_eq!(tree.depth(grandchild), None);
+}
+
+#[test]
+fn get_height() {
+    let mut tree = VecTree::new();
+
+    // 0-1-3-4
+    // `-2
+    let root_node = tree.insert_root(0);
+    let node_1 = tree.insert(1, root_node);
+    let node_2 = tree.insert(2, root_node);
+    let node_3 = tree.insert(3, node_1);
+    let node_4 = tree.insert(4, node_3);
+
+    assert_eq!(tree.height(root_node), Some(3));
+    assert_eq!(tree.height(node_1), Some(2));
+    assert_eq!(tree.height(node_2), Some(0));
+    assert_eq!(tree.height(node_4), Some(0));
+
+    tree.remove(node_3);
+    assert_eq!(tree.height(root_node), Some(1));
+    assert_eq!(tree.height(node_4), None);
+}
+
+#[test]
+fn get_subtree_size() {
+    let mut tree = VecTree::new();
+
+    // 0-1-3-4
+    // `-2
+    let root_node = tree.insert_root(0);
+    let node_1 = tree.insert(1, root_node);
+    let node_2 = tree.insert(2, root_node);
+    let node_3 = tree.insert(3, node_1);
+    let _node_4 = tree.insert(4, node_3);
+
+    assert_eq!(tree.subtree_size(root_node), 5);
+    assert_eq!(tree.subtree_size(node_1), 3);
+    assert_eq!(tree.subtree_size(node_2), 1);
+
+    tree.remove(node_3);
+    assert_eq!(tree.subtree_size(root_node), 3);
+}
+
+#[test]
+fn get_max_depth_and_leaf_count() {
+    let mut tree = VecTree::new();
+
+    assert_eq!(tree.max_depth(), None);
+    assert_eq!(tree.leaf_count(), 0);
+
+    // 0-1-3-4
+    // `-2
+    let root_node = tree.insert_root(0);
+
+    assert_eq!(tree.max_depth(), Some(0));
+    assert_eq!(tree.leaf_count(), 1);
+
+    let node_1 = tree.insert(1, root_node);
+    let _node_2 = tree.insert(2, root_node);
+    let node_3 = tree.insert(3, node_1);
+    let _node_4 = tree.insert(4, node_3);
+
+    assert_eq!(tree.max_depth(), Some(3));
+    assert_eq!(tree.leaf_count(), 2);
+
+    tree.remove(node_1);
+    assert_eq!(tree.max_depth(), Some(1));
+    assert_eq!(tree.leaf_count(), 1);
+}
+
+#[test]
+fn get_tree_stats() {
+    let mut tree = VecTree::new();
+
+    let stats = tree.stats();
+    assert_eq!(stats.node_count, 0);
+    assert_eq!(stats.leaf_count, 0);
+    assert!(stats.depth_histogram.is_empty());
+    assert!(stats.arity_histogram.is_empty());
+
+    // 0-1-4-6
+    // | `-5
+    // `-2
+    // `-3
+    let root_node = tree.insert_root(0);
+    let node_1 = tree.insert(1, root_node);
+    let _node_2 = tree.insert(2, root_node);
+    let _node_3 = tree.insert(3, root_node);
+    let node_4 = tree.insert(4, node_1);
+    let _node_5 = tree.insert(5, node_1);
+    let _node_6 = tree.insert(6, node_4);
+
+    let stats = tree.stats();
+    assert_eq!(stats.node_count, 7);
+    assert_eq!(stats.leaf_count, 4);
+    assert_eq!(stats.max_depth, 3);
+    assert_eq!(stats.average_depth, 10.0 / 7.0);
+    assert_eq!(stats.depth_histogram, [1, 3, 2, 1]);
+    assert_eq!(stats.arity_histogram, [4, 1, 1, 1]);
+}
+
+#[test]
+fn get_lowest_common_ancestor() {
+    let mut tree = VecTree::new();
+
+    // 0-1-4-6
+    // | `-5
+    // `-2
+    // `-3
+    let root_node = tree.insert_root(0);
+    let node_1 = tree.insert(1, root_node);
+    let node_2 = tree.insert(2, root_node);
+    let node_3 = tree.insert(3, root_node);
+    let node_4 = tree.insert(4, node_1);
+    let node_5 = tree.insert(5, node_1);
+    let node_6 = tree.insert(6, node_4);
+
+    assert_eq!(tree.lca(node_6, node_5), Some(node_1));
+    assert_eq!(tree.lca(node_5, node_6), Some(node_1));
+    assert_eq!(tree.lca(node_6, node_3), Some(root_node));
+    assert_eq!(tree.lca(node_2, node_3), Some(root_node));
+    assert_eq!(tree.lca(node_6, node_1), Some(node_1));
+    assert_eq!(tree.lca(node_4, node_4), Some(node_4));
+    assert_eq!(tree.lca(root_node, node_6), Some(root_node));
+
+    tree.remove(node_4);
+    assert_eq!(tree.lca(node_6, node_5), None);
+    assert_eq!(tree.lca(node_5, node_6), None);
+}
+
+#[test]
+fn check_ancestor_and_descendant_relationships() {
+    let mut tree = VecTree::new();
+
+    // 0-1-3
+    // `-2
+    let root_node = tree.insert_root(0);
+    let node_1 = tree.insert(1, root_node);
+    let node_2 = tree.insert(2, root_node);
+    let node_3 = tree.insert(3, node_1);
+
+    assert!(tree.is_ancestor_of(root_node, node_3));
+    assert!(tree.is_ancestor_of(node_1, node_3));
+    assert!(!tree.is_ancestor_of(node_2, node_3));
+    assert!(!tree.is_ancestor_of(node_3, node_1));
+    assert!(!tree.is_ancestor_of(node_1, node_1));
+
+    assert!(tree.is_descendant_of(node_3, root_node));
+    assert!(tree.is_descendant_of(node_3, node_1));
+    assert!(!tree.is_descendant_of(node_1, node_3));
+    assert!(!tree.is_descendant_of(node_1, node_1));
+
+    tree.remove(node_3);
+    assert!(!tree.is_ancestor_of(node_1, node_3));
+    assert!(!tree.is_descendant_of(node_3, node_1));
+}
+
+#[test]
+fn get_distance() {
+    let mut tree = VecTree::new();
+
+    // 0-1-4-6
+    // | `-5
+    // `-2
+    let root_node = tree.insert_root(0);
+    let node_1 = tree.insert(1, root_node);
+    let node_2 = tree.insert(2, root_node);
+    let node_4 = tree.insert(4, node_1);
+    let node_5 = tree.insert(5, node_1);
+    let node_6 = tree.insert(6, node_4);
+
+    assert_eq!(tree.distance(node_6, node_5), Some(3));
+    assert_eq!(tree.distance(node_6, node_2), Some(4));
+    assert_eq!(tree.distance(node_2, node_6), Some(4));
+    assert_eq!(tree.distance(root_node, node_6), Some(3));
+    assert_eq!(tree.distance(node_4, node_4), Some(0));
+
+    tree.remove(node_4);
+    assert_eq!(tree.distance(node_6, node_5), None);
+}
+
+#[test]
+fn iterate_over_path_between() {
+    let mut tree = VecTree::new();
+
+    // 0-1-4-6
+    // | `-5
+    // `-2
+    let root_node = tree.insert_root(0);
+    let node_1 = tree.insert(1, root_node);
+    let node_2 = tree.insert(2, root_node);
+    let node_4 = tree.insert(4, node_1);
+    let node_5 = tree.insert(5, node_1);
+    let node_6 = tree.insert(6, node_4);
+
+    let path = |a, b| {
+        tree.path_between(a, b)
+            .unwrap()
+            .map(|node_id| tree[node_id])
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(path(node_6, node_5), [6, 4, 1, 5]);
+    assert_eq!(path(node_5, node_6), [5, 1, 4, 6]);
+    assert_eq!(path(node_6, node_2), [6, 4, 1, 0, 2]);
+    assert_eq!(path(root_node, node_6), [0, 1, 4, 6]);
+    assert_eq!(path(node_6, root_node), [6, 4, 1, 0]);
+    assert_eq!(path(node_4, node_4), [4]);
+
+    tree.remove(node_4);
+    assert!(tree.path_between(node_6, node_5).is_none());
+}
+
+#[test]
+fn iterate_over_path_from_root() {
+    let mut tree = VecTree::new();
+
+    let root_node = tree.insert_root(1);
+    let child_node_1 = tree.insert(2, root_node);
+    let child_node_2 = tree.insert(3, root_node);
+    let grandchild = tree.insert(5, child_node_2);
+
+    assert_eq!(
+        tree.path_from_root(root_node)
+            .map(|node_id| tree[node_id])
+            .collect::<Vec<_>>(),
+        [1]
+    );
+
+    assert_eq!(
+        tree.path_from_root(child_node_1)
+            .map(|node_id| tree[node_id])
+            .collect::<Vec<_>>(),
+        [1, 2]
+    );
+
+    assert_eq!(
+        tree.path_from_root(grandchild)
+            .map(|node_id| tree[node_id])
+            .collect::<Vec<_>>(),
+        [1, 3, 5]
+    );
+
+    tree.remove(child_node_2);
+    assert_eq!(tree.path_from_root(grandchild).count(), 0);
+}
+
+#[test]
+fn get_by_path_and_path_of() {
+    let mut tree = VecTree::new();
+
+    assert_eq!(tree.get_by_path(&[]), None);
+
+    // 0-1-4-6
+    // | `-5
+    // `-2
+    let root_node = tree.insert_root(0);
+    let node_1 = tree.insert(1, root_node);
+    let node_2 = tree.insert(2, root_node);
+    let node_4 = tree.insert(4, node_1);
+    let node_5 = tree.insert(5, node_1);
+    let node_6 = tree.insert(6, node_4);
+
+    assert_eq!(tree.get_by_path(&[]), Some(root_node));
+    assert_eq!(tree.get_by_path(&[1]), Some(node_2));
+    assert_eq!(tree.get_by_path(&[0, 1]), Some(node_5));
+    assert_eq!(tree.get_by_path(&[0, 0, 0]), Some(node_6));
+    assert_eq!(tree.get_by_path(&[2]), None);
+    assert_eq!(tree.get_by_path(&[1, 0]), None);
+
+    for node_id in tree.descendants(root_node) {
+        let path = tree.path_of(node_id).unwrap();
+        assert_eq!(tree.get_by_path(&path), Some(node_id));
+    }
+
+    tree.remove(node_4);
+    assert_eq!(tree.path_of(node_6), None);
+    assert_eq!(tree.path_of(node_5), Some(vec![0, 0]));
+}
+
+#[test]
+fn find_descendants_matching_a_predicate() {
+    let mut tree = VecTree::new();
+
+    // 0-1-4-6
+    // | `-5
+    // `-2
+    // `-3
+    let root_node = tree.insert_root(0);
+    let node_1 = tree.insert(1, root_node);
+    let _node_2 = tree.insert(2, root_node);
+    let node_3 = tree.insert(3, root_node);
+    let node_4 = tree.insert(4, node_1);
+    let _node_5 = tree.insert(5, node_1);
+    let _node_6 = tree.insert(6, node_4);
+
+    assert_eq!(
+        tree.find_descendants(root_node, |value| value % 2 == 0)
+            .map(|node_id| tree[node_id])
+            .collect::<Vec<_>>(),
+        [0, 4, 6, 2]
+    );
+
+    assert_eq!(
+        tree.find_descendants(node_1, |&value| value > 4)
+            .map(|node_id| tree[node_id])
+            .collect::<Vec<_>>(),
+        [6, 5]
+    );
+
+    assert_eq!(
+        tree.find_first(root_node, |&value| value % 2 == 1),
+        Some(node_1)
+    );
+    assert_eq!(
+        tree.find_first(root_node, |&value| value == 3),
+        Some(node_3)
+    );
+    assert_eq!(tree.find_first(node_4, |&value| value == 3), None);
+}
+
+#[test]
+fn find_nodes_by_key_in_indexed_tree() {
+    let mut tree = IndexedVecTree::new(|&(key, _): &(char, i32)| key);
+
+    // a-b-d
+    // `-c
+    let root_node = tree.insert_root(('a', 0));
+    let node_b = tree.insert(('b', 1), root_node);
+    let node_c = tree.insert(('c', 2), root_node);
+    let node_d = tree.insert(('d', 3), node_b);
+
+    assert_eq!(tree.find_by_key(&'a'), Some(root_node));
+    assert_eq!(tree.find_by_key(&'d'), Some(node_d));
+    assert_eq!(tree.find_by_key(&'z'), None);
+    assert_eq!(tree[node_c], ('c', 2));
+
+    assert_eq!(tree.modify(node_c, |data| data.0 = 'e'), Some(()));
+    assert_eq!(tree.find_by_key(&'c'), None);
+    assert_eq!(tree.find_by_key(&'e'), Some(node_c));
+
+    tree.append_child(node_c, node_b);
+    assert_eq!(tree.find_by_key(&'b'), Some(node_b));
+
+    assert_eq!(tree.remove(node_c), Some(('e', 2)));
+    assert_eq!(tree.find_by_key(&'e'), None);
+    assert_eq!(tree.find_by_key(&'b'), None);
+    assert_eq!(tree.find_by_key(&'d'), None);
+    assert_eq!(tree.modify(node_c, |data| data.0 = 'f'), None);
+    assert_eq!(tree.find_by_key(&'a'), Some(root_node));
+
+    tree.clear();
+    assert_eq!(tree.find_by_key(&'a'), None);
+}
+
+#[cfg(feature = "keyed-children")]
+#[test]
+fn find_children_by_key_in_keyed_tree() {
+    use vec_tree::KeyedVecTree;
+
+    let mut tree = KeyedVecTree::new(|&(key, _): &(char, i32)| key);
+
+    // a-b-d
+    // `-c
+    let root_node = tree.insert_root(('a', 0));
+    let node_b = tree.insert(('b', 1), root_node);
+    let node_c = tree.insert(('c', 2), root_node);
+    let node_d = tree.insert(('d', 3), node_b);
+
+    assert_eq!(tree.child_by_key(root_node, &'b'), Some(node_b));
+    assert_eq!(tree.child_by_key(root_node, &'c'), Some(node_c));
+    assert_eq!(tree.child_by_key(root_node, &'d'), None);
+    assert_eq!(tree.child_by_key(node_b, &'d'), Some(node_d));
+
+    assert_eq!(tree.modify(node_c, |data| data.0 = 'e'), Some(()));
+    assert_eq!(tree.child_by_key(root_node, &'c'), None);
+    assert_eq!(tree.child_by_key(root_node, &'e'), Some(node_c));
+
+    tree.append_child(node_c, node_b);
+    assert_eq!(tree.child_by_key(root_node, &'b'), None);
+    assert_eq!(tree.child_by_key(node_c, &'b'), Some(node_b));
+
+    assert_eq!(tree.remove(node_b), Some(('b', 1)));
+    assert_eq!(tree.child_by_key(node_c, &'b'), None);
+    assert_eq!(tree.child_by_key(node_b, &'d'), None);
+}
+
+#[test]
+fn insert_sorted_and_binary_search_children() {
+    let mut tree = VecTree::new();
+
+    let root_node = tree.insert_root((0, 'r'));
+    for &value in &[(5, 'a'), (1, 'b'), (3, 'c'), (5, 'd'), (0, 'e'), (9, 'f')] {
+        tree.insert_sorted(value, root_node, |a, b| a.0.cmp(&b.0));
+    }
+
+    assert_eq!(
+        tree.children(root_node)
+            .map(|node_id| tree[node_id].1)
+            .collect::<Vec<_>>(),
+        ['e', 'b', 'c', 'a', 'd', 'f']
+    );
+    assert_eq!(
+        tree.first_child(root_node).map(|node_id| tree[node_id].1),
+        Some('e')
+    );
+    assert_eq!(
+        tree.last_child(root_node).map(|node_id| tree[node_id].1),
+        Some('f')
+    );
+
+    let node_c = tree.binary_search_child(root_node, |value| value.0.cmp(&3));
+    assert_eq!(node_c.map(|node_id| tree[node_id].1), Ok('c'));
+    assert_eq!(
+        tree.binary_search_child(root_node, |value| value.0.cmp(&2)),
+        Err(2)
+    );
+    assert_eq!(
+        tree.binary_search_child(root_node, |value| value.0.cmp(&10)),
+        Err(6)
+    );
+
+    let node_c = node_c.unwrap();
+    let grandchild = tree.insert_sorted((7, 'g'), node_c, |a, b| a.0.cmp(&b.0));
+    assert_eq!(tree.parent(grandchild), Some(node_c));
+    assert_eq!(
+        tree.binary_search_child(node_c, |value| value.0.cmp(&7)),
+        Ok(grandchild)
+    );
+}
+
+#[test]
+fn insert_sorted_after_other_changes_to_children() {
+    let mut tree = VecTree::new();
+
+    let root_node = tree.insert_root(0);
+    let node_20 = tree.insert_sorted(20, root_node, |a, b| a.cmp(b));
+    let node_40 = tree.insert_sorted(40, root_node, |a, b| a.cmp(b));
+    let node_10 = tree.insert_sorted(10, root_node, |a, b| a.cmp(b));
+
+    // Changing the children by other means must not leave a stale order behind.
+    tree.remove(node_20);
+    tree.insert(50, root_node);
+    let node_30 = tree.insert_sorted(30, root_node, |a, b| a.cmp(b));
+    tree.append_child(node_10, node_40);
+
+    assert_eq!(
+        tree.children(root_node)
+            .map(|node_id| tree[node_id])
+            .collect::<Vec<_>>(),
+        [10, 30, 50]
+    );
+    assert_eq!(
+        tree.binary_search_child(root_node, |value| value.cmp(&30)),
+        Ok(node_30)
+    );
+    assert_eq!(
+        tree.binary_search_child(root_node, |value| value.cmp(&40)),
+        Err(2)
+    );
+    assert_eq!(
+        tree.binary_search_child(node_10, |value| value.cmp(&40)),
+        Ok(node_40)
+    );
+
+    tree.clear();
+    let root_node = tree.insert_root(0);
+    assert_eq!(
+        tree.binary_search_child(root_node, |value| value.cmp(&30)),
+        Err(0)
+    );
+}
+
+#[test]
+fn run_queries() {
+    let mut tree = VecTree::new();
+
+    // 0-1-4-6
+    // | `-5
+    // `-2-7
+    // `-3
+    let root_node = tree.insert_root(0);
+    let node_1 = tree.insert(1, root_node);
+    let node_2 = tree.insert(2, root_node);
+    let _node_3 = tree.insert(3, root_node);
+    let node_4 = tree.insert(4, node_1);
+    let _node_5 = tree.insert(5, node_1);
+    let node_6 = tree.insert(6, node_4);
+    let _node_7 = tree.insert(7, node_2);
+
+    let run = |query: Query<i32>, node_id| {
+        query
+            .run(&tree, node_id)
+            .map(|node_id| tree[node_id])
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(run(Query::new(), root_node), [0]);
+    assert_eq!(run(Query::new().children(), root_node), [1, 2, 3]);
+    assert_eq!(run(Query::new().descendants(), node_1), [4, 6, 5]);
+    assert_eq!(
+        run(Query::new().children().children(), root_node),
+        [4, 5, 7]
+    );
+    assert_eq!(run(Query::new().children().nth_child(1), root_node), [5]);
+    assert_eq!(
+        run(
+            Query::new().descendants().filter(|&value| value > 4),
+            root_node
+        ),
+        [6, 5, 7]
+    );
+    assert_eq!(
+        run(Query::new().descendants().ancestors(), node_1),
+        [1, 0, 4]
+    );
+    assert_eq!(run(Query::new().ancestors().parent(), node_6), [1, 0]);
+
+    tree.remove(node_4);
+    assert_eq!(Query::new().children().run(&tree, node_4).count(), 0);
+}
+
+#[test]
+fn iterate_over_descendants_until_depth() {
+    let mut tree = VecTree::new();
+
+    // 0-1-4-6
+    // | `-5
+    // `-2-7
+    // `-3
+    let root_node = tree.insert_root(0);
+    let node_1 = tree.insert(1, root_node);
+    let node_2 = tree.insert(2, root_node);
+    let _node_3 = tree.insert(3, root_node);
+    let node_4 = tree.insert(4, node_1);
+    let _node_5 = tree.insert(5, node_1);
+    let _node_6 = tree.insert(6, node_4);
+    let _node_7 = tree.insert(7, node_2);
+
+    let descendants = |max_depth| {
+        tree.descendants_until_depth(root_node, max_depth)
+            .map(|node| tree[node])
+            .collect::<Vec<i32>>()
+    };
+
+    assert_eq!(descendants(0), [0]);
+    assert_eq!(descendants(1), [0, 1, 2, 3]);
+    assert_eq!(descendants(2), [0, 1, 4, 5, 2, 7, 3]);
+    assert_eq!(descendants(3), [0, 1, 4, 6, 5, 2, 7, 3]);
+    assert_eq!(descendants(10), [0, 1, 4, 6, 5, 2, 7, 3]);
+
+    assert_eq!(
+        tree.descendants_until_depth(node_1, 1)
+            .map(|node| tree[node])
+            .collect::<Vec<i32>>(),
+        [1, 4, 5]
+    );
+}
+
+#[test]
+fn skip_subtrees_during_traversal() {
+    let mut tree = VecTree::new();
+
+    // 0-1-4-6
+    // | `-5
+    // `-2-7
+    // `-3
+    let root_node = tree.insert_root(0);
+    let node_1 = tree.insert(1, root_node);
+    let node_2 = tree.insert(2, root_node);
+    let _node_3 = tree.insert(3, root_node);
+    let node_4 = tree.insert(4, node_1);
+    let _node_5 = tree.insert(5, node_1);
+    let _node_6 = tree.insert(6, node_4);
+    let _node_7 = tree.insert(7, node_2);
+
+    let mut descendants = tree.descendants(root_node);
+    let mut values = Vec::new();
+    while let Some(node_id) = descendants.next() {
+        values.push(tree[node_id]);
+        if node_id == node_4 || node_id == node_2 {
+            descendants.skip_subtree();
+        }
+    }
+    assert_eq!(values, [0, 1, 4, 5, 2, 3]);
+
+    // Skipping a leaf or skipping twice has no effect.
+    let mut descendants = tree.descendants_with_depth(root_node);
+    let mut values = Vec::new();
+    while let Some((node_id, depth)) = descendants.next() {
+        values.push((tree[node_id], depth));
+        if tree[node_id] % 2 == 1 {
+            descendants.skip_subtree();
+            descendants.skip_subtree();
+        }
+    }
+    assert_eq!(values, [(0, 0), (1, 1), (2, 1), (7, 2), (3, 1)]);
+
+    let mut descendants = tree.descendants(root_node);
+    descendants.next();
+    descendants.skip_subtree();
+    assert_eq!(descendants.next(), None);
+
+    // Skipping before the traversal starts has no effect, even from a first child.
+    let mut traverse = tree.traverse(node_1);
+    traverse.skip_subtree();
+    assert_eq!(traverse.next(), Some(NodeEdge::Start(node_1)));
+
+    let mut descendants = tree.descendants(node_1);
+    descendants.skip_subtree();
+    assert_eq!(descendants.count(), 4);
+
+    let mut descendants = tree.descendants_with_depth(node_4);
+    descendants.skip_subtree();
+    assert_eq!(descendants.next(), Some((node_4, 0)));
+}
+
+#[test]
+fn iterate_over_following_and_preceding() {
+    let mut tree = VecTree::new();
+
+    // 0-1-4-6
+    // | `-5
+    // `-2-7
+    // `-3
+    let root_node = tree.insert_root(0);
+    let node_1 = tree.insert(1, root_node);
+    let node_2 = tree.insert(2, root_node);
+    let node_3 = tree.insert(3, root_node);
+    let node_4 = tree.insert(4, node_1);
+    let node_5 = tree.insert(5, node_1);
+    let node_6 = tree.insert(6, node_4);
+    let node_7 = tree.insert(7, node_2);
+
+    let following = |node_id| {
+        tree.following(node_id)
+            .map(|node_id| tree[node_id])
+            .collect::<Vec<_>>()
+    };
+    let preceding = |node_id| {
+        tree.preceding(node_id)
+            .map(|node_id| tree[node_id])
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(following(root_node), Vec::<i32>::new());
+    assert_eq!(following(node_1), [2, 7, 3]);
+    assert_eq!(following(node_6), [5, 2, 7, 3]);
+    assert_eq!(following(node_7), [3]);
+    assert_eq!(following(node_3), Vec::<i32>::new());
+
+    assert_eq!(preceding(root_node), Vec::<i32>::new());
+    assert_eq!(preceding(node_1), Vec::<i32>::new());
+    assert_eq!(preceding(node_5), [6, 4]);
+    assert_eq!(preceding(node_7), [5, 6, 4, 1]);
+    assert_eq!(preceding(node_3), [7, 2, 5, 6, 4, 1]);
+    assert_eq!(preceding(node_6), Vec::<i32>::new());
+
+    tree.remove(node_2);
+    assert_eq!(tree.following(node_2).count(), 0);
+    assert_eq!(tree.preceding(node_2).count(), 0);
+}
+
+#[test]
+fn step_in_preorder() {
+    let mut tree = VecTree::new();
+
+    // 0-1-4-6
+    // | `-5
+    // `-2-7
+    // `-3
+    let root_node = tree.insert_root(0);
+    let node_1 = tree.insert(1, root_node);
+    let node_2 = tree.insert(2, root_node);
+    let _node_3 = tree.insert(3, root_node);
+    let node_4 = tree.insert(4, node_1);
+    let _node_5 = tree.insert(5, node_1);
+    let _node_6 = tree.insert(6, node_4);
+    let _node_7 = tree.insert(7, node_2);
+
+    let mut forward = Vec::new();
+    let mut node_id = Some(root_node);
+    while let Some(current) = node_id {
+        forward.push(tree[current]);
+        node_id = tree.next_preorder(current);
+    }
+    assert_eq!(forward, [0, 1, 4, 6, 5, 2, 7, 3]);
+
+    let mut backward = Vec::new();
+    let mut node_id = tree.get_by_path(&[2]);
+    while let Some(current) = node_id {
+        backward.push(tree[current]);
+        node_id = tree.prev_preorder(current);
+    }
+    assert_eq!(backward, [3, 7, 2, 5, 6, 4, 1, 0]);
+
+    tree.remove(node_4);
+    assert_eq!(tree.next_preorder(node_4), None);
+    assert_eq!(tree.prev_preorder(node_4), None);
+}
+
+#[test]
+fn iterate_over_leaves() {
+    let mut tree = VecTree::new();
+
+    // 0-1-4-6
+    // | `-5
+    // `-2-7
+    // `-3
+    let root_node = tree.insert_root(0);
+    let node_1 = tree.insert(1, root_node);
+    let node_2 = tree.insert(2, root_node);
+    let node_3 = tree.insert(3, root_node);
+    let node_4 = tree.insert(4, node_1);
+    let _node_5 = tree.insert(5, node_1);
+    let _node_6 = tree.insert(6, node_4);
+    let _node_7 = tree.insert(7, node_2);
+
+    let leaves = |node_id| {
+        tree.leaves(node_id)
+            .map(|node_id| tree[node_id])
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(leaves(root_node), [6, 5, 7, 3]);
+    assert_eq!(leaves(node_1), [6, 5]);
+    assert_eq!(leaves(node_3), [3]);
+}
+
+#[test]
+fn iterate_breadth_first_with_depth() {
+    let mut tree = VecTree::new();
+
+    // 0-1-4-6
+    // | `-5
+    // `-2-7
+    // `-3
+    let root_node = tree.insert_root(0);
+    let node_1 = tree.insert(1, root_node);
+    let node_2 = tree.insert(2, root_node);
+    let _node_3 = tree.insert(3, root_node);
+    let node_4 = tree.insert(4, node_1);
+    let _node_5 = tree.insert(5, node_1);
+    let _node_6 = tree.insert(6, node_4);
+    let _node_7 = tree.insert(7, node_2);
+
+    let nodes = tree
+        .breadth_first_with_depth(root_node)
+        .map(|(node, depth)| (tree[node], depth))
+        .collect::<Vec<(i32, u32)>>();
+
+    let expected_result = [
+        (0, 0),
+        (1, 1),
+        (2, 1),
+        (3, 1),
+        (4, 2),
+        (5, 2),
+        (7, 2),
+        (6, 3),
+    ];
+
+    assert_eq!(nodes, expected_result);
+
+    let nodes = tree
+        .breadth_first_with_depth(node_1)
+        .map(|(node, depth)| (tree[node], depth))
+        .collect::<Vec<(i32, u32)>>();
+
+    assert_eq!(nodes, [(1, 0), (4, 1), (5, 1), (6, 2)]);
+}
+
+#[test]
+fn iterate_over_levels() {
+    let mut tree = VecTree::new();
+
+    // 0-1-4-6
+    // | `-5
+    // `-2-7
+    // `-3
+    let root_node = tree.insert_root(0);
+    let node_1 = tree.insert(1, root_node);
+    let node_2 = tree.insert(2, root_node);
+    let node_3 = tree.insert(3, root_node);
+    let node_4 = tree.insert(4, node_1);
+    let _node_5 = tree.insert(5, node_1);
+    let _node_6 = tree.insert(6, node_4);
+    let _node_7 = tree.insert(7, node_2);
+
+    let levels = |node_id| {
+        tree.levels(node_id)
+            .map(|level| level.iter().map(|&node| tree[node]).collect::<Vec<_>>())
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        levels(root_node),
+        [vec![0], vec![1, 2, 3], vec![4, 5, 7], vec![6]]
+    );
+    assert_eq!(levels(node_1), [vec![1], vec![4, 5], vec![6]]);
+    assert_eq!(levels(node_3), [vec![3]]);
+}
+
+#[test]
+fn iterate_with_iterative_deepening() {
+    let mut tree = VecTree::new();
+
+    // 0-1-4-6
+    // | `-5
+    // `-2-7
+    // `-3
+    let root_node = tree.insert_root(0);
+    let node_1 = tree.insert(1, root_node);
+    let node_2 = tree.insert(2, root_node);
+    let node_3 = tree.insert(3, root_node);
+    let node_4 = tree.insert(4, node_1);
+    let _node_5 = tree.insert(5, node_1);
+    let _node_6 = tree.insert(6, node_4);
+    let _node_7 = tree.insert(7, node_2);
+
+    let nodes = |node_id| {
+        tree.iddfs(node_id)
+            .map(|node_id| tree[node_id])
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(nodes(root_node), [0, 1, 2, 3, 4, 5, 7, 6]);
+    assert_eq!(nodes(node_1), [1, 4, 5, 6]);
+    assert_eq!(nodes(node_3), [3]);
+
+    let mut iter = tree.iddfs(root_node);
+    assert_eq!(iter.find(|&node_id| tree[node_id] > 3), Some(node_4));
+}
+
+#[test]
+fn traverse_edges() {
+    let mut tree = VecTree::new();
+
+    // 0-1-3
+    // `-2
+    let root_node = tree.insert_root(0);
+    let node_1 = tree.insert(1, root_node);
+    let node_2 = tree.insert(2, root_node);
+    let node_3 = tree.insert(3, node_1);
+
+    assert_eq!(
+        tree.traverse(root_node).collect::<Vec<_>>(),
+        [
+            NodeEdge::Start(root_node),
+            NodeEdge::Start(node_1),
+            NodeEdge::Start(node_3),
+            NodeEdge::End(node_3),
+            NodeEdge::End(node_1),
+            NodeEdge::Start(node_2),
+            NodeEdge::End(node_2),
+            NodeEdge::End(root_node),
+        ]
+    );
+
+    assert_eq!(
+        tree.traverse_with_depth(node_1).collect::<Vec<_>>(),
+        [
+            NodeEdgeWithDepth::Start(node_1, 0),
+            NodeEdgeWithDepth::Start(node_3, 1),
+            NodeEdgeWithDepth::End(node_3, 1),
+            NodeEdgeWithDepth::End(node_1, 0),
+        ]
+    );
+}
+
+#[test]
+fn build_tree_from_events() {
+    let mut builder = TreeBuilder::new();
+
+    assert_eq!(builder.end(), None);
+
+    let root_node = builder.start(0);
+    let node_1 = builder.start(1);
+    builder.start(3);
+    builder.end();
+    assert_eq!(builder.end(), Some(node_1));
+    builder.start(2);
+    assert_eq!(builder.open_count(), 2);
+    builder.end();
+    assert_eq!(builder.end(), Some(root_node));
+
+    let tree = builder.finish();
+    assert_eq!(tree.get_root_index(), Some(root_node));
+    assert_eq!(
+        tree.descendants_with_depth(root_node)
+            .map(|(node_id, depth)| (tree[node_id], depth))
+            .collect::<Vec<_>>(),
+        [(0, 0), (1, 1), (3, 2), (2, 1)]
+    );
+
+    // Rebuild the tree from its own traversal.
+    let copy = tree
+        .traverse(root_node)
+        .map(|edge| match edge {
+            NodeEdge::Start(node_id) => TreeEvent::Start(tree[node_id]),
+            NodeEdge::End(_) => TreeEvent::End,
+        })
+        .collect::<VecTree<_>>();
+    let copy_root = copy.get_root_index().unwrap();
+    assert_eq!(
+        copy.descendants(copy_root)
+            .map(|node_id| copy[node_id])
+            .collect::<Vec<_>>(),
+        [0, 1, 3, 2]
+    );
+}
+
+#[test]
+#[should_panic]
+fn build_tree_with_two_roots() {
+    let mut builder = TreeBuilder::new();
+    builder.start(0);
+    builder.end();
+    builder.start(1);
+}
+
+#[test]
+fn check_ancestors_with_euler_tour_intervals() {
+    let mut tree = EulerTourVecTree::new();
+
+    // 0-1-3
+    // `-2
+    let root_node = tree.insert_root(0);
+    let node_1 = tree.insert(1, root_node);
+    let node_2 = tree.insert(2, root_node);
+    let node_3 = tree.insert(3, node_1);
+
+    let check = |tree: &EulerTourVecTree<i32>| {
+        for a in tree.descendants(root_node) {
+            for b in tree.descendants(root_node) {
+                assert_eq!(
+                    tree.is_ancestor_of(a, b),
+                    VecTree::is_ancestor_of(tree, a, b)
+                );
+            }
+        }
+    };
+    check(&tree);
+
+    assert!(tree.is_ancestor_of(root_node, node_3));
+    assert!(tree.is_descendant_of(node_3, node_1));
+    assert!(!tree.is_ancestor_of(node_2, node_3));
+    assert!(tree.is_in_subtree(node_1, node_1));
+
+    tree.append_child(node_2, node_1);
+    check(&tree);
+    assert!(tree.is_ancestor_of(node_2, node_3));
+
+    // Exhaust the gaps to force renumbering.
+    let mut parent = node_3;
+    for i in 0..100 {
+        parent = tree.insert(10 + i, parent);
+    }
+    check(&tree);
+    assert!(tree.is_ancestor_of(node_2, parent));
+
+    tree[node_3] = 30;
+    assert_eq!(tree[node_3], 30);
+
+    tree.remove(node_1);
+    assert!(!tree.is_ancestor_of(node_2, node_3));
+    assert_eq!(tree.interval(parent), None);
+    check(&tree);
+}
+
+#[test]
+fn keep_euler_tour_intervals_consistent_when_appending_many_siblings() {
+    let mut tree = EulerTourVecTree::new();
+
+    let root_node = tree.insert_root(0);
+    let first_child = tree.insert(1, root_node);
+    for i in 0..20_000 {
+        tree.insert(i, root_node);
+    }
+    let mut parent = first_child;
+    for i in 0..1_000 {
+        parent = tree.insert(i, parent);
+        tree.insert(i, first_child);
+    }
+    let moved = tree.insert(0, parent);
+    tree.append_child(root_node, moved);
+
+    let mut last_number = None;
+    for edge in tree.traverse(root_node) {
+        let number = match edge {
+            NodeEdge::Start(node_id) => tree.interval(node_id).unwrap().0,
+            NodeEdge::End(node_id) => tree.interval(node_id).unwrap().1,
+        };
+        assert!(last_number < Some(number));
+        last_number = Some(number);
+    }
+    assert!(tree.is_ancestor_of(first_child, parent));
+    assert!(!tree.is_ancestor_of(first_child, moved));
+    assert!(tree.is_ancestor_of(root_node, moved));
+}
+
+#[test]
+fn get_lowest_common_ancestors_in_batch() {
+    let mut tree = VecTree::new();
+
+    assert_eq!(tree.lca_batch(&[]), []);
+
+    // 0-1-4-6
+    // | `-5
+    // `-2-7
+    // `-3
+    let root_node = tree.insert_root(0);
+    let node_1 = tree.insert(1, root_node);
+    let node_2 = tree.insert(2, root_node);
+    let node_3 = tree.insert(3, root_node);
+    let node_4 = tree.insert(4, node_1);
+    let node_5 = tree.insert(5, node_1);
+    let node_6 = tree.insert(6, node_4);
+    let node_7 = tree.insert(7, node_2);
+
+    let nodes = [
+        root_node, node_1, node_2, node_3, node_4, node_5, node_6, node_7,
+    ];
+    let queries = nodes
+        .iter()
+        .flat_map(|&a| nodes.iter().map(move |&b| (a, b)))
+        .collect::<Vec<_>>();
+
+    let expected = queries
+        .iter()
+        .map(|&(a, b)| tree.lca(a, b))
+        .collect::<Vec<_>>();
+    assert_eq!(tree.lca_batch(&queries), expected);
+
+    tree.remove(node_4);
+    assert_eq!(
+        tree.lca_batch(&[(node_6, node_5), (node_5, node_7)]),
+        [None, Some(root_node)]
+    );
+}
+
+#[test]
+fn query_ancestor_table() {
+    let mut tree = VecTree::new();
+
+    let empty_table = AncestorTable::new(&tree);
+
+    let root_node = tree.insert_root(0);
+    assert_eq!(empty_table.depth(root_node), None);
+
+    // A long chain with a few branches.
+    let mut nodes = vec![root_node];
+    for i in 1..40 {
+        let parent = nodes[(i - 1) / 2 * 2];
+        nodes.push(tree.insert(i, parent));
+    }
+
+    let table = AncestorTable::new(&tree);
+
+    for &a in &nodes {
+        assert_eq!(table.depth(a), tree.depth(a));
+
+        let ancestors = tree.ancestors(a).collect::<Vec<_>>();
+        for k in 0..=ancestors.len() as u32 {
+            assert_eq!(table.kth_ancestor(a, k), ancestors.get(k as usize).cloned());
+        }
+
+        for &b in &nodes {
+            assert_eq!(table.lca(a, b), tree.lca(a, b));
+        }
+    }
+
+    let new_node = tree.insert(100, root_node);
+    assert_eq!(table.lca(new_node, root_node), None);
+}
+
+#[test]
+fn maintain_aggregates_in_augmented_tree() {
+    // Maintain the sum and the height of each subtree.
+    let mut tree = AugmentedVecTree::new(|&value: &u32, children: &[&(u32, u32)]| {
+        (
+            value + children.iter().map(|child| child.0).sum::<u32>(),
+            children.iter().map(|child| child.1 + 1).max().unwrap_or(0),
+        )
+    });
+
+    // 1-2-4
+    // `-3
+    let root_node = tree.insert_root(1);
+    let node_2 = tree.insert(2, root_node);
+    let node_3 = tree.insert(3, root_node);
+    let node_4 = tree.insert(4, node_2);
+
+    assert_eq!(tree.aggregate(root_node), Some(&(10, 2)));
+    assert_eq!(tree.aggregate(node_2), Some(&(6, 1)));
+    assert_eq!(tree.aggregate(node_3), Some(&(3, 0)));
+
+    tree.modify(node_4, |value| *value = 40);
+    assert_eq!(tree.aggregate(root_node), Some(&(46, 2)));
+
+    tree.append_child(node_4, node_3);
+    assert_eq!(tree.aggregate(root_node), Some(&(46, 3)));
+    assert_eq!(tree.aggregate(node_2), Some(&(45, 2)));
+
+    tree.remove(node_4);
+    assert_eq!(tree.aggregate(root_node), Some(&(3, 1)));
+    assert_eq!(tree.aggregate(node_3), None);
+}
+
+#[test]
+fn iterate_over_values() {
+    let mut tree = VecTree::new();
+
+    // 0-1-3
+    // `-2
+    let root_node = tree.insert_root(0);
+    let node_1 = tree.insert(1, root_node);
+    let node_2 = tree.insert(2, root_node);
+    let node_3 = tree.insert(3, node_1);
+
+    assert_eq!(
+        tree.children_values(root_node).collect::<Vec<_>>(),
+        [(node_1, &1), (node_2, &2)]
+    );
+    assert_eq!(
+        tree.ancestors_values(node_3).collect::<Vec<_>>(),
+        [(node_3, &3), (node_1, &1), (root_node, &0)]
+    );
+    assert_eq!(
+        tree.descendants_values(root_node).collect::<Vec<_>>(),
+        [(root_node, &0), (node_1, &1), (node_3, &3), (node_2, &2)]
+    );
+}
+
+#[test]
+fn iterate_over_all_values() {
+    let mut tree = VecTree::new();
+
+    assert_eq!(tree.values().count(), 0);
+
+    let root_node = tree.insert_root(1);
+    let node_1 = tree.insert(2, root_node);
+    let _node_2 = tree.insert(3, root_node);
+    let _node_3 = tree.insert(4, node_1);
+
+    let mut values = tree.values().cloned().collect::<Vec<_>>();
+    values.sort();
+    assert_eq!(values, [1, 2, 3, 4]);
+
+    for value in tree.values_mut() {
+        *value += 10;
+    }
+
+    let mut values = tree.values().cloned().collect::<Vec<_>>();
+    values.sort();
+    assert_eq!(values, [11, 12, 13, 14]);
+
+    tree.remove(node_1);
+    assert_eq!(tree.values().sum::<i32>(), 24);
+}
+
+#[test]
+fn iterate_over_all_nodes() {
+    let mut tree = VecTree::new();
+
+    assert_eq!(tree.iter().count(), 0);
+
+    let root_node = tree.insert_root(1);
+    let node_1 = tree.insert(2, root_node);
+    let node_2 = tree.insert(3, root_node);
+    let node_3 = tree.insert(4, node_1);
+
+    let mut nodes = tree.iter().collect::<Vec<_>>();
+    nodes.sort_by_key(|&(_, value)| *value);
+    assert_eq!(
+        nodes,
+        [(root_node, &1), (node_1, &2), (node_2, &3), (node_3, &4)]
+    );
+
+    tree.remove(node_1);
+    let mut nodes = tree.iter().collect::<Vec<_>>();
+    nodes.sort_by_key(|&(_, value)| *value);
+    assert_eq!(nodes, [(root_node, &1), (node_2, &3)]);
+}
+
+#[test]
+fn mutate_all_nodes() {
+    let mut tree = VecTree::new();
+
+    let root_node = tree.insert_root(1);
+    let node_1 = tree.insert(2, root_node);
+    let node_2 = tree.insert(3, root_node);
+
+    for (node_id, value) in tree.iter_mut() {
+        if node_id != node_1 {
+            *value *= 10;
+        }
+    }
+
+    assert_eq!(tree[root_node], 10);
+    assert_eq!(tree[node_1], 2);
+    assert_eq!(tree[node_2], 30);
+}
+
+#[test]
+fn into_iter() {
+    let mut tree = VecTree::new();
+
+    // 0-1-4-6
+    // | |
+    // | 5
+    // |
+    // 2
+    // |
+    // 3-7-8
+    let root_node = tree.insert_root(0);
+    let node_1 = tree.insert(1, root_node);
+    tree.insert(2, root_node);
+    let node_3 = tree.insert(3, root_node);
+    let node_4 = tree.insert(4, node_1);
+    tree.insert(5, node_1);
+    tree.insert(6, node_4);
+    let node_7 = tree.insert(7, node_3);
+    tree.insert(8, node_7);
+
+    assert_eq!(
+        tree.clone().into_iter().collect::<Vec<_>>(),
+        [0, 1, 4, 6, 5, 2, 3, 7, 8]
+    );
+    assert_eq!(
+        tree.into_iter_with_depth().collect::<Vec<_>>(),
+        [
+            (0, 0),
+            (1, 1),
+            (4, 2),
+            (6, 3),
+            (5, 2),
+            (2, 1),
+            (3, 1),
+            (7, 2),
+            (8, 3)
+        ]
+    );
+
+    assert_eq!(VecTree::<i32>::new().into_iter().count(), 0);
+}
+
+#[test]
+fn into_iter_by_reference() {
+    let mut tree = VecTree::new();
+
+    let root_node = tree.insert_root(1);
+    let node_1 = tree.insert(2, root_node);
+
+    for (_, value) in &mut tree {
+        *value += 1;
+    }
+
+    let mut sum = 0;
+    for (node_id, value) in &tree {
+        assert_eq!(tree[node_id], *value);
+        sum += value;
+    }
+    assert_eq!(sum, 5);
+    assert_eq!(tree[node_1], 3);
+}
+
+#[test]
+fn children_double_ended() {
+    let mut tree = VecTree::new();
+
+    let root_node = tree.insert_root(0);
+    tree.insert(1, root_node);
+    tree.insert(2, root_node);
+    tree.insert(3, root_node);
+    tree.insert(4, root_node);
+
+    assert_eq!(
+        tree.children(root_node)
+            .rev()
+            .map(|node_id| tree[node_id])
+            .collect::<Vec<_>>(),
+        [4, 3, 2, 1]
+    );
+
+    let mut children = tree.children(root_node).map(|node_id| tree[node_id]);
+    assert_eq!(children.next(), Some(1));
+    assert_eq!(children.next_back(), Some(4));
+    assert_eq!(children.next_back(), Some(3));
+    assert_eq!(children.next(), Some(2));
+    assert_eq!(children.next(), None);
+    assert_eq!(children.next_back(), None);
+
+    let leaf = tree.insert(5, root_node);
+    assert_eq!(tree.children(leaf).next_back(), None);
+    assert_eq!(
+        tree.children(root_node)
+            .rev()
+            .find(|&node_id| tree[node_id] % 2 == 0),
+        tree.nth_child(root_node, 3)
+    );
+}
+
+#[test]
+fn descendants_double_ended() {
+    let mut tree = VecTree::new();
+
+    // 0-1-4-6
+    // | |
+    // | 5
+    // |
+    // 2
+    // |
+    // 3-7-8
+    let root_node = tree.insert_root(0);
+    let node_1 = tree.insert(1, root_node);
+    tree.insert(2, root_node);
+    let node_3 = tree.insert(3, root_node);
+    let node_4 = tree.insert(4, node_1);
+    tree.insert(5, node_1);
+    tree.insert(6, node_4);
+    let node_7 = tree.insert(7, node_3);
+    tree.insert(8, node_7);
+
+    assert_eq!(
+        tree.descendants(root_node)
+            .rev()
+            .map(|node_id| tree[node_id])
+            .collect::<Vec<_>>(),
+        [8, 7, 3, 2, 5, 6, 4, 1, 0]
+    );
+    assert_eq!(
+        tree.descendants(node_1)
+            .rev()
+            .map(|node_id| tree[node_id])
+            .collect::<Vec<_>>(),
+        [5, 6, 4, 1]
+    );
+
+    let mut traverse = tree.traverse(node_4);
+    assert_eq!(traverse.next_back(), Some(NodeEdge::End(node_4)));
+    assert_eq!(traverse.next(), Some(NodeEdge::Start(node_4)));
+    assert_eq!(
+        traverse.next_back(),
+        Some(NodeEdge::End(tree.first_child(node_4).unwrap()))
+    );
+    assert_eq!(
+        traverse.next(),
+        Some(NodeEdge::Start(tree.first_child(node_4).unwrap()))
+    );
+    assert_eq!(traverse.next(), None);
+    assert_eq!(traverse.next_back(), None);
+
+    let mut descendants = tree.descendants(root_node).map(|node_id| tree[node_id]);
+    assert_eq!(descendants.next(), Some(0));
+    assert_eq!(descendants.next_back(), Some(8));
+    assert_eq!(descendants.next(), Some(1));
+    assert_eq!(descendants.next_back(), Some(7));
+    assert_eq!(descendants.collect::<Vec<_>>(), [4, 6, 5, 2, 3]);
+}
+
+#[test]
+fn size_hints() {
+    let mut tree = VecTree::new();
+
+    // 0-1-3
+    // |
+    // 2
+    let root_node = tree.insert_root(0);
+    let node_1 = tree.insert(1, root_node);
+    tree.insert(2, root_node);
+    let node_3 = tree.insert(3, node_1);
+
+    assert_eq!(tree.children(root_node).size_hint(), (1, Some(4)));
+    assert_eq!(tree.children(node_3).size_hint(), (0, Some(4)));
+    assert_eq!(tree.ancestors(node_3).size_hint(), (1, Some(4)));
+    assert_eq!(tree.descendants(root_node).size_hint(), (1, Some(4)));
+    assert_eq!(tree.traverse(root_node).size_hint(), (1, Some(8)));
+    assert_eq!(tree.path_from_root(node_3).len(), 3);
+
+    assert_eq!(tree.iter().len(), 4);
+    assert_eq!(tree.values().len(), 4);
+    assert_eq!(tree.values_mut().len(), 4);
+    assert_eq!(tree.iter_mut().len(), 4);
+
+    let mut into_iter = tree.into_iter();
+    assert_eq!(into_iter.len(), 4);
+    into_iter.next();
+    assert_eq!(into_iter.len(), 3);
+    assert_eq!(into_iter.count(), 3);
+}
+
+#[test]
+fn fused_iterators() {
+    fn assert_fused<I: std::iter::FusedIterator>(mut iter: I) {
+        while iter.next().is_some() {}
+        assert!(iter.next().is_none());
+        assert!(iter.next().is_none());
+    }
+
+    let mut tree = VecTree::new();
+
+    let root_node = tree.insert_root(0);
+    let node_1 = tree.insert(1, root_node);
+    let node_2 = tree.insert(2, node_1);
+    tree.insert(3, root_node);
+
+    assert_fused(tree.children(root_node));
+    assert_fused(tree.ancestors(node_2));
+    assert_fused(tree.traverse(root_node));
+    assert_fused(tree.descendants(root_node));
+    assert_fused(tree.descendants_with_depth(root_node));
+    assert_fused(tree.levels(root_node));
+    assert_fused(tree.iddfs(root_node));
+    assert_fused(tree.children_values(root_node));
+    assert_fused(tree.iter());
+    assert_fused(Query::new().descendants().run(&tree, root_node));
+    assert_fused(tree.into_iter());
+}
+
+#[test]
+fn for_each_descendant_mut() {
+    let mut tree = VecTree::new();
+
+    // 0-1-4-6
+    // | |
+    // | 5
+    // |
+    // 2
+    // |
+    // 3-7-8
+    let root_node = tree.insert_root(0);
+    let node_1 = tree.insert(1, root_node);
+    tree.insert(2, root_node);
+    let node_3 = tree.insert(3, root_node);
+    let node_4 = tree.insert(4, node_1);
+    tree.insert(5, node_1);
+    tree.insert(6, node_4);
+    let node_7 = tree.insert(7, node_3);
+    tree.insert(8, node_7);
+
+    let mut visited = Vec::new();
+    tree.for_each_descendant_mut(node_1, |node_id, value| {
+        visited.push(node_id);
+        *value += 10;
+    });
+
+    assert_eq!(visited, tree.descendants(node_1).collect::<Vec<_>>());
+    assert_eq!(
+        tree.descendants(root_node)
+            .map(|node_id| tree[node_id])
+            .collect::<Vec<_>>(),
+        [0, 11, 14, 16, 15, 2, 3, 7, 8]
+    );
+
+    let mut count = 0;
+    tree.for_each_descendant_mut(root_node, |_, _| count += 1);
+    assert_eq!(count, 9);
+}
+
+#[test]
+fn for_each_child_mut() {
+    let mut tree = VecTree::new();
+
+    // 0-1-3
+    // |
+    // 2
+    let root_node = tree.insert_root(0);
+    let node_1 = tree.insert(1, root_node);
+    let node_2 = tree.insert(2, root_node);
+    let node_3 = tree.insert(3, node_1);
+
+    let mut visited = Vec::new();
+    tree.for_each_child_mut(root_node, |node_id, value| {
+        visited.push(node_id);
+        *value *= 10;
+    });
+
+    assert_eq!(visited, [node_1, node_2]);
+    assert_eq!(tree[root_node], 0);
+    assert_eq!(tree[node_1], 10);
+    assert_eq!(tree[node_2], 20);
+    assert_eq!(tree[node_3], 3);
+
+    tree.for_each_child_mut(node_3, |_, _| panic!("node_3 has no children"));
+}
+
+#[test]
+fn traverse_mut() {
+    let mut tree = VecTree::new();
+
+    // 1-1-1
+    // | |
+    // | 1
+    // |
+    // 1
+    let root_node = tree.insert_root(1);
+    let node_1 = tree.insert(1, root_node);
+    let node_2 = tree.insert(1, root_node);
+    let node_3 = tree.insert(1, node_1);
+    let node_4 = tree.insert(1, node_1);
+
+    let mut edges = Vec::new();
+    let mut sums = Vec::new();
+    tree.traverse_mut(root_node, |edge, value| {
+        edges.push(edge);
+        // Replace each value with the sum of its subtree.
+        match edge {
+            NodeEdge::Start(_) => sums.push(0),
+            NodeEdge::End(_) => {
+                *value += sums.pop().unwrap();
+                if let Some(parent_sum) = sums.last_mut() {
+                    *parent_sum += *value;
+                }
+            }
+        }
+    });
+
+    assert_eq!(edges, tree.traverse(root_node).collect::<Vec<_>>());
+    assert_eq!(tree[root_node], 5);
+    assert_eq!(tree[node_1], 3);
+    assert_eq!(tree[node_2], 1);
+    assert_eq!(tree[node_3], 1);
+    assert_eq!(tree[node_4], 1);
+
+    let mut count = 0;
+    tree.traverse_mut(node_3, |_, _| count += 1);
+    assert_eq!(count, 2);
+}
+
+#[test]
+fn version() {
+    let mut tree = VecTree::new();
+
+    let mut version = tree.version();
+    let mut assert_changed = |tree: &VecTree<i32>, changed: bool| {
+        assert_eq!(tree.version() != version, changed);
+        version = tree.version();
+    };
+
+    let root_node = tree.insert_root(0);
+    assert_changed(&tree, true);
+    let node_1 = tree.insert(1, root_node);
+    assert_changed(&tree, true);
+    let node_2 = tree.insert(2, root_node);
+    assert_changed(&tree, true);
+
+    tree[node_1] = 10;
+    *tree.get_mut(node_2).unwrap() = 20;
+    tree.for_each_descendant_mut(root_node, |_, value| *value += 1);
+    assert_changed(&tree, false);
+
+    tree.append_child(node_1, node_2);
+    assert_changed(&tree, true);
+    tree.insert_sorted(3, root_node, |a, b| a.cmp(b));
+    assert_changed(&tree, true);
+    tree.remove(node_2);
+    assert_changed(&tree, true);
+    tree.remove(node_2);
+    assert_changed(&tree, false);
+    tree.clear();
+    assert_changed(&tree, true);
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn parallel_iterators() {
+    use rayon::prelude::*;
+
+    let mut tree = VecTree::new();
+
+    // 0-1-4-6
+    // | |
+    // | 5
+    // |
+    // 2
+    // |
+    // 3-7-8
+    let root_node = tree.insert_root(0);
+    let node_1 = tree.insert(1, root_node);
+    tree.insert(2, root_node);
+    let node_3 = tree.insert(3, root_node);
+    let node_4 = tree.insert(4, node_1);
+    tree.insert(5, node_1);
+    tree.insert(6, node_4);
+    let node_7 = tree.insert(7, node_3);
+    tree.insert(8, node_7);
+
+    assert_eq!(tree.par_values().sum::<i32>(), 36);
+    assert_eq!(tree.par_values().count(), 9);
+    assert!(tree
+        .par_iter()
+        .all(|(node_id, value)| tree[node_id] == *value));
+    assert_eq!(
+        tree.par_descendants(node_1)
+            .map(|node_id| tree[node_id])
+            .collect::<Vec<_>>(),
+        [1, 4, 6, 5]
+    );
+
+    // Large enough to be split between threads, with free slots in the arena.
+    let mut tree = VecTree::new();
+    let root_node = tree.insert_root(0);
+    let mut parents = vec![root_node];
+    for value in 1..10_000 {
+        let node_id = tree.insert(value, parents[value / 3]);
+        parents.push(node_id);
+    }
+    for &node_id in parents.iter().skip(2).step_by(7) {
+        tree.remove(node_id);
+    }
+
+    let mut values = tree.par_values().copied().collect::<Vec<_>>();
+    values.sort_unstable();
+    assert_eq!(values, tree.values().copied().collect::<Vec<_>>());
+    assert_eq!(
+        tree.par_descendants(root_node).collect::<Vec<_>>(),
+        tree.descendants(root_node).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn subtrees_mut() {
+    let mut tree = VecTree::new();
+
+    // 0-1-4-6
+    // | |
+    // | 5
+    // |
+    // 2
+    // |
+    // 3-7-8
+    let root_node = tree.insert_root(0);
+    let node_1 = tree.insert(1, root_node);
+    let node_2 = tree.insert(2, root_node);
+    let node_3 = tree.insert(3, root_node);
+    let node_4 = tree.insert(4, node_1);
+    tree.insert(5, node_1);
+    tree.insert(6, node_4);
+    let node_7 = tree.insert(7, node_3);
+    let node_8 = tree.insert(8, node_7);
+
+    {
+        let mut subtrees = tree.subtrees_mut(&[node_3, node_1]);
+        assert_eq!(subtrees.len(), 2);
+        assert_eq!(subtrees[0].root(), node_3);
+        assert_eq!(
+            subtrees[0]
+                .iter()
+                .map(|(_, value)| *value)
+                .collect::<Vec<_>>(),
+            [3, 7, 8]
+        );
+        assert!(subtrees[1].contains(node_4));
+        assert!(!subtrees[1].contains(node_2));
+        assert_eq!(subtrees[1].get(node_2), None);
+
+        let (first, second) = subtrees.split_at_mut(1);
+        first[0][node_8] = 80;
+        for (_, value) in second[0].iter_mut() {
+            *value += 10;
+        }
+    }
+
+    assert_eq!(
+        tree.descendants(root_node)
+            .map(|node_id| tree[node_id])
+            .collect::<Vec<_>>(),
+        [0, 11, 14, 16, 15, 2, 3, 7, 80]
+    );
+}
+
+#[test]
+#[should_panic(expected = "The subtrees overlap")]
+fn subtrees_mut_overlapping() {
+    let mut tree = VecTree::new();
+
+    let root_node = tree.insert_root(0);
+    let node_1 = tree.insert(1, root_node);
+    let node_2 = tree.insert(2, node_1);
+
+    tree.subtrees_mut(&[node_2, node_1]);
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn par_subtrees_mut() {
+    let mut tree = VecTree::new();
+
+    let root_node = tree.insert_root(0);
+    let roots = (1..=8)
+        .map(|value| tree.insert(value, root_node))
+        .collect::<Vec<_>>();
+    for &node_id in &roots {
+        tree.insert(0, node_id);
+    }
+
+    tree.par_subtrees_mut(&roots, |mut subtree| {
+        let root = subtree.root();
+        let root_value = subtree[root];
+        for (_, value) in subtree.iter_mut() {
+            *value = root_value * 10;
+        }
+    });
+
+    assert_eq!(tree[root_node], 0);
+    for (value, &node_id) in (1..=8).zip(&roots) {
+        assert!(tree
+            .descendants(node_id)
+            .all(|node_id| tree[node_id] == value * 10));
+    }
+}
+
+#[cfg(feature = "futures")]
+#[test]
+fn traversal_streams() {
+    use futures::executor::block_on;
+    use futures::{Stream, StreamExt};
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+
+    let mut tree = VecTree::new();
+
+    // 0-1-3
+    // |
+    // 2
+    let root_node = tree.insert_root(0);
+    let node_1 = tree.insert(1, root_node);
+    tree.insert(2, root_node);
+    tree.insert(3, node_1);
+
+    assert_eq!(
+        block_on(tree.descendants_stream(root_node, 1).collect::<Vec<_>>()),
+        tree.descendants(root_node).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        block_on(tree.traverse_stream(node_1, 3).collect::<Vec<_>>()),
+        tree.traverse(node_1).collect::<Vec<_>>()
+    );
+
+    // The stream gives control back to the executor after each chunk.
+    let mut stream = tree.descendants_stream(root_node, 2);
+    let waker = futures::task::noop_waker();
+    let mut cx = Context::from_waker(&waker);
+    let mut polls = Vec::new();
+    loop {
+        match Pin::new(&mut stream).poll_next(&mut cx) {
+            Poll::Ready(Some(node_id)) => polls.push(Some(tree[node_id])),
+            Poll::Ready(None) => break,
+            Poll::Pending => polls.push(None),
+        }
+    }
+    assert_eq!(polls, [Some(0), Some(1), None, Some(3), Some(2), None]);
+}
+
+#[test]
+fn visit() {
+    use std::ops::ControlFlow;
+    use vec_tree::{VisitAction, Visitor};
+
+    #[derive(Default)]
+    struct Recorder {
+        events: Vec<String>,
+        skip: Option<i32>,
+        stop: Option<i32>,
+    }
+
+    impl Visitor<i32> for Recorder {
+        fn enter(&mut self, _: vec_tree::Index, data: &i32) -> ControlFlow<(), VisitAction> {
+            if Some(*data) == self.stop {
+                return ControlFlow::Break(());
+            }
+            self.events.push(format!("enter {}", data));
+            if Some(*data) == self.skip {
+                ControlFlow::Continue(VisitAction::SkipChildren)
+            } else {
+                ControlFlow::Continue(VisitAction::Continue)
+            }
+        }
+
+        fn exit(&mut self, _: vec_tree::Index, data: &i32) {
+            self.events.push(format!("exit {}", data));
+        }
+    }
+
+    let mut tree = VecTree::new();
+
+    // 0-1-3
+    // |
+    // 2
+    let root_node = tree.insert_root(0);
+    let node_1 = tree.insert(1, root_node);
+    tree.insert(2, root_node);
+    tree.insert(3, node_1);
+
+    let mut recorder = Recorder::default();
+    assert_eq!(
+        tree.visit(root_node, &mut recorder),
+        ControlFlow::Continue(())
+    );
+    assert_eq!(
+        recorder.events,
+        ["enter 0", "enter 1", "enter 3", "exit 3", "exit 1", "enter 2", "exit 2", "exit 0"]
+    );
+
+    let mut recorder = Recorder {
+        skip: Some(1),
+        ..Recorder::default()
+    };
+    assert_eq!(
+        tree.visit(root_node, &mut recorder),
+        ControlFlow::Continue(())
+    );
+    assert_eq!(
+        recorder.events,
+        ["enter 0", "enter 1", "exit 1", "enter 2", "exit 2", "exit 0"]
+    );
+
+    let mut recorder = Recorder {
+        stop: Some(2),
+        ..Recorder::default()
+    };
+    assert_eq!(tree.visit(root_node, &mut recorder), ControlFlow::Break(()));
+    assert_eq!(
+        recorder.events,
+        ["enter 0", "enter 1", "enter 3", "exit 3", "exit 1"]
+    );
+
+    // Leaves have no subtree to skip.
+    let mut recorder = Recorder {
+        skip: Some(3),
+        ..Recorder::default()
+    };
+    assert_eq!(tree.visit(node_1, &mut recorder), ControlFlow::Continue(()));
+    assert_eq!(recorder.events, ["enter 1", "enter 3", "exit 3", "exit 1"]);
+}
+
+#[test]
+fn fold_subtree() {
+    let mut tree = VecTree::new();
+
+    // 0-1-4-6
+    // | |
+    // | 5
+    // |
+    // 2
+    // |
+    // 3-7-8
+    let root_node = tree.insert_root(0);
+    let node_1 = tree.insert(1, root_node);
+    let node_2 = tree.insert(2, root_node);
+    let node_3 = tree.insert(3, root_node);
+    let node_4 = tree.insert(4, node_1);
+    tree.insert(5, node_1);
+    tree.insert(6, node_4);
+    let node_7 = tree.insert(7, node_3);
+    tree.insert(8, node_7);
+
+    let sum = |value: &i32, children: &[i32]| value + children.iter().sum::<i32>();
+    assert_eq!(tree.fold_subtree(root_node, sum), 36);
+    assert_eq!(tree.fold_subtree(node_1, sum), 16);
+    assert_eq!(tree.fold_subtree(node_2, sum), 2);
+
+    let mut order = Vec::new();
+    let rendered = tree.fold_subtree(root_node, |value, children: &[String]| {
+        order.push(*value);
+        if children.is_empty() {
+            value.to_string()
+        } else {
+            format!("{}({})", value, children.join(","))
+        }
+    });
+    assert_eq!(rendered, "0(1(4(6),5),2,3(7(8)))");
+    assert_eq!(order, [6, 4, 5, 1, 2, 8, 7, 3, 0]);
+}
+
+#[test]
+fn propagate_down() {
+    let mut tree = VecTree::new();
+
+    // a-b-d
+    // | |
+    // | e
+    // |
+    // c-f
+    let root_node = tree.insert_root(String::from("a"));
+    let node_b = tree.insert(String::from("b"), root_node);
+    let node_c = tree.insert(String::from("c"), root_node);
+    let node_d = tree.insert(String::from("d"), node_b);
+    let node_e = tree.insert(String::from("e"), node_b);
+    let node_f = tree.insert(String::from("f"), node_c);
+
+    tree.propagate_down(root_node, String::new(), |parent_path, name| {
+        *name = format!("{}/{}", parent_path, name);
+        name.clone()
+    });
+
+    assert_eq!(tree[root_node], "/a");
+    assert_eq!(tree[node_b], "/a/b");
+    assert_eq!(tree[node_d], "/a/b/d");
+    assert_eq!(tree[node_e], "/a/b/e");
+    assert_eq!(tree[node_c], "/a/c");
+    assert_eq!(tree[node_f], "/a/c/f");
+
+    let mut depths = Vec::new();
+    tree.propagate_down(node_b, 0, |parent_depth, _| {
+        depths.push(parent_depth + 1);
+        parent_depth + 1
+    });
+    assert_eq!(depths, [1, 2, 2]);
+    assert_eq!(tree[node_b], "/a/b");
+}
+
+#[test]
+fn map() {
+    let mut tree = VecTree::new();
+
+    // 0-1-4-6
+    // | |
+    // | 5
+    // |
+    // 2
+    // |
+    // 3-7-8
+    let root_node = tree.insert_root(0);
+    let node_1 = tree.insert(1, root_node);
+    tree.insert(2, root_node);
+    let node_3 = tree.insert(3, root_node);
+    let node_4 = tree.insert(4, node_1);
+    tree.insert(5, node_1);
+    tree.insert(6, node_4);
+    let node_7 = tree.insert(7, node_3);
+    tree.insert(8, node_7);
+    tree.remove(node_4);
+
+    let mapped = tree.map(|node_id, value| (node_id, value * 10));
+    let mapped_root = mapped.get_root_index().unwrap();
+
+    assert_eq!(mapped.capacity(), tree.descendants(root_node).count());
+    assert_eq!(
+        mapped
+            .traverse(mapped_root)
+            .map(|edge| match edge {
+                NodeEdge::Start(node_id) => NodeEdge::Start(mapped[node_id].0),
+                NodeEdge::End(node_id) => NodeEdge::End(mapped[node_id].0),
+            })
+            .collect::<Vec<_>>(),
+        tree.traverse(root_node).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        mapped
+            .descendants(mapped_root)
+            .map(|node_id| mapped[node_id].1)
+            .collect::<Vec<_>>(),
+        [0, 10, 50, 20, 30, 70, 80]
+    );
+
+    let (mapped, remap) = tree.map_with_remap(|_, value| value * 10);
+    assert_eq!(remap.len(), 7);
+    for node_id in tree.descendants(root_node) {
+        assert_eq!(mapped[remap[&node_id]], tree[node_id] * 10);
+        assert_eq!(
+            mapped.parent(remap[&node_id]),
+            tree.parent(node_id).map(|parent_id| remap[&parent_id])
+        );
+    }
+    assert!(!remap.contains_key(&node_4));
+
+    assert!(VecTree::<i32>::new()
+        .map(|_, value| *value)
+        .get_root_index()
+        .is_none());
+}
+
+#[test]
+fn filter_map() {
+    let mut tree = VecTree::new();
+
+    // 0-1-4-6
+    // | |
+    // | 5
+    // |
+    // 2
+    // |
+    // 3-7-8
+    let root_node = tree.insert_root(0);
+    let node_1 = tree.insert(1, root_node);
+    tree.insert(2, root_node);
+    let node_3 = tree.insert(3, root_node);
+    let node_4 = tree.insert(4, node_1);
+    tree.insert(5, node_1);
+    tree.insert(6, node_4);
+    let node_7 = tree.insert(7, node_3);
+    tree.insert(8, node_7);
+
+    let mut visited = Vec::new();
+    let filtered = tree.filter_map(|_, &value| {
+        visited.push(value);
+        if value == 4 || value == 2 {
+            None
+        } else {
+            Some(value * 10)
+        }
+    });
+    let filtered_root = filtered.get_root_index().unwrap();
+
+    // 0-10-50
+    // |
+    // 30-70-80
+    assert_eq!(visited, [0, 1, 4, 5, 2, 3, 7, 8]);
+    assert_eq!(
+        filtered
+            .descendants_with_depth(filtered_root)
+            .map(|(node_id, depth)| (filtered[node_id], depth))
+            .collect::<Vec<_>>(),
+        [(0, 0), (10, 1), (50, 2), (30, 1), (70, 2), (80, 3)]
+    );
+
+    let empty = tree.filter_map(|_, &value| if value == 0 { None } else { Some(value) });
+    assert!(empty.get_root_index().is_none());
+}
+
+#[test]
+fn map_in_place() {
+    let mut tree = VecTree::new();
+
+    let root_node = tree.insert_root(1);
+    let node_1 = tree.insert(2, root_node);
+    let node_3 = tree.insert(4, root_node);
+    let node_2 = tree.insert(3, node_1);
+    let version = tree.version();
+
+    let mut visited = Vec::new();
+    tree.map_in_place(|node_id, value| {
+        visited.push(node_id);
+        *value = *value * *value;
+    });
+
+    assert_eq!(visited, [root_node, node_1, node_2, node_3]);
+    assert_eq!(tree[root_node], 1);
+    assert_eq!(tree[node_1], 4);
+    assert_eq!(tree[node_2], 9);
+    assert_eq!(tree[node_3], 16);
+    assert_eq!(tree.version(), version);
+
+    VecTree::<i32>::new().map_in_place(|_, _| unreachable!());
+}
+
+#[test]
+fn zip() {
+    let mut tree = VecTree::new();
+
+    // 0-1-4-6
+    // | |
+    // | 5
+    // |
+    // 2
+    // |
+    // 3-7-8
+    let root_node = tree.insert_root(0);
+    let node_1 = tree.insert(1, root_node);
+    tree.insert(2, root_node);
+    let node_3 = tree.insert(3, root_node);
+    let node_4 = tree.insert(4, node_1);
+    tree.insert(5, node_1);
+    tree.insert(6, node_4);
+    let node_7 = tree.insert(7, node_3);
+    tree.insert(8, node_7);
+
+    let mapped = tree.map(|_, value| value * 10);
+    assert_eq!(
+        tree.zip(&mapped)
+            .map(|(a, b)| (tree[a], mapped[b]))
+            .collect::<Vec<_>>(),
+        [
+            (0, 0),
+            (1, 10),
+            (4, 40),
+            (6, 60),
+            (5, 50),
+            (2, 20),
+            (3, 30),
+            (7, 70),
+            (8, 80)
+        ]
+    );
+
+    // a-b-d
+    // |
+    // c
+    let mut other = VecTree::new();
+    let node_a = other.insert_root("a");
+    let node_b = other.insert("b", node_a);
+    other.insert("c", node_a);
+    other.insert("d", node_b);
+
+    assert_eq!(
+        tree.zip(&other)
+            .map(|(a, b)| (tree[a], other[b]))
+            .collect::<Vec<_>>(),
+        [(0, "a"), (1, "b"), (4, "d"), (2, "c")]
+    );
+    assert_eq!(
+        other
+            .zip(&tree)
+            .map(|(a, b)| (other[a], tree[b]))
+            .collect::<Vec<_>>(),
+        [("a", 0), ("b", 1), ("d", 4), ("c", 2)]
+    );
+
+    assert_eq!(tree.zip(&VecTree::<i32>::new()).count(), 0);
+}
+
+#[test]
+fn edges() {
+    let mut tree = VecTree::new();
+
+    // 0-1-4-6
+    // | |
+    // | 5
+    // |
+    // 2
+    // |
+    // 3-7-8
+    let root_node = tree.insert_root(0);
+    let node_1 = tree.insert(1, root_node);
+    tree.insert(2, root_node);
+    let node_3 = tree.insert(3, root_node);
+    let node_4 = tree.insert(4, node_1);
+    tree.insert(5, node_1);
+    tree.insert(6, node_4);
+    let node_7 = tree.insert(7, node_3);
+    tree.insert(8, node_7);
+
+    assert_eq!(
+        tree.edges()
+            .map(|(parent, child)| (tree[parent], tree[child]))
+            .collect::<Vec<_>>(),
+        [
+            (0, 1),
+            (1, 4),
+            (4, 6),
+            (1, 5),
+            (0, 2),
+            (0, 3),
+            (3, 7),
+            (7, 8)
+        ]
+    );
+
+    tree.remove(node_1);
+    tree.remove(node_3);
+    assert_eq!(tree.edges().count(), 1);
+    tree.clear();
+    assert_eq!(tree.edges().count(), 0);
+}
+
+#[test]
+fn descendant_range() {
+    let mut tree = VecTree::new();
+
+    // 0-1-4-6
+    // | |
+    // | 5
+    // |
+    // 2
+    // |
+    // 3-7-8
+    let root_node = tree.insert_root(0);
+    let node_1 = tree.insert(1, root_node);
+    let node_2 = tree.insert(2, root_node);
+    let node_3 = tree.insert(3, root_node);
+    let node_4 = tree.insert(4, node_1);
+    tree.insert(5, node_1);
+    let node_6 = tree.insert(6, node_4);
+    let node_7 = tree.insert(7, node_3);
+    let node_8 = tree.insert(8, node_7);
+
+    let table = AncestorTable::new(&tree);
+    let order = tree.descendants(root_node).collect::<Vec<_>>();
+
+    for (position, &node_id) in order.iter().enumerate() {
+        assert_eq!(table.position(node_id), Some(position));
+
+        let range = table.descendant_range(node_id).unwrap();
+        assert_eq!(
+            order[range],
+            tree.descendants(node_id).collect::<Vec<_>>()[..]
+        );
+    }
+
+    assert_eq!(table.descendant_range(root_node), Some(0..9));
+    assert_eq!(table.descendant_range(node_1), Some(1..5));
+    assert_eq!(table.descendant_range(node_6), Some(3..4));
+    assert_eq!(table.descendant_range(node_2), Some(5..6));
+    assert_eq!(table.descendant_range(node_3), Some(6..9));
+    assert_eq!(table.position(node_8), Some(8));
+
+    tree.remove(node_4);
+    let new_node = tree.insert(9, root_node);
+    assert_eq!(table.descendant_range(new_node), None);
+}
+
+#[test]
+fn resume_traverse() {
+    let mut tree = VecTree::new();
+
+    // 0-1-4-6
+    // | |
+    // | 5
+    // |
+    // 2
+    // |
+    // 3-7-8
+    let root_node = tree.insert_root(0);
+    let node_1 = tree.insert(1, root_node);
+    tree.insert(2, root_node);
+    let node_3 = tree.insert(3, root_node);
+    let node_4 = tree.insert(4, node_1);
+    tree.insert(5, node_1);
+    tree.insert(6, node_4);
+    let node_7 = tree.insert(7, node_3);
+    tree.insert(8, node_7);
+
+    let expected = tree.traverse(root_node).collect::<Vec<_>>();
+
+    // Process the traversal in slices of 3 edges, saving the cursor in between.
+    let mut cursor = tree.traverse(root_node).cursor();
+    let mut edges = Vec::new();
+    while !cursor.is_finished() {
+        let mut traverse = tree.resume_traverse(cursor).unwrap();
+        edges.extend(traverse.by_ref().take(3));
+        cursor = traverse.cursor();
+    }
+    assert_eq!(edges, expected);
+    assert_eq!(cursor.root(), root_node);
+    assert_eq!(cursor.next_edge(), None);
+
+    // Both ends are saved.
+    let mut traverse = tree.traverse(node_1);
+    traverse.next();
+    traverse.next_back();
+    let cursor = traverse.cursor();
+    assert_eq!(cursor.next_edge(), Some(NodeEdge::Start(node_4)));
+    assert_eq!(
+        tree.resume_traverse(cursor).unwrap().collect::<Vec<_>>(),
+        traverse.collect::<Vec<_>>()
+    );
+
+    let mut traverse = tree.traverse(root_node);
+    traverse.next();
+    let cursor = traverse.cursor();
+    tree.remove(node_1);
+    assert!(tree.resume_traverse(cursor).is_none());
+}
+
+#[test]
+fn get_many() {
+    let mut tree = VecTree::new();
+
+    let root_node = tree.insert_root(0);
+    let node_1 = tree.insert(1, root_node);
+    let node_2 = tree.insert(2, node_1);
+
+    assert_eq!(
+        tree.get_many(&[node_2, root_node, node_2]),
+        [Some(&2), Some(&0), Some(&2)]
+    );
+    assert!(tree.get_many(&[]).is_empty());
+
+    tree.remove(node_1);
+    assert_eq!(
+        tree.get_many(&[root_node, node_1, node_2]),
+        [Some(&0), None, None]
+    );
+}
+
+#[test]
+fn cursor_mut() {
+    let mut tree = VecTree::new();
+
+    // 0-1-3
+    // |
+    // 2
+    let root_node = tree.insert_root(0);
+    let node_1 = tree.insert(1, root_node);
+    let node_2 = tree.insert(2, root_node);
+    let node_3 = tree.insert(3, node_1);
+
+    let mut cursor = tree.cursor_mut(root_node);
+    assert!(!cursor.move_parent());
+    assert!(!cursor.move_next_sibling());
+    assert_eq!(cursor.current(), root_node);
+
+    assert!(cursor.move_last_child());
+    assert_eq!(cursor.current(), node_2);
+    assert!(!cursor.move_first_child());
+    assert!(cursor.move_previous_sibling());
+    assert_eq!(*cursor.value(), 1);
+    assert!(cursor.move_first_child());
+    assert_eq!(cursor.current(), node_3);
+
+    let node_4 = cursor.insert_before(4);
+    let node_5 = cursor.insert_after(5);
+    let node_6 = cursor.insert_after(6);
+    let node_7 = cursor.append_child(7);
+    *cursor.value_mut() = 30;
+    assert_eq!(
+        cursor.tree().children(node_1).collect::<Vec<_>>(),
+        [node_4, node_3, node_6, node_5]
+    );
+
+    assert_eq!(cursor.remove_current(), 30);
+    assert_eq!(cursor.current(), node_1);
+    assert!(cursor.move_first_child());
+    assert!(cursor.move_next_sibling());
+    assert_eq!(cursor.current(), node_6);
+
+    assert!(!tree.contains(node_3));
+    assert!(!tree.contains(node_7));
+
+    // 0-1-4
+    // | |
+    // | 6
+    // | |
+    // | 5
+    // |
+    // 2
+    assert_eq!(
+        tree.descendants(root_node)
+            .map(|node_id| tree[node_id])
+            .collect::<Vec<_>>(),
+        [0, 1, 4, 6, 5, 2]
+    );
+    assert_eq!(tree.previous_sibling(node_5), Some(node_6));
+    assert_eq!(tree.last_child(node_1), Some(node_5));
+    assert_eq!(tree.first_child(node_1), Some(node_4));
+}
+
+#[test]
+fn node_handles() {
+    let mut tree = VecTree::new();
+    assert!(tree.root_node().is_none());
+
+    // 0-1-3
+    // |
+    // 2
+    let root_node = tree.insert_root(0);
+    let node_1 = tree.insert(1, root_node);
+    let node_2 = tree.insert(2, root_node);
+
+    {
+        let mut node = tree.node_mut(node_1).unwrap();
+        let mut child = node.append(3);
+        *child.value_mut() *= 10;
+        assert_eq!(*child.parent().unwrap().value(), 1);
+    }
+
+    let root = tree.root_node().unwrap();
+    assert!(root.is_root());
+    assert_eq!(root.id(), root_node);
+    assert_eq!(
+        root.children()
+            .map(|node| *node.value())
+            .collect::<Vec<_>>(),
+        [1, 2]
+    );
+    assert_eq!(
+        root.children()
+            .rev()
+            .map(|node| node.id())
+            .collect::<Vec<_>>(),
+        [node_2, node_1]
+    );
+    assert_eq!(
+        root.descendants()
+            .map(|node| *node.value())
+            .collect::<Vec<_>>(),
+        [0, 1, 30, 2]
+    );
+
+    let node_3 = root.first_child().unwrap().first_child().unwrap();
+    assert!(!node_3.has_children());
+    assert_eq!(
+        node_3
+            .ancestors()
+            .map(|node| *node.value())
+            .collect::<Vec<_>>(),
+        [30, 1, 0]
+    );
+    assert_eq!(
+        node_3.parent().unwrap().next_sibling().unwrap().id(),
+        node_2
+    );
+    assert!(tree.node(node_2).unwrap().next_sibling().is_none());
+
+    tree.remove(node_2);
+    assert!(tree.node(node_2).is_none());
+    assert!(tree.node_mut(node_2).is_none());
+}
+
+#[test]
+fn zipper() {
+    let mut tree = VecTree::new();
+    assert!(tree.clone().into_zipper().is_none());
+
+    // 0-1-3
+    // |
+    // 2
+    let root_node = tree.insert_root(0);
+    let node_1 = tree.insert(1, root_node);
+    tree.insert(2, root_node);
+    tree.insert(3, node_1);
+
+    let original = tree.into_zipper().unwrap();
+    assert!(original.path().is_empty());
+    assert!(original.parent().is_none());
+    assert!(original.next_sibling().is_none());
+
+    let zipper = original.last_child().unwrap();
+    assert_eq!(zipper.path(), [1]);
+    let zipper = zipper.previous_sibling().unwrap();
+    assert_eq!(zipper.path(), [0]);
+    assert!(zipper.previous_sibling().is_none());
+    assert!(zipper.next_sibling().unwrap().next_sibling().is_none());
+
+    let edited = zipper.set(10).insert_child(4).modify(|value| *value *= 10);
+    assert_eq!(*edited.value(), 40);
+    assert_eq!(edited.path(), [0, 1]);
+    let edited = edited
+        .parent()
+        .unwrap()
+        .next_sibling()
+        .unwrap()
+        .remove()
+        .unwrap();
+    assert!(edited.path().is_empty());
+    assert!(edited.clone().remove().is_none());
+
+    let values =
+        |zipper: &vec_tree::Zipper<i32>| zipper.tree().values().cloned().collect::<Vec<_>>();
+
+    // 0-10-3
+    //    |
+    //    40
+    assert_eq!(values(&edited), [0, 10, 3, 40]);
+    assert_eq!(values(&original), [0, 1, 3, 2]);
+    assert_eq!(
+        values(&edited.first_child().unwrap().root()),
+        [0, 10, 3, 40]
+    );
+
+    // Only the nodes on the path to the edited nodes were copied.
+    assert!(edited.tree().shares_subtree(&original.tree(), &[0, 0]));
+    assert!(!edited.tree().shares_subtree(&original.tree(), &[0]));
+    let sibling_edited = original.last_child().unwrap().set(20);
+    assert!(sibling_edited.tree().shares_subtree(&original.tree(), &[0]));
+
+    // Readers on other threads see their own version of the tree.
+    let reader = original.clone();
+    let values_read = std::thread::spawn(move || reader.tree().values().sum::<i32>());
+    assert_eq!(values_read.join().unwrap(), 6);
+
+    let tree = edited.into_tree();
+    let root_node = tree.get_root_index().unwrap();
+    assert_eq!(
+        tree.descendants(root_node)
+            .map(|node_id| tree[node_id])
+            .collect::<Vec<_>>(),
+        [0, 10, 3, 40]
+    );
+}
+
+#[test]
+fn zipper_deep_navigation() {
+    let mut tree = VecTree::new();
+    let mut node_id = tree.insert_root(0);
+    for value in 1..100_000 {
+        node_id = tree.insert(value, node_id);
+    }
+
+    // Moving down to the deepest node and editing it does not walk the path again at each step.
+    let mut zipper = tree.into_zipper().unwrap();
+    while let Some(child) = zipper.first_child() {
+        zipper = child;
+    }
+    assert_eq!(*zipper.value(), 99_999);
+    assert_eq!(zipper.path().len(), 99_999);
+
+    let edited = zipper.clone().set(-1).insert_child(-2);
+    assert_eq!(*edited.parent().unwrap().value(), -1);
+    assert_eq!(
+        format!("{:?}", edited.parent().unwrap().previous_sibling()),
+        "None"
+    );
+
+    let root = edited.root();
+    assert!(root.path().is_empty());
+    let deepest = vec![0; 100_000];
+    assert_eq!(root.tree().get(&deepest), Some(&-2));
+    assert_eq!(root.tree().get(&deepest[1..]), Some(&-1));
+    assert_eq!(zipper.root().tree().get(&deepest[1..]), Some(&99_999));
+
+    drop(root);
+    drop(edited);
+    drop(zipper);
+}
+
+#[test]
+fn get_pair_mut() {
+    let mut tree = VecTree::new();
+
+    let root_node = tree.insert_root(1);
+    let node_1 = tree.insert(2, root_node);
+    let node_2 = tree.insert(3, root_node);
+    tree.remove(node_2);
+
+    {
+        let (root, child) = tree.get_pair_mut(root_node, node_1);
+        let child = child.unwrap();
+        *child += *root.unwrap();
+    }
+    assert_eq!(tree[node_1], 3);
+
+    let (child, removed) = tree.get_pair_mut(node_1, node_2);
+    assert_eq!(child, Some(&mut 3));
+    assert!(removed.is_none());
+}
+
+#[test]
+fn get_disjoint_mut() {
+    let mut tree = VecTree::new();
+
+    // 0-1-3
+    // |
+    // 2
+    let root_node = tree.insert_root(0);
+    let node_1 = tree.insert(1, root_node);
+    let node_2 = tree.insert(2, root_node);
+    let node_3 = tree.insert(3, node_1);
+
+    {
+        let [node_3, root, node_1] = tree.get_disjoint_mut([node_3, root_node, node_1]).unwrap();
+        *root = *node_1 + *node_3;
+        *node_3 = 0;
+    }
+    assert_eq!(tree[root_node], 4);
+    assert_eq!(tree[node_3], 0);
+
+    assert_eq!(tree.get_disjoint_mut([]), Some([]));
+
+    tree.remove(node_2);
+    assert!(tree.get_disjoint_mut([root_node, node_2]).is_none());
+}
+
+#[test]
+#[should_panic]
+fn get_disjoint_mut_duplicate_indices() {
+    let mut tree = VecTree::new();
+    let root_node = tree.insert_root(0);
+    let node_1 = tree.insert(1, root_node);
+
+    tree.get_disjoint_mut([node_1, root_node, node_1]);
+}
+
+#[test]
+fn get_with_parent_mut() {
+    let mut tree = VecTree::new();
+
+    // 1-2-3
+    let root_node = tree.insert_root(1);
+    let node_1 = tree.insert(2, root_node);
+    let node_2 = tree.insert(3, node_1);
+
+    for node_id in [node_1, node_2] {
+        let (data, parent) = tree.get_with_parent_mut(node_id).unwrap();
+        *data += *parent.unwrap();
+    }
+    assert_eq!(tree[node_1], 3);
+    assert_eq!(tree[node_2], 6);
+
+    let (root, parent) = tree.get_with_parent_mut(root_node).unwrap();
+    assert_eq!(*root, 1);
+    assert!(parent.is_none());
+
+    tree.remove(node_2);
+    assert!(tree.get_with_parent_mut(node_2).is_none());
+}
+
+#[test]
+fn split_subtrees_mut() {
+    let mut tree = VecTree::new();
+
+    // 0-1-3
+    // |
+    // 2-4
+    //   |
+    //   5
+    let root_node = tree.insert_root(0);
+    let node_1 = tree.insert(1, root_node);
+    let node_2 = tree.insert(2, root_node);
+    let node_3 = tree.insert(3, node_1);
+    let node_4 = tree.insert(4, node_2);
+    let node_5 = tree.insert(5, node_2);
+
+    {
+        let (mut left, mut right) = tree.split_subtrees_mut(node_2, node_1);
+        assert_eq!(left.root(), node_2);
+        assert_eq!(right.root(), node_1);
+        assert!(!left.contains(node_3));
+        assert!(right.get_mut(node_4).is_none());
+
+        let total = left.iter().map(|(_, value)| *value).sum::<i32>();
+        for (_, value) in right.iter_mut() {
+            *value += total;
+        }
+        left[node_5] = 0;
+    }
+
+    assert_eq!(
+        tree.descendants(root_node)
+            .map(|node_id| tree[node_id])
+            .collect::<Vec<_>>(),
+        [0, 12, 14, 2, 4, 0]
+    );
+}
+
+#[test]
+#[should_panic]
+fn split_subtrees_mut_overlapping() {
+    let mut tree = VecTree::new();
+    let root_node = tree.insert_root(0);
+    let node_1 = tree.insert(1, root_node);
+
+    tree.split_subtrees_mut(node_1, root_node);
+}
+
+#[test]
+fn tagged_tree() {
+    use vec_tree::{TaggedVecTree, TypedIndex};
+
+    enum Scene {}
+
+    let mut tree = TaggedVecTree::<_, Scene>::new();
+
+    // 0-1-3
+    // |
+    // 2
+    let root_node = tree.insert_root(0);
+    let node_1 = tree.insert(1, root_node);
+    let node_2 = tree.insert(2, root_node);
+    let node_3: TypedIndex<Scene> = tree.insert(3, node_1);
+
+    assert_eq!(tree.get_root_index(), Some(root_node));
+    assert_eq!(tree.parent(node_3), Some(node_1));
+    assert_eq!(tree.first_child(root_node), Some(node_1));
+    assert_eq!(tree.last_child(root_node), Some(node_2));
+    assert_eq!(tree.next_sibling(node_1), Some(node_2));
+    assert_eq!(tree.previous_sibling(node_1), None);
+    assert_eq!(
+        tree.children(root_node).rev().collect::<Vec<_>>(),
+        [node_2, node_1]
+    );
+    assert_eq!(
+        tree.ancestors(node_3).collect::<Vec<_>>(),
+        [node_3, node_1, root_node]
+    );
+
+    tree[node_3] = 30;
+    *tree.get_mut(node_2).unwrap() = 20;
+    tree.append_child(node_2, node_1);
+    assert_eq!(
+        tree.descendants(root_node)
+            .map(|node_id| tree[node_id])
+            .collect::<Vec<_>>(),
+        [0, 20, 1, 30]
+    );
+    assert_eq!(tree.descendants(root_node).count(), tree.iter().count());
+
+    assert_eq!(tree.remove(node_1), Some(1));
+    assert!(!tree.contains(node_3));
+    assert!(tree.get(node_3).is_none());
+
+    let untyped = tree.into_inner();
+    assert_eq!(untyped[node_2.index()], 20);
+}
+
+#[test]
+fn checked_node_ids() {
+    let mut tree = VecTree::new();
+
+    // 0-1
+    // |
+    // 2
+    let root_node = tree.insert_root(0);
+    let node_1 = tree.insert(1, root_node);
+    let node_2 = tree.insert(2, root_node);
+
+    let root_id = tree.node_id(root_node);
+    let node_1_id = tree.node_id(node_1);
+    let node_2_id = tree.node_id(node_2);
+    assert_eq!(node_1_id.index(), node_1);
+    assert_eq!(node_1_id.tree_id(), tree.tree_id());
+
+    tree[node_2_id] = 20;
+    *tree.get_mut_checked(node_1_id).unwrap() = 10;
+    tree.append_child_checked(node_1_id, node_2_id);
+    assert_eq!(
+        tree.descendants(root_node)
+            .map(|node_id| tree[node_id])
+            .collect::<Vec<_>>(),
+        [0, 10, 20]
+    );
+
+    let copy = tree.clone();
+    assert_eq!(copy.tree_id(), tree.tree_id());
+    assert_eq!(copy.get_checked(node_2_id), Some(&20));
+
+    let mut other_tree = VecTree::new();
+    let other_root = other_tree.insert_root(0);
+    assert_ne!(other_tree.tree_id(), tree.tree_id());
+    assert_eq!(other_root, root_node);
+
+    tree.remove(node_1);
+    assert!(tree.get_checked(node_2_id).is_none());
+    assert_eq!(tree.get_checked(root_id), Some(&0));
+}
+
+#[test]
+#[should_panic]
+fn checked_node_id_from_another_tree() {
+    let mut tree = VecTree::new();
+    let root_node = tree.insert_root(0);
+    let root_id = tree.node_id(root_node);
+
+    let mut other_tree = VecTree::new();
+    other_tree.insert_root(1);
+    other_tree.get_checked(root_id);
+}
+
+#[test]
+fn index_introspection() {
+    let mut tree = VecTree::with_capacity(4);
+
+    let root_node = tree.insert_root(0);
+    let node_1 = tree.insert(1, root_node);
+    let node_2 = tree.insert(2, node_1);
+    assert_eq!(
+        tree.current_index(vec_tree::index_slot(node_2)),
+        Some(node_2)
+    );
+
+    tree.remove(node_1);
+    assert!(tree.is_stale(node_1));
+    assert!(tree.is_stale(node_2));
+    assert!(!tree.is_stale(root_node));
+    assert_eq!(tree.current_index(vec_tree::index_slot(node_1)), None);
+
+    let node_3 = tree.insert(3, root_node);
+    let replaced = *[node_1, node_2]
+        .iter()
+        .find(|&&node_id| tree.current_index(vec_tree::index_slot(node_id)) == Some(node_3))
+        .unwrap();
+    assert_eq!(vec_tree::index_slot(replaced), vec_tree::index_slot(node_3));
+    assert!(vec_tree::index_generation(node_3) > vec_tree::index_generation(replaced));
+    assert!(tree.is_stale(replaced));
+
+    let mut other_tree = VecTree::with_capacity(16);
+    let other_root = other_tree.insert_root(0);
+    for i in 1..16 {
+        other_tree.insert(i, other_root);
+    }
+    let foreign = other_tree.current_index(15).unwrap();
+    assert!(!tree.is_stale(foreign));
+    assert!(!tree.contains(foreign));
+}
+
+#[test]
+fn clone_keeps_indices() {
+    let mut tree = VecTree::new();
+
+    // 0-1-3
+    // |
+    // 2
+    let root_node = tree.insert_root(0);
+    let node_1 = tree.insert(1, root_node);
+    let node_2 = tree.insert(2, root_node);
+    let node_3 = tree.insert(3, node_1);
+    let removed = tree.insert(4, node_2);
+    tree.remove(removed);
+
+    let mut copy = tree.clone();
+    assert_eq!(copy.get_root_index(), Some(root_node));
+    assert!(!copy.contains(removed));
+    for node_id in tree.descendants(root_node) {
+        assert_eq!(copy[node_id], tree[node_id]);
+        assert_eq!(copy.parent(node_id), tree.parent(node_id));
+        assert_eq!(
+            copy.children(node_id).collect::<Vec<_>>(),
+            tree.children(node_id).collect::<Vec<_>>()
+        );
+    }
+
+    copy.remove(node_1);
+    let node_4 = copy.insert(4, node_2);
+    copy[node_2] = 20;
+    assert_eq!(copy[node_4], 4);
+    assert_eq!(tree[node_3], 3);
+    assert_eq!(tree[node_2], 2);
+    assert!(!tree.contains(node_4));
+}
+
+#[test]
+fn structural_eq() {
+    assert_eq!(VecTree::<i32>::new(), VecTree::new());
+
+    // 0-1-3
+    // |
+    // 2
+    let mut expected = VecTree::new();
+    let root_node = expected.insert_root(0);
+    let node_1 = expected.insert(1, root_node);
+    expected.insert(2, root_node);
+    expected.insert(3, node_1);
+    assert_ne!(expected, VecTree::new());
+
+    let mut actual = VecTree::with_capacity(1);
+    let root_node = actual.insert_root(0);
+    let node_2 = actual.insert(2, root_node);
+    let node_1 = actual.insert(1, root_node);
+    actual.insert(3, node_2);
+    assert_ne!(expected, actual);
+
+    actual.remove(node_2);
+    let node_2 = actual.insert(2, root_node);
+    assert_ne!(expected, actual);
+
+    let node_3 = actual.insert(3, node_1);
+    assert_eq!(expected, actual);
+    assert_eq!(actual, actual.clone());
+
+    // Same values in tree order, different shape.
+    actual.append_child(node_3, node_2);
+    let values = |tree: &VecTree<i32>| {
+        tree.descendants(tree.get_root_index().unwrap())
+            .map(|node_id| tree[node_id])
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(values(&expected), values(&actual));
+    assert_ne!(expected, actual);
+}
+
+#[test]
+fn tree_hash() {
+    use std::collections::hash_map::DefaultHasher;
+    use std::collections::HashSet;
+    use std::hash::{Hash, Hasher};
+
+    fn hash_of(tree: &VecTree<i32>) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        tree.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    // 0-1-3
+    // |
+    // 2
+    let mut tree = VecTree::new();
+    let root_node = tree.insert_root(0);
+    let node_1 = tree.insert(1, root_node);
+    let node_2 = tree.insert(2, root_node);
+    let node_3 = tree.insert(3, node_1);
+
+    let mut same = VecTree::new();
+    let root = same.insert_root(0);
+    let removed = same.insert(4, root);
+    same.remove(removed);
+    let child = same.insert(1, root);
+    same.insert(2, root);
+    same.insert(3, child);
+
+    assert_eq!(tree, same);
+    assert_eq!(hash_of(&tree), hash_of(&same));
+    assert_ne!(hash_of(&tree), hash_of(&VecTree::new()));
+
+    // Same values in tree order, different shape.
+    let mut reshaped = tree.clone();
+    reshaped.append_child(node_3, node_2);
+    assert_ne!(hash_of(&tree), hash_of(&reshaped));
+
+    let mut seen = HashSet::new();
+    assert!(seen.insert(tree.clone()));
+    assert!(!seen.insert(same));
+    assert!(seen.insert(reshaped));
+
+    // 0-1-3
+    // |
+    // 1-3
+    tree[node_2] = 1;
+    tree.insert(3, node_2);
+    assert_eq!(tree.subtree_hash(node_1), tree.subtree_hash(node_2));
+    assert_ne!(tree.subtree_hash(root_node), tree.subtree_hash(node_1));
+
+    tree.insert(5, node_2);
+    assert_ne!(tree.subtree_hash(node_1), tree.subtree_hash(node_2));
+}
+
+#[test]
+fn display() {
+    let mut tree = VecTree::new();
+    assert_eq!(tree.to_string(), "");
+
+    let root_node = tree.insert_root(0);
+    assert_eq!(tree.to_string(), "0");
+
+    // 0-1-3-4
+    // | |
+    // | 5
+    // |
+    // 2-6
+    let node_1 = tree.insert(1, root_node);
+    let node_2 = tree.insert(2, root_node);
+    let node_3 = tree.insert(3, node_1);
+    tree.insert(4, node_3);
+    tree.insert(5, node_1);
+    tree.insert(6, node_2);
+
+    assert_eq!(
+        tree.to_string(),
+        [
+            "0",
+            "├── 1",
+            "│   ├── 3",
+            "│   │   └── 4",
+            "│   └── 5",
+            "└── 2",
+            "    └── 6",
+        ]
+        .join("\n")
+    );
+}
+
+#[test]
+fn debug() {
+    let mut tree = VecTree::new();
+    assert_eq!(format!("{:?}", tree), "VecTree");
+
+    // 0-1-3
+    // |
+    // 2
+    let root_node = tree.insert_root("0");
+    let node_1 = tree.insert("1", root_node);
+    tree.insert("2", root_node);
+    tree.insert("3", node_1);
+
+    assert_eq!(
+        format!("{:?}", tree),
+        r#"VecTree({"0": [{"1": ["3"]}, "2"]})"#
+    );
+    assert_eq!(
+        format!("{:#?}", tree),
+        r#"VecTree(
+    {
+        "0": [
+            {
+                "1": [
+                    "3",
+                ],
+            },
+            "2",
+        ],
+    },
+)"#
+    );
+
+    let flat = format!("{:?}", tree.debug_flat());
+    assert!(flat.starts_with("VecTree { nodes: "));
+    assert!(flat.contains(&format!("root_index: Some({:?})", root_node)));
+
+    // Deep trees are shown flat rather than overflowing the stack.
+    let mut tree = VecTree::new();
+    let mut node_id = tree.insert_root(0);
+    for value in 1..=64 {
+        node_id = tree.insert(value, node_id);
+    }
+    assert!(format!("{:?}", tree).starts_with("VecTree({0: [{1: "));
+    for value in 65..20_000 {
+        node_id = tree.insert(value, node_id);
+    }
+    assert!(format!("{:?}", tree).starts_with("VecTree { nodes: "));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_structural() {
+    use serde_test::{assert_de_tokens_error, assert_tokens, Token};
+
+    let mut tree = VecTree::new();
+    assert_tokens(&tree, &[Token::Seq { len: Some(0) }, Token::SeqEnd]);
+
+    // 0-1-3
+    // |
+    // 2
+    let root_node = tree.insert_root(0);
+    let removed = tree.insert(4, root_node);
+    tree.remove(removed);
+    let node_1 = tree.insert(1, root_node);
+    tree.insert(2, root_node);
+    tree.insert(3, node_1);
+
+    let node = |data, children| {
+        [
+            Token::Tuple { len: 2 },
+            Token::I32(data),
+            Token::U64(children),
+            Token::TupleEnd,
+        ]
+    };
+    let mut tokens = vec![Token::Seq { len: Some(4) }];
+    tokens.extend(&node(0, 2));
+    tokens.extend(&node(1, 1));
+    tokens.extend(&node(3, 0));
+    tokens.extend(&node(2, 0));
+    tokens.push(Token::SeqEnd);
+
+    // The deserialized tree is compared structurally.
+    assert_tokens(&tree, &tokens);
+
+    let mut tokens = vec![Token::Seq { len: Some(2) }];
+    tokens.extend(&node(0, 0));
+    tokens.extend(&node(1, 0));
+    tokens.push(Token::SeqEnd);
+    assert_de_tokens_error::<VecTree<i32>>(&tokens, "more nodes than children");
+
+    let mut tokens = vec![Token::Seq { len: Some(1) }];
+    tokens.extend(&node(0, 1));
+    tokens.push(Token::SeqEnd);
+    assert_de_tokens_error::<VecTree<i32>>(&tokens, "fewer nodes than children");
+
+    // Deep trees do not reach the recursion limit of the format.
+    let mut tree = VecTree::new();
+    let mut node_id = tree.insert_root(0);
+    for value in 1..10_000 {
+        node_id = tree.insert(value, node_id);
+    }
+    let json = serde_json::to_string(&tree).unwrap();
+    assert_eq!(serde_json::from_str::<VecTree<i32>>(&json).unwrap(), tree);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_with_indices() {
+    use serde_test::{assert_ser_tokens, Token};
+    use vec_tree::RemappedVecTree;
+
+    // 0-1-3
+    // |
+    // 2
+    let mut tree = VecTree::new();
+    let root_node = tree.insert_root(0);
+    let removed = tree.insert(4, root_node);
+    tree.remove(removed);
+    let node_1 = tree.insert(1, root_node);
+    let node_2 = tree.insert(2, root_node);
+    let node_3 = tree.insert(3, node_1);
+
+    let node = |node_id: vec_tree::Index, data, children| {
+        let (slot, generation) = node_id.into_raw_parts();
+        [
+            Token::Tuple { len: 3 },
+            Token::Tuple { len: 2 },
+            Token::U64(slot as u64),
+            Token::U64(generation),
+            Token::TupleEnd,
+            Token::I32(data),
+            Token::U64(children),
+            Token::TupleEnd,
+        ]
+    };
+    let mut tokens = vec![Token::Seq { len: Some(4) }];
+    tokens.extend(&node(root_node, 0, 2));
+    tokens.extend(&node(node_1, 1, 1));
+    tokens.extend(&node(node_3, 3, 0));
+    tokens.extend(&node(node_2, 2, 0));
+    tokens.push(Token::SeqEnd);
+    assert_ser_tokens(&tree.with_indices(), &tokens);
+
+    let bytes = bincode::serialize(&tree.with_indices()).unwrap();
+    let remapped: RemappedVecTree<i32> = bincode::deserialize(&bytes).unwrap();
+    assert_eq!(*remapped.tree(), tree);
+    for &node_id in &[root_node, node_1, node_2, node_3] {
+        assert_eq!(
+            remapped.tree()[remapped.remap(node_id).unwrap()],
+            tree[node_id]
+        );
+    }
+    assert!(remapped.remap(removed).is_none());
+
+    let (new_tree, remap) = remapped.into_parts();
+    assert_eq!(remap.len(), 4);
+    assert_eq!(new_tree.parent(remap[&node_3]), Some(remap[&node_1]));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_indices_in_user_types() {
+    use serde::{Deserialize, Serialize};
+    use vec_tree::{TaggedVecTree, TypedIndex};
+
+    enum Scene {}
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Selection {
+        node: vec_tree::Index,
+        typed: TypedIndex<Scene>,
+        edge: NodeEdge<vec_tree::Index>,
+    }
+
+    let mut tree = VecTree::new();
+    let root_node = tree.insert_root(0);
+    let removed = tree.insert(1, root_node);
+    tree.remove(removed);
+    let node_1 = tree.insert(1, root_node);
+
+    let mut scene = TaggedVecTree::<_, Scene>::new();
+    let camera = scene.insert_root("camera");
+
+    let selection = Selection {
+        node: node_1,
+        typed: camera,
+        edge: NodeEdge::End(root_node),
+    };
+
+    let bytes = bincode::serialize(&selection).unwrap();
+    let restored: Selection = bincode::deserialize(&bytes).unwrap();
+    assert_eq!(restored, selection);
+    assert_eq!(tree[restored.node], 1);
+    assert_eq!(tree[tree.node_id(restored.node)], 1);
+    assert_eq!(scene[restored.typed], "camera");
+    assert!(!tree.contains(removed));
+}
+
+#[cfg(feature = "arbitrary")]
+#[test]
+fn arbitrary_trees() {
+    use arbitrary::{Arbitrary, Unstructured};
+
+    assert_eq!(
+        VecTree::<u8>::arbitrary(&mut Unstructured::new(&[])).unwrap(),
+        VecTree::new()
+    );
+
+    let mut sizes = Vec::new();
+    for seed in 0..64u32 {
+        let bytes = (0..256u32)
+            .map(|i| (i.wrapping_mul(2_654_435_761).wrapping_add(seed * 97) >> 13) as u8)
+            .collect::<Vec<_>>();
+        let tree = VecTree::<u8>::arbitrary(&mut Unstructured::new(&bytes)).unwrap();
+
+        let size = tree.iter().count();
+        match tree.get_root_index() {
+            Some(root_node) => {
+                assert_eq!(tree.descendants(root_node).count(), size);
+                for node_id in tree.descendants(root_node).skip(1) {
+                    assert!(tree.is_ancestor_of(root_node, node_id));
+                }
+            }
+            None => assert_eq!(size, 0),
+        }
+        sizes.push(size);
+    }
+
+    assert!(sizes.iter().any(|&size| size > 2));
+}
+
+#[cfg(feature = "proptest")]
+#[test]
+fn proptest_trees() {
+    use proptest::prelude::*;
+    use proptest::test_runner::{TestCaseError, TestError, TestRunner};
+
+    let mut runner = TestRunner::deterministic();
+    runner
+        .run(&vec_tree::proptest::tree(any::<u8>(), 1..32), |tree| {
+            let root_node = tree.get_root_index().unwrap();
+            let size = tree.iter().count();
+            prop_assert!((1..32).contains(&size));
+            prop_assert_eq!(tree.descendants(root_node).count(), size);
+            Ok(())
+        })
+        .unwrap();
+
+    // Shrinks to a minimal tree with more than 4 nodes, or with a node holding at least 100.
+    let mut runner = TestRunner::deterministic();
+    let result = runner.run(&vec_tree::proptest::tree(any::<u8>(), 2..64), |tree| {
+        if tree.iter().count() > 4 || tree.values().any(|&value| value >= 100) {
+            return Err(TestCaseError::fail("too big"));
+        }
+        Ok(())
+    });
+    let minimal = match result {
+        Err(TestError::Fail(_, minimal)) => minimal,
+        result => panic!("unexpected result {:?}", result),
+    };
+
+    let size = minimal.iter().count();
+    let large_values = minimal.values().filter(|&&value| value >= 100).count();
+    if large_values == 0 {
+        assert_eq!(size, 5);
+        assert!(minimal.values().all(|&value| value == 0));
+    } else {
+        assert_eq!(size, 2);
+        assert_eq!(large_values, 1);
+        assert!(minimal.values().all(|&value| value == 0 || value == 100));
+    }
+}
+
+#[cfg(feature = "quickcheck")]
+#[test]
+fn quickcheck_trees() {
+    use quickcheck::{Arbitrary, QuickCheck};
+
+    fn descendants_visit_every_node(tree: VecTree<u8>) -> bool {
+        match tree.get_root_index() {
+            Some(root_node) => tree.descendants(root_node).count() == tree.iter().count(),
+            None => tree.iter().count() == 0,
+        }
+    }
+    QuickCheck::new().quickcheck(descendants_visit_every_node as fn(VecTree<u8>) -> bool);
+
+    // 1-0
+    // |
+    // 2
+    let mut tree = VecTree::new();
+    let root_node = tree.insert_root(1u8);
+    let node_1 = tree.insert(0, root_node);
+    let node_2 = tree.insert(2, root_node);
+
+    let shrunk = tree.shrink().collect::<Vec<_>>();
+    assert_eq!(shrunk[0], VecTree::new());
+    assert!(!shrunk[1].contains(node_1));
+    assert_eq!(shrunk[1][node_2], 2);
+    assert!(!shrunk[2].contains(node_2));
+    assert!(shrunk[3..].iter().all(|shrunk| {
+        shrunk.iter().count() == 3 && shrunk[root_node] <= 1 && shrunk[node_2] <= 2
+    }));
+    assert!(shrunk[3..].iter().any(|shrunk| shrunk[root_node] == 0));
+    assert!(shrunk[3..].iter().any(|shrunk| shrunk[node_2] == 0));
+}
+
+#[test]
+fn frozen_tree() {
+    use std::thread;
+    use vec_tree::FrozenVecTree;
+
+    fn assert_send_sync<S: Send + Sync>() {}
+    assert_send_sync::<FrozenVecTree<String>>();
+
+    // 0-1-3
+    // |
+    // 2
+    let mut tree = VecTree::new();
+    let root_node = tree.insert_root(0);
+    let node_1 = tree.insert(1, root_node);
+    tree.insert(2, root_node);
+    let node_3 = tree.insert(3, node_1);
+
+    let frozen = tree.freeze();
+    let copy = frozen.clone();
+    let handle = thread::spawn(move || {
+        copy.descendants(root_node)
+            .map(|node_id| copy[node_id])
+            .collect::<Vec<_>>()
+    });
+    assert_eq!(handle.join().unwrap(), [0, 1, 3, 2]);
+    assert_eq!(frozen.parent(node_3), Some(node_1));
+
+    let kept = frozen.clone();
+    let mut tree = frozen.thaw();
+    tree[node_3] = 30;
+    assert_eq!(kept[node_3], 3);
+
+    let frozen = FrozenVecTree::from(tree);
+    assert_eq!(frozen.thaw()[node_3], 30);
+}
+
+#[test]
+fn sync_tree() {
+    use std::sync::Arc;
+    use std::thread;
+    use vec_tree::SyncVecTree;
+
+    let tree = Arc::new(SyncVecTree::default());
+    let root_node = tree.insert_root(0);
+    let node_1 = tree.insert(1, root_node);
+
+    let workers = (0..4)
+        .map(|i| {
+            let tree = Arc::clone(&tree);
+            thread::spawn(move || {
+                let node_id = tree.insert(10 + i, node_1);
+                tree.modify(node_id, |value| *value *= 10);
+                let reader = tree.read();
+                assert!(reader.is_ancestor_of(root_node, node_id));
+                node_id
+            })
+        })
+        .collect::<Vec<_>>();
+    let node_ids = workers
+        .into_iter()
+        .map(|worker| worker.join().unwrap())
+        .collect::<Vec<_>>();
+
+    {
+        let reader = tree.read();
+        let mut values = reader
+            .children(node_1)
+            .map(|node_id| reader[node_id])
+            .collect::<Vec<_>>();
+        values.sort_unstable();
+        assert_eq!(values, [100, 110, 120, 130]);
+    }
+
+    {
+        let mut writer = tree.write();
+        let node_2 = writer.insert(2, root_node);
+        writer.append_child(node_2, node_ids[0]);
+    }
+    assert_eq!(tree.get_cloned(node_ids[0]), Some(100));
+    assert_eq!(tree.remove(node_1), Some(1));
+    assert_eq!(tree.get_cloned(node_ids[1]), None);
+    assert_eq!(tree.modify(node_ids[1], |value| *value += 1), None);
+
+    let tree = Arc::try_unwrap(tree).unwrap().into_inner();
+    assert_eq!(
+        tree.descendants(root_node)
+            .map(|node_id| tree[node_id])
+            .collect::<Vec<_>>(),
+        [0, 2, 100]
+    );
+}
+
+#[test]
+fn persistent_tree() {
+    use vec_tree::PersistentVecTree;
+
+    // 0-1-3
+    // |
+    // 2
+    let mut tree = VecTree::new();
+    let root_node = tree.insert_root(0);
+    let node_1 = tree.insert(1, root_node);
+    tree.insert(2, root_node);
+    tree.insert(3, node_1);
+
+    let mut persistent = PersistentVecTree::from(&tree);
+    assert_eq!(
+        persistent.values().cloned().collect::<Vec<_>>(),
+        [0, 1, 3, 2]
+    );
+    assert_eq!(persistent.to_vec_tree(), tree);
+    assert_eq!(persistent.child_count(&[]), Some(2));
+    assert_eq!(persistent.get(&[0, 0]), Some(&3));
+    assert_eq!(persistent.get(&[0, 1]), None);
+
+    let version_1 = persistent.clone();
+    assert_eq!(persistent.insert(&[0], 4), [0, 1]);
+    assert_eq!(persistent.set(&[1], 20), Some(2));
+    assert_eq!(persistent.set(&[2], 20), None);
+
+    let version_2 = persistent.clone();
+    assert_eq!(persistent.remove(&[0, 0]), Some(3));
+    assert_eq!(persistent.remove(&[0, 1]), None);
+
+    // 0-1-4    0-1-3-4    0-1-3
+    // |        |          |
+    // 20       20         2
+    assert_eq!(
+        persistent.values().cloned().collect::<Vec<_>>(),
+        [0, 1, 4, 20]
+    );
+    assert_eq!(
+        version_2.values().cloned().collect::<Vec<_>>(),
+        [0, 1, 3, 4, 20]
+    );
+    assert_eq!(
+        version_1.values().cloned().collect::<Vec<_>>(),
+        [0, 1, 3, 2]
+    );
+    assert_eq!(version_1.to_vec_tree(), tree);
+
+    assert!(version_2.shares_subtree(&version_1, &[0, 0]));
+    assert!(persistent.shares_subtree(&version_2, &[1]));
+    assert!(!persistent.shares_subtree(&version_2, &[0]));
+    assert!(!persistent.shares_subtree(&version_1, &[]));
+
+    assert_eq!(persistent.remove(&[]), Some(0));
+    assert!(persistent.is_empty());
+    assert_eq!(persistent, PersistentVecTree::new());
+    assert_eq!(persistent.to_vec_tree(), VecTree::new());
+    persistent.insert_root(5);
+    assert_eq!(persistent.get(&[]), Some(&5));
+}
+
+#[test]
+fn persistent_deep_tree() {
+    use vec_tree::PersistentVecTree;
+
+    // Deep trees are compared, shown and dropped without overflowing the stack.
+    let mut tree = VecTree::new();
+    let mut node_id = tree.insert_root(0);
+    for value in 1..1_000_000 {
+        node_id = tree.insert(value, node_id);
+    }
+
+    let persistent = PersistentVecTree::from(&tree);
+    let mut version = PersistentVecTree::from(tree);
+    assert_eq!(version, persistent);
+
+    let deepest = vec![0; 999_999];
+    *version.get_mut(&deepest).unwrap() = 0;
+    assert_ne!(version, persistent);
+    assert!(format!("{:?}", version).starts_with("PersistentVecTree([(0, 0), (1, 1), "));
+
+    drop(persistent);
+    drop(version);
+}
+
+#[test]
+fn new_does_not_allocate() {
+    let mut tree = VecTree::new();
+    assert_eq!(tree.capacity(), 0);
+    assert_eq!(VecTree::<usize>::default().capacity(), 0);
+    assert_eq!(VecTree::<usize>::with_capacity(0).capacity(), 0);
+
+    assert_eq!(tree.iter().count(), 0);
+    assert_eq!(tree.values_mut().count(), 0);
+    assert!(tree.try_insert_root(0).is_err());
+    tree.clear();
+    assert_eq!(tree.capacity(), 0);
+
+    let root = tree.insert_root(0);
+    tree.insert(1, root);
+    assert!(tree.capacity() >= 2);
+    assert_eq!(tree.values().cloned().collect::<Vec<_>>(), [0, 1]);
+
+    let mut tree = VecTree::new();
+    tree.reserve(3);
+    assert_eq!(tree.capacity(), 3);
+    let root = tree.try_insert_root(0).unwrap();
+    assert!(tree.try_insert(1, root).is_ok());
+}
+
+#[test]
+fn index_raw_parts() {
+    let mut tree = VecTree::new();
+    let root = tree.insert_root(0);
+    let child = tree.insert(1, root);
+    tree.remove(child);
+    let new_child = tree.insert(2, root);
+
+    let (slot, generation) = vec_tree::index_to_raw_parts(new_child);
+    assert_eq!(slot, vec_tree::index_slot(new_child));
+    assert_eq!(generation, vec_tree::index_generation(new_child));
+    assert_eq!(vec_tree::index_from_raw_parts(slot, generation), new_child);
+
+    let (slot, generation) = vec_tree::index_to_raw_parts(child);
+    let stale = vec_tree::index_from_raw_parts(slot, generation);
+    assert_eq!(stale, child);
+    assert!(!tree.contains(stale));
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn json_value() {
+    let mut tree = VecTree::new();
+    assert!(tree.to_json_value().unwrap().is_null());
+
+    // 0-1-3
+    // |
+    // 2
+    let root_node = tree.insert_root(0);
+    let node_1 = tree.insert(1, root_node);
+    tree.insert(2, root_node);
+    tree.insert(3, node_1);
+
+    let value = tree.to_json_value().unwrap();
+    assert_eq!(
+        value.to_string(),
+        concat!(
+            r#"{"children":[{"children":[{"children":[],"value":3}],"value":1},"#,
+            r#"{"children":[],"value":2}],"value":0}"#
+        )
+    );
+    assert_eq!(VecTree::<i32>::from_json_value(value).unwrap(), tree);
+
+    let empty = VecTree::<i32>::new().to_json_value().unwrap();
+    assert!(VecTree::<i32>::from_json_value(empty)
+        .unwrap()
+        .get_root_index()
+        .is_none());
+
+    let mut strings = VecTree::new();
+    strings.insert_root("0");
+    let strings = strings.to_json_value().unwrap();
+    assert!(VecTree::<i32>::from_json_value(strings).is_err());
+
+    let children = tree.to_json_value().unwrap()["children"].clone();
+    assert!(VecTree::<i32>::from_json_value(children).is_err());
+}
+
+#[test]
+fn binary_format() {
+    use std::io::{self, Read, Write};
+
+    fn write_u8<W: Write>(writer: &mut W, data: &u8) -> io::Result<()> {
+        writer.write_all(&[*data])
+    }
+    fn read_u8<R: Read>(reader: &mut R) -> io::Result<u8> {
+        let mut data = [0];
+        reader.read_exact(&mut data)?;
+        Ok(data[0])
+    }
+
+    let mut bytes = Vec::new();
+    VecTree::new().write_to(&mut bytes, write_u8).unwrap();
+    assert_eq!(bytes, b"VTRE\x01\x00");
+    assert_eq!(
+        VecTree::read_from(&bytes[..], read_u8).unwrap(),
+        VecTree::new()
+    );
+
+    // 0-1-3
+    // |
+    // 2-(200 children)
+    let mut tree = VecTree::new();
+    let root_node = tree.insert_root(0);
+    let node_1 = tree.insert(1, root_node);
+    let node_2 = tree.insert(2, root_node);
+    tree.insert(3, node_1);
+    for i in 0..200 {
+        tree.insert(i, node_2);
+    }
+
+    let mut bytes = Vec::new();
+    tree.write_to(&mut bytes, write_u8).unwrap();
+    // The header, 204 nodes, then the root (2 children, 0), node 1 (1 child, 1) and node 3.
+    assert_eq!(&bytes[..13], b"VTRE\x01\xcc\x01\x02\x00\x01\x01\x00\x03");
+    assert_eq!(VecTree::read_from(&bytes[..], read_u8).unwrap(), tree);
+
+    let invalid_data = |bytes: &[u8]| {
+        VecTree::read_from(bytes, read_u8).unwrap_err().kind() == io::ErrorKind::InvalidData
+    };
+    assert!(invalid_data(b"VTREE\x00"));
+    assert!(invalid_data(b"VTRE\x02\x00"));
+    // The root has one child, but two are written.
+    assert!(invalid_data(b"VTRE\x01\x03\x01\x00\x00\x01\x00\x02"));
+    // The root has two children, but only one is written.
+    assert!(invalid_data(b"VTRE\x01\x02\x02\x00\x00\x01"));
+
+    let truncated = VecTree::read_from(&bytes[..bytes.len() - 1], read_u8).unwrap_err();
+    assert_eq!(truncated.kind(), io::ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn indented_outline() {
+    use vec_tree::ParseIndentErrorKind;
+
+    assert_eq!(VecTree::from_indented_str("", 2).unwrap(), VecTree::new());
+    assert_eq!(VecTree::<String>::new().to_indented_string(2), "");
+
+    let tree = VecTree::from_indented_str("0\n\n    1\n        3\n  \n    2\r\n", 4).unwrap();
+    let root = tree.get_root_index().unwrap();
+    assert_eq!(
+        tree.descendants(root)
+            .map(|node_id| tree[node_id].as_str())
+            .collect::<Vec<_>>(),
+        ["0", "1", "3", "2"]
+    );
+    assert_eq!(tree.to_indented_string(1), "0\n 1\n  3\n 2\n");
+    assert_eq!(
+        VecTree::from_indented_str(&tree.to_indented_string(1), 1).unwrap(),
+        tree
+    );
+
+    let error = |s| VecTree::from_indented_str(s, 2).unwrap_err();
+    assert_eq!(error("0\n   1").kind(), ParseIndentErrorKind::InvalidIndent);
+    assert_eq!(error("0\n\t1").kind(), ParseIndentErrorKind::InvalidIndent);
+    assert_eq!(error("  0").kind(), ParseIndentErrorKind::TooDeep);
+    assert_eq!(
+        error("0\n  1\n      2").kind(),
+        ParseIndentErrorKind::TooDeep
+    );
+    assert_eq!(
+        error("0\n  1\n2").kind(),
+        ParseIndentErrorKind::SeveralRoots
+    );
+    assert_eq!(error("0\n  1\n\n2").line(), 4);
+    assert_eq!(
+        error("0\n  1\n\n2").to_string(),
+        "several unindented lines at line 4"
+    );
+}
+
+#[test]
+#[should_panic]
+fn indented_outline_zero_width() {
+    let _ = VecTree::from_indented_str("0", 0);
+}
+
+#[cfg(feature = "xml")]
+#[test]
+fn xml_document() {
+    use vec_tree::{XmlError, XmlNode};
+
+    let document = r#"<?xml version="1.0"?>
+<!-- The list -->
+<list kind="a &amp; b">
+    <item done="true">milk &lt;2L&gt;</item>
+    <item><![CDATA[<eggs>]]><!-- twelve --></item>
+    <empty/>
+</list>
+"#;
+
+    let tree = VecTree::from_xml_str(document).unwrap();
+    let root = tree.get_root_index().unwrap();
+    assert_eq!(
+        tree[root],
+        XmlNode::Element {
+            name: "list".to_string(),
+            attributes: vec![("kind".to_string(), "a & b".to_string())],
+        }
+    );
+    assert_eq!(
+        tree.descendants(root)
+            .skip(1)
+            .map(|node_id| &tree[node_id])
+            .collect::<Vec<_>>(),
+        [
+            &XmlNode::Element {
+                name: "item".to_string(),
+                attributes: vec![("done".to_string(), "true".to_string())],
+            },
+            &XmlNode::Text("milk <2L>".to_string()),
+            &XmlNode::element("item"),
+            &XmlNode::CData("<eggs>".to_string()),
+            &XmlNode::Comment(" twelve ".to_string()),
+            &XmlNode::element("empty"),
+        ]
+    );
+
+    let xml = tree.to_xml_string();
+    assert_eq!(
+        xml,
+        concat!(
+            r#"<list kind="a &amp; b"><item done="true">milk &lt;2L&gt;</item>"#,
+            r#"<item><![CDATA[<eggs>]]><!-- twelve --></item><empty/></list>"#
+        )
+    );
+    assert_eq!(VecTree::from_xml_str(&xml).unwrap(), tree);
+    assert_eq!(VecTree::<XmlNode>::new().to_xml_string(), "");
+
+    let error = |s| VecTree::from_xml_str(s).unwrap_err();
+    assert!(matches!(error(""), XmlError::NoRoot));
+    assert!(matches!(error("<!-- nothing -->"), XmlError::NoRoot));
+    assert!(matches!(error("<a/><b/>"), XmlError::SeveralRoots));
+    assert!(matches!(error("<a/>text"), XmlError::SeveralRoots));
+    assert!(matches!(error("<a><b></a>"), XmlError::Xml(_)));
+    assert!(matches!(error("<a><b></b>"), XmlError::Xml(_)));
+}
+
+#[cfg(feature = "csv")]
+#[test]
+fn csv_edges() {
+    use vec_tree::CsvEdgesError;
+
+    let read = |csv: &str| VecTree::<i32>::read_csv_edges(csv.as_bytes());
+
+    let (tree, ids) = read("id,parent_id,value\n").unwrap();
+    assert_eq!(tree, VecTree::new());
+    assert!(ids.is_empty());
+
+    // 0-1-3
+    // |
+    // 2
+    let (tree, ids) = read("id,parent_id,value\nc,a,3\nb,r,2\na,r,1\nr,,0\n").unwrap();
+    let root = tree.get_root_index().unwrap();
+    assert_eq!(ids["r"], root);
+    assert_eq!(tree.parent(ids["c"]), Some(ids["a"]));
+    assert_eq!(
+        tree.descendants(root)
+            .map(|node_id| tree[node_id])
+            .collect::<Vec<_>>(),
+        [0, 2, 1, 3]
+    );
+
+    let mut csv = Vec::new();
+    tree.write_csv_edges(&mut csv).unwrap();
+    let (copy, _) = VecTree::<i32>::read_csv_edges(&csv[..]).unwrap();
+    assert_eq!(copy, tree);
+
+    let error = |csv: &str| read(csv).unwrap_err();
+    assert!(matches!(
+        error("id,parent_id,value\na,,x"),
+        CsvEdgesError::InvalidValue { line: 2, .. }
+    ));
+    assert!(matches!(
+        error("id,parent_id,value\na,,0\na,a,1"),
+        CsvEdgesError::DuplicateId { line: 3, .. }
+    ));
+    assert!(matches!(
+        error("id,parent_id,value\na,b,0\nb,a,1"),
+        CsvEdgesError::NoRoot
+    ));
+    assert!(matches!(
+        error("id,parent_id,value\na,,0\nb,c,1"),
+        CsvEdgesError::MissingParent { line: 3, .. }
+    ));
+    assert!(matches!(
+        error("id,parent_id,value\na,,0\nb,b,1"),
+        CsvEdgesError::Cycle { line: 3, .. }
+    ));
+    assert!(matches!(
+        error("id,value\na,0"),
+        CsvEdgesError::InvalidRow { line: 2 }
+    ));
+    assert!(matches!(
+        error("id,parent_id,value\na,,0\nb"),
+        CsvEdgesError::Csv(_)
+    ));
+    assert_eq!(
+        error("id,parent_id,value\na,,0\nb,,1").to_string(),
+        "\"a\" and \"b\" have no parent id, the tree would have several roots"
+    );
+}
+
+#[test]
+fn folded_stacks() {
+    let mut tree = VecTree::new();
+    assert_eq!(tree.to_folded_stacks(|_| 1, |name: &&str| *name), "");
+
+    // a-b-c
+    // | `-d;e
+    // f
+    let a = tree.insert_root("a");
+    let b = tree.insert("b", a);
+    tree.insert("c", b);
+    tree.insert("d;e", b);
+    tree.insert("f", a);
+
+    assert_eq!(
+        tree.to_folded_stacks(|name| name.len() as u64 - 1, |name| *name),
+        "a;b;d_e 2\n"
+    );
+    assert_eq!(
+        tree.to_folded_stacks(|_| 10, |name| name.to_uppercase()),
+        "A 10\nA;B 10\nA;B;C 10\nA;B;D_E 10\nA;F 10\n"
+    );
+}
+
+#[test]
+fn mermaid_flowchart() {
+    let mut tree = VecTree::new();
+    assert_eq!(tree.mermaid(|n: &i32| *n).to_string(), "flowchart TD\n");
+
+    // 0-1-3
+    // |
+    // 2
+    let root_node = tree.insert_root(0);
+    let node_1 = tree.insert(1, root_node);
+    let node_2 = tree.insert(2, root_node);
+    tree.remove(node_2);
+    tree.insert(2, root_node);
+    tree.insert(3, node_1);
+
+    assert_eq!(
+        tree.mermaid(|n| format!("node {}", n))
+            .style(|n| n % 2 == 1, "fill:#f96")
+            .style(|n| *n > 10, "stroke:#333")
+            .style(|n| *n >= 2, "color:red")
+            .to_string(),
+        "flowchart TD\n    \
+         n0[\"node 0\"]\n    \
+         n0 --> n1[\"node 1\"]\n    \
+         n1 --> n3[\"node 3\"]\n    \
+         n0 --> n2[\"node 2\"]\n    \
+         classDef style0 fill:#f96\n    \
+         class n1,n3 style0\n    \
+         classDef style2 color:red\n    \
+         class n3,n2 style2\n"
+    );
+}
+
+#[cfg(feature = "indextree")]
+#[test]
+fn indextree_conversion() {
+    // 0-1-3
+    // |
+    // 2
+    let mut tree = VecTree::new();
+    let root_node = tree.insert_root(0);
+    let node_1 = tree.insert(1, root_node);
+    let node_2 = tree.insert(2, root_node);
+    let node_3 = tree.insert(3, node_1);
+
+    let (arena, node_ids) = tree.clone().into_indextree();
+    assert_eq!(arena.len(), 4);
+    assert_eq!(
+        node_ids[&root_node]
+            .descendants(&arena)
+            .map(|node_id| *arena[node_id].get())
+            .collect::<Vec<_>>(),
+        [0, 1, 3, 2]
+    );
+    assert_eq!(node_ids[&node_3].parent(&arena), Some(node_ids[&node_1]));
+    assert_eq!(node_ids[&node_2].parent(&arena), Some(node_ids[&root_node]));
+
+    let (copy, indices) = VecTree::from_indextree(&arena, node_ids[&root_node]);
+    assert_eq!(copy, tree);
+    assert_eq!(copy[indices[&node_ids[&node_3]]], 3);
+
+    // Only the subtree is copied, its root becoming the root of the tree.
+    let (subtree, _) = VecTree::from_indextree(&arena, node_ids[&node_1]);
+    assert_eq!(subtree.values().cloned().collect::<Vec<_>>(), [1, 3]);
+    assert_eq!(subtree[subtree.get_root_index().unwrap()], 1);
+
+    let arena: indextree::Arena<i32> = VecTree::new().into();
+    assert!(arena.is_empty());
+}
+
+#[cfg(feature = "ego-tree")]
+#[test]
+fn ego_tree_conversion() {
+    assert!(VecTree::<i32>::new().into_ego_tree().is_none());
+
+    // 0-1-3
+    // |
+    // 2
+    let mut tree = VecTree::new();
+    let root_node = tree.insert_root(0);
+    let node_1 = tree.insert(1, root_node);
+    let node_2 = tree.insert(2, root_node);
+    let node_3 = tree.insert(3, node_1);
+
+    let (ego, node_ids) = tree.clone().into_ego_tree().unwrap();
+    assert_eq!(
+        ego.root()
+            .descendants()
+            .map(|node| *node.value())
+            .collect::<Vec<_>>(),
+        [0, 1, 3, 2]
+    );
+    assert_eq!(ego.root().id(), node_ids[&root_node]);
+    let ego_node_3 = ego.get(node_ids[&node_3]).unwrap();
+    assert_eq!(ego_node_3.parent().unwrap().id(), node_ids[&node_1]);
+    let ego_node_2 = ego.get(node_ids[&node_2]).unwrap();
+    assert_eq!(ego_node_2.parent().unwrap().id(), node_ids[&root_node]);
+
+    assert_eq!(VecTree::from(&ego), tree);
+    let (copy, indices) = VecTree::from_ego_tree(ego.root());
+    assert_eq!(copy[indices[&node_ids[&node_3]]], 3);
+
+    // Only the subtree is copied, its root becoming the root of the tree.
+    let (subtree, _) = VecTree::from_ego_tree(ego.get(node_ids[&node_1]).unwrap());
+    assert_eq!(subtree.values().cloned().collect::<Vec<_>>(), [1, 3]);
+    assert_eq!(subtree[subtree.get_root_index().unwrap()], 1);
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn json_document() {
+    use serde_json::json;
+    use vec_tree::{JsonKey, JsonKind};
+
+    let tree = VecTree::from_json_document(&json!({
+        "name": "a",
+        "sizes": [1, 2.5, null],
+        "nested": { "ok": true, "empty": [] },
+    }));
+    let root = tree.get_root_index().unwrap();
+    assert_eq!(tree[root].key, JsonKey::Root);
+    assert_eq!(tree[root].kind, JsonKind::Object);
+
+    let field = |name: &str| JsonKey::Field(name.to_string());
+    let number = |value| JsonKind::Number(serde_json::Number::from_f64(value).unwrap());
+    assert_eq!(
+        tree.descendants(root)
+            .skip(1)
+            .map(|node_id| (tree[node_id].key.clone(), tree[node_id].kind.clone()))
+            .collect::<Vec<_>>(),
+        [
+            (field("name"), JsonKind::String("a".to_string())),
+            (field("nested"), JsonKind::Object),
+            (field("empty"), JsonKind::Array),
+            (field("ok"), JsonKind::Bool(true)),
+            (field("sizes"), JsonKind::Array),
+            (JsonKey::Item(0), JsonKind::Number(1.into())),
+            (JsonKey::Item(1), number(2.5)),
+            (JsonKey::Item(2), JsonKind::Null),
+        ]
+    );
+
+    let tree = VecTree::from_json_document(&json!(null));
+    let root = tree.get_root_index().unwrap();
+    assert_eq!(tree.subtree_size(root), 1);
+    assert_eq!(tree[root].kind, JsonKind::Null);
+}
+
+#[cfg(feature = "fs")]
+#[test]
+fn load_dir() {
+    use std::fs;
+    use vec_tree::{DirEntryKind, DirOptions, SymlinkPolicy};
+
+    let dir = std::env::temp_dir().join(format!("vec_tree_load_dir_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("b/c")).unwrap();
+    fs::write(dir.join("a.txt"), "hello").unwrap();
+    fs::write(dir.join("b/c/d.txt"), "hi").unwrap();
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::symlink(&dir, dir.join("b/loop")).unwrap();
+        std::os::unix::fs::symlink(dir.join("a.txt"), dir.join("e.txt")).unwrap();
+    }
+
+    let names = |tree: &VecTree<vec_tree::DirEntryData>| {
+        let root = tree.get_root_index().unwrap();
+        tree.descendants(root)
+            .skip(1)
+            .map(|node_id| tree[node_id].name.to_str().unwrap().to_string())
+            .collect::<Vec<_>>()
+    };
+
+    let (tree, errors) = VecTree::from_dir(&dir, &DirOptions::new()).unwrap();
+    assert!(errors.is_empty());
+    let root = tree.get_root_index().unwrap();
+    assert_eq!(tree[root].path, dir);
+    assert_eq!(tree[root].kind, DirEntryKind::Dir);
+    let a = tree.first_child(root).unwrap();
+    assert_eq!(tree[a].path, dir.join("a.txt"));
+    assert_eq!(tree[a].kind, DirEntryKind::File);
+    assert_eq!(tree[a].size, 5);
+
+    let (shallow, _) = VecTree::from_dir(&dir, &DirOptions::new().max_depth(1)).unwrap();
+    let (skipped, _) =
+        VecTree::from_dir(&dir, &DirOptions::new().symlinks(SymlinkPolicy::Skip)).unwrap();
+    assert_eq!(names(&skipped), ["a.txt", "b", "c", "d.txt"]);
+
+    #[cfg(unix)]
+    {
+        assert_eq!(names(&tree), ["a.txt", "b", "c", "d.txt", "loop", "e.txt"]);
+        assert_eq!(names(&shallow), ["a.txt", "b", "e.txt"]);
+
+        let e = tree.last_child(root).unwrap();
+        assert_eq!(tree[e].kind, DirEntryKind::Symlink);
+
+        let options = DirOptions::new().symlinks(SymlinkPolicy::Follow);
+        let (followed, errors) = VecTree::from_dir(&dir, &options).unwrap();
+        assert_eq!(names(&followed), ["a.txt", "b", "c", "d.txt", "e.txt"]);
+        let e = followed
+            .last_child(followed.get_root_index().unwrap())
+            .unwrap();
+        assert_eq!(followed[e].kind, DirEntryKind::File);
+        assert_eq!(followed[e].size, 5);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].path, dir.join("b/loop"));
+    }
+
+    assert!(VecTree::from_dir(dir.join("missing"), &DirOptions::new()).is_err());
+
+    fs::remove_dir_all(&dir).unwrap();
+}